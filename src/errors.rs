@@ -10,6 +10,59 @@ pub enum IOError {
     IO(#[from] std::io::Error),
 }
 
+/// 项目树相关操作的错误
+#[derive(Error, Debug)]
+pub enum AruiError {
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+    #[error("Project tree is not built, please build by `build()` first")]
+    NotBuilt,
+    #[error("Node not found: {0}")]
+    NodeNotFound(String),
+    #[error("Operation cancelled")]
+    Cancelled,
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+    #[cfg(feature = "parallel")]
+    #[error("Failed to build thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
+    #[cfg(feature = "git")]
+    #[error("Git error: {0}")]
+    Git(#[from] git2::Error),
+    #[cfg(feature = "serde")]
+    #[error("Unsupported export schema version: {0}")]
+    UnsupportedSchema(u32),
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "yaml")]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+    #[cfg(feature = "msgpack")]
+    #[error("Invalid cache file: {0}")]
+    InvalidCache(String),
+    #[cfg(feature = "index")]
+    #[error("Invalid index archive: {0}")]
+    InvalidIndex(String),
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+}
+
+impl From<IOError> for AruiError {
+    fn from(error: IOError) -> Self {
+        match error {
+            IOError::InvalidPath(path) => AruiError::InvalidPath(path),
+            IOError::IO(error) => AruiError::IO(error),
+        }
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use crate::errors::IOError;
@@ -0,0 +1,196 @@
+//! # 文件系统抽象
+//! 项目树的构建与总结均通过 `FileSystem` 访问文件，而不直接依赖磁盘。
+//! - `DiskFs`：基于 `std::fs` 的真实磁盘实现，为默认实现
+//! - `MemoryFs`：基于内存的虚拟文件系统，可用于分析虚拟目录树或编写确定性的测试，
+//!   开启 `zip` / `tar` 特性后也可以直接从归档文件加载
+//! - `PrefetchFs`：开启 `jwalk` 特性后可用，构建前并行预取整个目录的结构，适用于扫描海量路径
+pub mod archive;
+pub mod disk;
+pub mod memory;
+#[cfg(feature = "jwalk")]
+pub mod prefetch;
+
+pub use disk::DiskFs;
+pub use memory::MemoryFs;
+#[cfg(feature = "jwalk")]
+pub use prefetch::PrefetchFs;
+
+use std::io::{Read, Result};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// 文件类型
+/// 除普通文件与目录外，其余类型（如管道、套接字、设备文件）统称为特殊文件，
+/// 读取特殊文件可能无限阻塞（如没有写入端的 FIFO），因此构建与总结时不会读取其内容
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum FileKind {
+    /// 普通文件
+    #[default]
+    Regular,
+    /// 目录
+    Directory,
+    /// 命名管道（FIFO）
+    Fifo,
+    /// 套接字
+    Socket,
+    /// 块设备
+    BlockDevice,
+    /// 字符设备
+    CharDevice,
+    /// 其他无法识别的类型
+    Other,
+}
+
+impl FileKind {
+    /// 根据 `std::fs::FileType` 获取文件类型，符号链接需预先跟随
+    pub(crate) fn of(file_type: &std::fs::FileType) -> Self {
+        if file_type.is_dir() {
+            return FileKind::Directory;
+        }
+        if file_type.is_file() {
+            return FileKind::Regular;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if file_type.is_fifo() {
+                return FileKind::Fifo;
+            }
+            if file_type.is_socket() {
+                return FileKind::Socket;
+            }
+            if file_type.is_block_device() {
+                return FileKind::BlockDevice;
+            }
+            if file_type.is_char_device() {
+                return FileKind::CharDevice;
+            }
+        }
+        FileKind::Other
+    }
+
+    /// 是否为特殊文件，即既不是普通文件也不是目录
+    pub fn is_special(&self) -> bool {
+        !matches!(self, FileKind::Regular | FileKind::Directory)
+    }
+}
+
+impl std::fmt::Display for FileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FileKind::Regular => "file",
+            FileKind::Directory => "directory",
+            FileKind::Fifo => "fifo",
+            FileKind::Socket => "socket",
+            FileKind::BlockDevice => "block device",
+            FileKind::CharDevice => "char device",
+            FileKind::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 获取 `std::fs::Metadata` 中的权限位，非 Unix 平台为空
+pub(crate) fn mode_of(metadata: &std::fs::Metadata) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Some(metadata.permissions().mode() & 0o7777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// 获取 `std::fs::Metadata` 中实际分配的磁盘大小，即已分配的块数（`st_blocks`）乘以 512 字节，非 Unix 平台为空
+pub(crate) fn allocated_of(metadata: &std::fs::Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(metadata.blocks() * 512)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// 获取 `std::fs::Metadata` 中的属主与属组 ID，非 Unix 平台均为空
+pub(crate) fn owner_of(metadata: &std::fs::Metadata) -> (Option<u32>, Option<u32>) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        (Some(metadata.uid()), Some(metadata.gid()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        (None, None)
+    }
+}
+
+/// 文件元数据
+/// 仅包含项目树构建与总结所需的字段
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FsMetadata {
+    /// 是否是目录
+    pub is_dir: bool,
+    /// 文件大小（字节），目录为文件系统报告的大小
+    pub len: u64,
+    /// 实际分配的磁盘大小（字节），稀疏文件可能小于 `len`，小文件通常大于 `len`，非 Unix 平台为空
+    pub allocated: Option<u64>,
+    /// 路径本身是否为符号链接，此时其余字段描述的是链接目标
+    pub is_symlink: bool,
+    /// 文件类型
+    pub kind: FileKind,
+    /// 最后修改时间，文件系统不支持时为空
+    pub modified: Option<SystemTime>,
+    /// 创建时间，平台或文件系统不支持时为空
+    pub created: Option<SystemTime>,
+    /// 权限位（如 `0o644`，包含 setuid 等特殊位），非 Unix 平台为空
+    pub mode: Option<u32>,
+    /// 属主的用户 ID，非 Unix 平台为空
+    pub uid: Option<u32>,
+    /// 属组的组 ID，非 Unix 平台为空
+    pub gid: Option<u32>,
+}
+
+/// 文件系统接口
+/// - metadata：获取路径的元数据
+/// - read_dir：列出目录下的直接子路径
+/// - open：以只读方式打开文件
+/// - read_link：读取符号链接指向的目标
+/// - canonicalize：获取路径的规范形式，用于识别符号链接造成的循环
+pub trait FileSystem: Send + Sync {
+    /// 获取路径的元数据，符号链接会被跟随
+    fn metadata(&self, path: &Path) -> Result<FsMetadata>;
+
+    /// 列出目录下的直接子路径，返回的路径以 `path` 为前缀
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
+
+    /// 以只读方式打开文件
+    fn open(&self, path: &Path) -> Result<Box<dyn Read + '_>>;
+
+    /// 读取符号链接指向的目标，路径不是符号链接时返回错误，默认不支持符号链接
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("not a symbolic link: {}", path.display()),
+        ))
+    }
+
+    /// 获取路径的规范形式，解析所有符号链接，默认原样返回
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        Ok(path.to_path_buf())
+    }
+
+    /// 路径是否存在
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+}
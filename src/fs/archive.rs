@@ -0,0 +1,229 @@
+//! # 归档文件系统
+//! 将 zip / tar / tar.gz 归档直接读入 `MemoryFs`，无需解压到磁盘即可构建项目树。
+//! 需要开启对应的 `zip` 或 `tar` 特性，归档中的条目会被挂载到指定的根路径之下。
+use crate::fs::MemoryFs;
+#[cfg(any(feature = "zip", feature = "tar"))]
+use std::io::Read;
+use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
+#[cfg(feature = "tar")]
+use std::path::{Component, PathBuf};
+
+impl MemoryFs {
+    /// 读取 zip 归档，条目挂载到 `mount` 之下
+    #[cfg(feature = "zip")]
+    pub fn from_zip<R, P>(reader: R, mount: P) -> Result<Self>
+    where
+        R: Read + std::io::Seek,
+        P: AsRef<Path>,
+    {
+        let mount = mount.as_ref();
+        let mut archive = zip::ZipArchive::new(reader)?;
+        let mut fs = MemoryFs::new().add_dir(mount);
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            // 跳过指向归档之外的非法路径
+            let Some(name) = file.enclosed_name() else {
+                continue;
+            };
+            if file.is_dir() {
+                fs = fs.add_dir(mount.join(name));
+            } else {
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents)?;
+                fs = fs.add_file(mount.join(name), contents);
+            }
+        }
+        Ok(fs)
+    }
+
+    /// 读取 tar 归档，条目挂载到 `mount` 之下，仅保留普通文件与目录
+    #[cfg(feature = "tar")]
+    pub fn from_tar<R, P>(reader: R, mount: P) -> Result<Self>
+    where
+        R: Read,
+        P: AsRef<Path>,
+    {
+        let mount = mount.as_ref();
+        let mut archive = tar::Archive::new(reader);
+        let mut fs = MemoryFs::new().add_dir(mount);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            // 跳过指向归档之外的非法路径（包含 `..`、绝对路径等）
+            let Some(name) = enclosed_name(&entry.path()?) else {
+                continue;
+            };
+            let path = mount.join(name);
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_dir() {
+                fs = fs.add_dir(path);
+            } else if entry_type.is_file() {
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents)?;
+                fs = fs.add_file(path, contents);
+            }
+        }
+        Ok(fs)
+    }
+
+    /// 读取 gzip 压缩的 tar 归档，条目挂载到 `mount` 之下
+    #[cfg(feature = "tar")]
+    pub fn from_tar_gz<R, P>(reader: R, mount: P) -> Result<Self>
+    where
+        R: Read,
+        P: AsRef<Path>,
+    {
+        Self::from_tar(flate2::read::GzDecoder::new(reader), mount)
+    }
+
+    /// 根据扩展名读取磁盘上的归档文件，条目挂载到以归档文件名命名的根路径之下
+    /// 支持 `.zip`、`.tar`、`.tar.gz`、`.tgz`，未开启对应特性时返回 `Unsupported` 错误
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use arui_core::fs::{FileSystem, MemoryFs};
+    /// use std::path::Path;
+    ///
+    /// let fs = MemoryFs::from_archive("dist.tar.gz").unwrap();
+    /// assert!(fs.metadata(Path::new("dist.tar.gz")).unwrap().is_dir);
+    /// ```
+    pub fn from_archive<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mount = path
+            .file_name()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "archive path has no file name"))?;
+        let name = mount.to_string_lossy().to_lowercase();
+        #[cfg(feature = "zip")]
+        if name.ends_with(".zip") {
+            return Self::from_zip(std::fs::File::open(path)?, mount);
+        }
+        #[cfg(feature = "tar")]
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            return Self::from_tar_gz(std::fs::File::open(path)?, mount);
+        }
+        #[cfg(feature = "tar")]
+        if name.ends_with(".tar") {
+            return Self::from_tar(std::fs::File::open(path)?, mount);
+        }
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("unsupported archive format: {}", name),
+        ))
+    }
+}
+
+/// 将归档中的条目路径转换为相对路径，去除 `.`；包含 `..`、根路径或盘符前缀时返回空
+#[cfg(feature = "tar")]
+fn enclosed_name(path: &Path) -> Option<PathBuf> {
+    let mut name = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => name.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    (!name.as_os_str().is_empty()).then_some(name)
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "zip", feature = "tar"))]
+    use crate::fs::FileSystem;
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn test_from_zip() {
+        use std::io::{Cursor, Write};
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        writer.add_directory("src/", options).unwrap();
+        writer.start_file("src/lib.rs", options).unwrap();
+        writer.write_all(b"pub mod tree;\n").unwrap();
+        writer.add_directory("empty/", options).unwrap();
+        let cursor = writer.finish().unwrap();
+
+        let fs = MemoryFs::from_zip(cursor, "dist.zip").unwrap();
+        assert_eq!(fs.read_dir(Path::new("dist.zip")).unwrap().len(), 2);
+        assert_eq!(
+            fs.metadata(Path::new("dist.zip/src/lib.rs")).unwrap().len,
+            14
+        );
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn test_from_tar_gz() {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let contents = b"fn main() {}\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "bin/main.rs", &contents[..])
+            .unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+
+        let fs = MemoryFs::from_tar_gz(archive.as_slice(), "dist.tar.gz").unwrap();
+        assert!(fs.metadata(Path::new("dist.tar.gz/bin")).unwrap().is_dir);
+        assert_eq!(
+            fs.metadata(Path::new("dist.tar.gz/bin/main.rs"))
+                .unwrap()
+                .len,
+            13
+        );
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn test_tar_path_traversal() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for name in [
+            "../escape.rs",
+            "/etc/passwd",
+            "./src/../../escape.rs",
+            "./src/lib.rs",
+        ] {
+            let mut header = tar::Header::new_gnu();
+            // `set_path` 会拒绝 `..`，直接写入名称字段以构造恶意归档
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(4);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, &b"evil"[..]).unwrap();
+        }
+        let archive = builder.into_inner().unwrap();
+
+        let fs = MemoryFs::from_tar(archive.as_slice(), "mount").unwrap();
+        assert_eq!(
+            fs.read_dir(Path::new("mount")).unwrap(),
+            vec![Path::new("mount/src")]
+        );
+        assert!(fs.metadata(Path::new("mount/src/lib.rs")).is_ok());
+        for path in [
+            "escape.rs",
+            "/etc/passwd",
+            "mount/../escape.rs",
+            "/escape.rs",
+        ] {
+            assert!(fs.metadata(Path::new(path)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unsupported_archive() {
+        let error = MemoryFs::from_archive("dist.rar").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+    }
+}
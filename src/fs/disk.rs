@@ -0,0 +1,99 @@
+//! # 磁盘文件系统
+//! 基于 `std::fs` 的 `FileSystem` 实现，项目树默认使用该实现。
+use crate::fs::{FileKind, FileSystem, FsMetadata, allocated_of, mode_of, owner_of};
+use crate::utils::simplify_path;
+use std::fs;
+use std::io::{Read, Result};
+use std::path::{Path, PathBuf};
+
+/// 磁盘文件系统
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiskFs;
+
+impl FileSystem for DiskFs {
+    fn metadata(&self, path: &Path) -> Result<FsMetadata> {
+        // 先获取路径本身的元数据，仅在路径为符号链接时再跟随到链接目标
+        let link = fs::symlink_metadata(path)?;
+        let is_symlink = link.is_symlink();
+        let metadata = if is_symlink {
+            fs::metadata(path)?
+        } else {
+            link
+        };
+        let (uid, gid) = owner_of(&metadata);
+        Ok(FsMetadata {
+            is_dir: metadata.is_dir(),
+            len: metadata.len(),
+            allocated: allocated_of(&metadata),
+            is_symlink,
+            kind: FileKind::of(&metadata.file_type()),
+            modified: metadata.modified().ok(),
+            created: metadata.created().ok(),
+            mode: mode_of(&metadata),
+            uid,
+            gid,
+        })
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect()
+    }
+
+    fn open(&self, path: &Path) -> Result<Box<dyn Read + '_>> {
+        Ok(Box::new(fs::File::open(path)?))
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        fs::read_link(path)
+    }
+
+    /// Windows 上会尽量去除扩展长度前缀 `\\?\`，便于展示以及与用户提供的路径比较
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        fs::canonicalize(path).map(simplify_path)
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_fs() {
+        let fs = DiskFs;
+        let dir = Path::new("./tests/examples/tree/summary");
+        assert!(fs.metadata(dir).unwrap().is_dir);
+        assert_eq!(fs.read_dir(dir).unwrap().len(), 3);
+        let mut contents = String::new();
+        fs.open(&dir.join("test.txt"))
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(
+            contents.len() as u64,
+            fs.metadata(&dir.join("test.txt")).unwrap().len
+        );
+        assert!(!fs.exists(Path::new("/not_exist")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_special_file() {
+        let fs = DiskFs;
+        assert_eq!(
+            fs.metadata(Path::new("/dev/null")).unwrap().kind,
+            FileKind::CharDevice
+        );
+        assert_eq!(
+            fs.metadata(Path::new("./src")).unwrap().kind,
+            FileKind::Directory
+        );
+        assert_eq!(
+            fs.metadata(Path::new("./Cargo.toml")).unwrap().kind,
+            FileKind::Regular
+        );
+    }
+}
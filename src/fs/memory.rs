@@ -0,0 +1,221 @@
+//! # 内存文件系统
+//! 基于内存的 `FileSystem` 实现，目录结构与文件内容均保存在内存中，不访问真实磁盘。
+//! 路径在存取时统一规范化（去除 `.`、解析 `..`），空路径视为根目录。
+use crate::fs::{FileKind, FileSystem, FsMetadata};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Error, ErrorKind, Read, Result};
+use std::ops::Bound;
+use std::path::{Component, Path, PathBuf};
+
+/// 内存中的条目
+#[derive(Debug, Clone, PartialEq)]
+enum MemoryEntry {
+    /// 目录
+    Dir,
+    /// 文件及其内容
+    File(Vec<u8>),
+}
+
+/// 内存文件系统
+/// 添加文件时会自动创建其所有父目录
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::fs::MemoryFs;
+/// use arui_core::ProjectTree;
+///
+/// let fs = MemoryFs::new()
+///     .add_file("project/src/main.rs", "fn main() {}\n")
+///     .add_file("project/README.md", "# demo\n")
+///     .add_dir("project/empty");
+/// let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+/// tree.build().unwrap();
+/// tree.summarize().unwrap();
+/// assert_eq!(tree.root.unwrap().summary.count, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    entries: BTreeMap<PathBuf, MemoryEntry>,
+}
+
+impl MemoryFs {
+    /// 创建一个空的内存文件系统
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加文件（接受 &str、String、Vec<u8> 等内容），已存在的文件会被覆盖
+    pub fn add_file<P, C>(mut self, path: P, contents: C) -> Self
+    where
+        P: AsRef<Path>,
+        C: Into<Vec<u8>>,
+    {
+        let path = normalize(path.as_ref());
+        self.insert_parents(&path);
+        self.entries
+            .insert(path, MemoryEntry::File(contents.into()));
+        self
+    }
+
+    /// 添加目录
+    pub fn add_dir<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = normalize(path.as_ref());
+        self.insert_parents(&path);
+        self.entries.insert(path, MemoryEntry::Dir);
+        self
+    }
+
+    /// 创建路径的所有父目录
+    fn insert_parents(&mut self, path: &Path) {
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            self.entries
+                .entry(ancestor.to_path_buf())
+                .or_insert(MemoryEntry::Dir);
+        }
+    }
+
+    /// 查找条目，空路径视为根目录
+    fn entry(&self, path: &Path) -> Result<&MemoryEntry> {
+        let path = normalize(path);
+        if is_root(&path) {
+            return Ok(&MemoryEntry::Dir);
+        }
+        self.entries.get(&path).ok_or_else(|| not_found(&path))
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn metadata(&self, path: &Path) -> Result<FsMetadata> {
+        Ok(match self.entry(path)? {
+            MemoryEntry::Dir => FsMetadata {
+                is_dir: true,
+                kind: FileKind::Directory,
+                ..Default::default()
+            },
+            MemoryEntry::File(contents) => FsMetadata {
+                len: contents.len() as u64,
+                ..Default::default()
+            },
+        })
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        if let MemoryEntry::File(_) = self.entry(path)? {
+            return Err(Error::other(format!("not a directory: {}", path.display())));
+        }
+        let dir = normalize(path);
+        // 子路径在有序表中连续排列于目录之后
+        let children = self
+            .entries
+            .range::<PathBuf, _>((Bound::Excluded(&dir), Bound::Unbounded))
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(&dir))
+            .filter(|key| key.parent() == Some(dir.as_path()))
+            .filter_map(|key| key.file_name())
+            .map(|name| path.join(name))
+            .collect();
+        Ok(children)
+    }
+
+    fn open(&self, path: &Path) -> Result<Box<dyn Read + '_>> {
+        match self.entry(path)? {
+            MemoryEntry::File(contents) => Ok(Box::new(Cursor::new(contents.as_slice()))),
+            MemoryEntry::Dir => Err(Error::other(format!("is a directory: {}", path.display()))),
+        }
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        self.entry(path)?;
+        Ok(normalize(path))
+    }
+}
+
+/// 规范化路径，去除 `.` 并解析 `..`
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// 是否为根目录（空路径或仅包含根）
+fn is_root(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::RootDir | Component::Prefix(_)))
+}
+
+fn not_found(path: &Path) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("no such file or directory: {}", path.display()),
+    )
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MemoryFs {
+        MemoryFs::new()
+            .add_file("root/a.txt", "a\nb\n")
+            .add_file("./root/src/lib.rs", "pub mod a;\n")
+            .add_file("root/src-gen/out.rs", "")
+            .add_dir("root/empty")
+    }
+
+    #[test]
+    fn test_metadata() {
+        let fs = sample();
+        assert!(fs.metadata(Path::new("root")).unwrap().is_dir);
+        assert!(fs.metadata(Path::new("./root/src")).unwrap().is_dir);
+        assert_eq!(fs.metadata(Path::new("root/a.txt")).unwrap().len, 4);
+        assert!(fs.metadata(Path::new("")).unwrap().is_dir);
+        assert!(!fs.exists(Path::new("root/missing")));
+    }
+
+    #[test]
+    fn test_read_dir() {
+        let fs = sample();
+        let children = fs.read_dir(Path::new("./root")).unwrap();
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("./root/a.txt"),
+                PathBuf::from("./root/empty"),
+                PathBuf::from("./root/src"),
+                PathBuf::from("./root/src-gen"),
+            ]
+        );
+        assert_eq!(fs.read_dir(Path::new("root/src")).unwrap().len(), 1);
+        assert!(fs.read_dir(Path::new("root/empty")).unwrap().is_empty());
+        assert!(fs.read_dir(Path::new("root/a.txt")).is_err());
+    }
+
+    #[test]
+    fn test_open() {
+        let fs = sample();
+        let mut contents = String::new();
+        fs.open(Path::new("root/src/../a.txt"))
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "a\nb\n");
+        assert!(fs.open(Path::new("root/src")).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! # 预取文件系统
+//! 需要开启 `jwalk` 特性。
+//! 扫描上百万个路径时，逐个目录调用 `std::fs::read_dir` 与 `metadata` 往往成为瓶颈。
+//! `PrefetchFs` 在构建前借助 `jwalk` 多线程并行遍历整个目录，预先获取所有目录的子路径与元数据，
+//! 之后构建过程中的查询直接从内存中返回；未命中的查询（如符号链接、读取失败的目录）以及文件内容的读取仍交由磁盘处理。
+use crate::fs::{DiskFs, FileKind, FileSystem, FsMetadata, allocated_of, mode_of, owner_of};
+use std::collections::HashMap;
+use std::io::{Read, Result};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// 并行遍历时收集的结果
+#[derive(Default)]
+struct Prefetched {
+    /// 目录与其子路径
+    dirs: HashMap<PathBuf, Vec<PathBuf>>,
+    /// 路径与其元数据，不包含符号链接
+    metadata: HashMap<PathBuf, FsMetadata>,
+}
+
+/// 预取文件系统
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::fs::PrefetchFs;
+/// use arui_core::tree::root::ProjectTree;
+///
+/// let fs = PrefetchFs::scan("./src");
+/// let mut project = ProjectTree::new("test", "./src", None).with_fs(fs);
+/// project.analyze().unwrap();
+/// ```
+#[derive(Default)]
+pub struct PrefetchFs {
+    prefetched: Prefetched,
+}
+
+impl PrefetchFs {
+    /// 并行遍历根路径，预取所有目录的子路径与元数据
+    /// 遍历过程中无法读取的目录不会被缓存，之后访问时交由磁盘处理并返回对应的错误
+    pub fn scan<P: AsRef<Path>>(root: P) -> Self {
+        let prefetched = Arc::new(Mutex::new(Prefetched::default()));
+        let sink = prefetched.clone();
+        let walk = jwalk::WalkDirGeneric::<((), Option<FsMetadata>)>::new(root)
+            .skip_hidden(false)
+            .follow_links(false)
+            .process_read_dir(move |_, dir, _, children| {
+                // 在工作线程中获取元数据，部分子路径读取失败时不缓存该目录
+                let mut paths = Vec::with_capacity(children.len());
+                let mut metadata = Vec::with_capacity(children.len());
+                for child in children.iter_mut() {
+                    let Ok(child) = child else {
+                        return;
+                    };
+                    let path = child.path();
+                    if !child.file_type().is_symlink()
+                        && let Ok(meta) = child.metadata()
+                    {
+                        metadata.push((path.clone(), to_metadata(&meta)));
+                    }
+                    paths.push(path);
+                }
+                let mut prefetched = sink.lock().unwrap();
+                prefetched.dirs.insert(dir.to_path_buf(), paths);
+                prefetched.metadata.extend(metadata);
+            });
+        // 驱动遍历，结果已在回调中收集
+        // 遍历结束后线程池中可能仍持有回调闭包，因此直接取出收集的结果，而不是等待引用计数归一
+        walk.into_iter().for_each(drop);
+        let prefetched = std::mem::take(&mut *prefetched.lock().unwrap());
+        PrefetchFs { prefetched }
+    }
+
+    /// 已预取的目录数量
+    pub fn dir_count(&self) -> usize {
+        self.prefetched.dirs.len()
+    }
+}
+
+/// 转换为文件元数据
+fn to_metadata(metadata: &std::fs::Metadata) -> FsMetadata {
+    let (uid, gid) = owner_of(metadata);
+    FsMetadata {
+        is_dir: metadata.is_dir(),
+        len: metadata.len(),
+        allocated: allocated_of(metadata),
+        is_symlink: false,
+        kind: FileKind::of(&metadata.file_type()),
+        modified: metadata.modified().ok(),
+        created: metadata.created().ok(),
+        mode: mode_of(metadata),
+        uid,
+        gid,
+    }
+}
+
+impl FileSystem for PrefetchFs {
+    fn metadata(&self, path: &Path) -> Result<FsMetadata> {
+        match self.prefetched.metadata.get(path) {
+            Some(metadata) => Ok(metadata.clone()),
+            None => DiskFs.metadata(path),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        match self.prefetched.dirs.get(path) {
+            Some(paths) => Ok(paths.clone()),
+            None => DiskFs.read_dir(path),
+        }
+    }
+
+    fn open(&self, path: &Path) -> Result<Box<dyn Read + '_>> {
+        DiskFs.open(path)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        DiskFs.read_link(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        DiskFs.canonicalize(path)
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefetch_fs() {
+        let root = Path::new("./tests/examples/tree");
+        let fs = PrefetchFs::scan(root);
+        assert!(fs.dir_count() > 1);
+        let summary = root.join("summary");
+        assert!(fs.prefetched.dirs.contains_key(&summary));
+        // 与磁盘的结果一致
+        let mut paths = fs.read_dir(&summary).unwrap();
+        let mut expected = DiskFs.read_dir(&summary).unwrap();
+        paths.sort();
+        expected.sort();
+        assert_eq!(paths, expected);
+        let file = summary.join("test.txt");
+        assert_eq!(fs.metadata(&file).unwrap(), DiskFs.metadata(&file).unwrap());
+        // 未预取的路径交由磁盘处理
+        assert!(fs.metadata(Path::new("./Cargo.toml")).is_ok());
+        assert!(fs.read_dir(Path::new("./not_exist")).is_err());
+    }
+}
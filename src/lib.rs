@@ -9,17 +9,77 @@
 //! - [ ] 提供更多总结信息的获取
 #![allow(dead_code)]
 
+/// 项目树相关错误
+pub use errors::AruiError;
+/// 紧凑项目树相关
+pub use tree::arena::{ArenaNode, ArenaTree, NodeId};
+/// 总结缓存相关
+pub use tree::cache::SummaryCache;
+/// 取消操作相关
+pub use tree::cancel::CancelToken;
+/// 语言统计表相关
+pub use tree::cloc::{LanguageRow, TableStyle};
 /// 项目配置相关
-pub use tree::config::ProjectConfig;
+pub use tree::config::{ChildOrder, DEFAULT_MARKERS, ProjectConfig, WalkStrategy};
+/// 项目树变更相关
+pub use tree::diff::{ChangeSet, MetricChange, Metrics};
+/// 重复文件检测相关
+#[cfg(feature = "hash")]
+pub use tree::duplicate::DuplicateGroup;
+/// 文本编码相关
+pub use tree::encoding::Encoding;
+/// 自定义导出相关
+pub use tree::export::TreeExporter;
+/// 树图导出相关
+#[cfg(feature = "json")]
+pub use tree::flare::FlareMetric;
+/// 多根项目相关
+pub use tree::forest::ProjectForest;
+/// 项目树新鲜度相关
+pub use tree::freshness::Freshness;
+/// Git 提交信息相关
+#[cfg(feature = "git")]
+pub use tree::git::GitCommit;
+/// 项目健康报告相关
+pub use tree::health::{HealthReport, HealthRule, RuleResult, RuleScore};
+/// 图片信息相关
+pub use tree::image::{ImageFormat, ImageInfo};
+/// JSON 导出选项相关
+#[cfg(feature = "json")]
+pub use tree::json::JsonOptions;
+/// 语言识别相关
+pub use tree::language::Language;
+/// 清单文件相关
+#[cfg(feature = "manifest")]
+pub use tree::manifest::{ManifestInfo, ManifestKind};
 /// 项目树节点相关
-pub use tree::node::TreeNode;
+pub use tree::node::{NodeMarker, TreeNode};
+/// 代码来源相关
+pub use tree::origin::CodeOrigin;
+/// 属主信息相关
+#[cfg(feature = "unix-metadata")]
+pub use tree::owner::Owner;
+/// 进度报告相关
+pub use tree::progress::{ProgressEvent, ProgressReporter};
 /// 项目树与公开 API
 pub use tree::root::ProjectTree;
+/// 分析会话相关
+#[cfg(feature = "parallel")]
+pub use tree::session::AnalysisSession;
+/// 扫描统计相关
+pub use tree::stats::BuildStats;
+/// 流式分析相关
+pub use tree::stream::DirRecord;
 /// 项目树节点总结信息相关
-pub use tree::summary::NodeSummary;
+pub use tree::summary::{
+    ExtStats, LineEnding, LineEndingStats, LineStats, NodeSummary, SizeHistogram,
+};
 /// 项目树可视化
 pub use tree::visible::ProjectTreeVisible;
+/// 警告相关
+pub use tree::warning::{BuildWarning, Warning, WarningKind};
 
 pub mod errors;
+pub mod fs;
 pub mod tree;
 pub mod utils;
@@ -1,8 +1,59 @@
 //! # 项目树模块
 //! 该模块为 `ARUI-CORE` 的核心，负责根据配置生成项目树、获取节点总结信息。
-//! 而在该模块中，`root` 模块为项目树入口，`node` 模块为树节点，`summary` 模块为总结信息。
+//! 而在该模块中，`root` 模块为项目树入口，`node` 模块为树节点，`summary` 模块为总结信息，`builder` 与 `summarizer` 模块分别负责遍历构建节点和获取总结信息。
+pub mod arena;
+mod builder;
+pub mod cache;
+pub mod cancel;
+pub mod checkpoint;
+#[cfg(feature = "hash")]
+pub mod checksum;
+pub mod cloc;
 pub mod config;
+pub mod csv;
+pub mod diff;
+pub mod du;
+#[cfg(feature = "hash")]
+pub mod duplicate;
+pub mod encoding;
+pub mod export;
+#[cfg(feature = "json")]
+pub mod flare;
+pub mod forest;
+pub mod freshness;
+#[cfg(feature = "git")]
+pub mod git;
+pub mod health;
+pub mod image;
+#[cfg(feature = "index")]
+pub mod index;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod language;
+pub mod license;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+pub mod mime;
+#[cfg(feature = "json")]
+pub mod ndjson;
 pub mod node;
+pub mod origin;
+#[cfg(feature = "unix-metadata")]
+pub mod owner;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod progress;
 pub mod root;
+#[cfg(feature = "serde")]
+pub mod schema;
+#[cfg(feature = "parallel")]
+pub mod session;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod stream;
+mod summarizer;
 pub mod summary;
 pub mod visible;
+pub mod warning;
+pub mod xml;
@@ -0,0 +1,314 @@
+//! # 紧凑项目树
+//! 嵌套的 `TreeNode` 为每个节点单独分配子节点列表并保存完整路径，节点数量达到数十万时内存占用较高。
+//! `ArenaTree` 将所有节点连续存放在同一个数组中，按广度优先顺序排列，同一目录的子节点总是相邻，
+//! 节点仅保存自身名称、父节点下标与子节点下标范围，完整路径在需要时根据祖先链拼接。
+use crate::fs::FileKind;
+use crate::tree::node::{NodeMarker, TreeNode};
+use crate::tree::summary::NodeSummary;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// 节点在 `ArenaTree` 中的下标
+pub type NodeId = usize;
+
+/// 紧凑项目树节点
+/// - name：节点名称，根节点为完整的根路径
+/// - is_dir：是否是目录
+/// - kind：文件类型
+/// - parent：父节点下标
+/// - children：子节点下标范围
+/// - summary：节点总结信息
+/// - marker：节点标记
+/// - symlink_target：符号链接指向的目标
+#[derive(Debug, Clone)]
+pub struct ArenaNode {
+    /// 节点名称，根节点为完整的根路径
+    pub name: String,
+    /// 是否是目录
+    pub is_dir: bool,
+    /// 文件类型
+    pub kind: FileKind,
+    /// 父节点下标，根节点为空
+    pub parent: Option<NodeId>,
+    /// 子节点下标范围，文件及尚未加载的目录为空
+    pub children: Option<Range<NodeId>>,
+    /// 节点总结信息
+    pub summary: NodeSummary,
+    /// 节点标记
+    pub marker: Option<NodeMarker>,
+    /// 符号链接指向的目标，不是符号链接时为空
+    pub symlink_target: Option<String>,
+}
+
+/// 紧凑项目树，根节点下标总是为 0
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::arena::ArenaTree;
+/// use arui_core::tree::root::ProjectTree;
+///
+/// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+/// let arena = project.to_arena().unwrap();
+/// let id = arena.find("./src/tree/node.rs").unwrap();
+/// assert_eq!(arena.path(id), std::path::Path::new("./src/tree/node.rs"));
+/// assert_eq!(arena.get(arena.parent(id).unwrap()).unwrap().name, "tree");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArenaTree {
+    nodes: Vec<ArenaNode>,
+}
+
+impl ArenaTree {
+    /// 节点数量
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 是否没有任何节点
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 根节点下标
+    pub fn root(&self) -> NodeId {
+        0
+    }
+
+    /// 获取节点
+    pub fn get(&self, id: NodeId) -> Option<&ArenaNode> {
+        self.nodes.get(id)
+    }
+
+    /// 获取节点的可变引用
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut ArenaNode> {
+        self.nodes.get_mut(id)
+    }
+
+    /// 按广度优先顺序遍历所有节点
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &ArenaNode)> {
+        self.nodes.iter().enumerate()
+    }
+
+    /// 子节点下标，文件及尚未加载的目录没有子节点
+    pub fn children(&self, id: NodeId) -> Range<NodeId> {
+        self.nodes
+            .get(id)
+            .and_then(|node| node.children.clone())
+            .unwrap_or_default()
+    }
+
+    /// 父节点下标
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes.get(id)?.parent
+    }
+
+    /// 祖先链，从根节点开始依次到父节点，不包含节点本身
+    pub fn ancestors(&self, id: NodeId) -> Vec<NodeId> {
+        let mut chain = Vec::new();
+        let mut current = self.parent(id);
+        while let Some(parent) = current {
+            chain.push(parent);
+            current = self.parent(parent);
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// 拼接节点的完整路径
+    pub fn path(&self, id: NodeId) -> PathBuf {
+        let mut path = PathBuf::new();
+        for ancestor in self.ancestors(id).into_iter().chain([id]) {
+            path.push(&self.nodes[ancestor].name);
+        }
+        path
+    }
+
+    /// 查找路径对应的节点下标
+    pub fn find<P: AsRef<Path>>(&self, path: P) -> Option<NodeId> {
+        let root = self.nodes.first()?;
+        let relative = path.as_ref().strip_prefix(&root.name).ok()?;
+        let mut id = self.root();
+        for component in relative.components() {
+            id = self
+                .children(id)
+                .find(|&child| component.as_os_str() == self.nodes[child].name.as_str())?;
+        }
+        Some(id)
+    }
+
+    /// 转换为嵌套的 `TreeNode`
+    pub fn to_tree(&self) -> Option<TreeNode> {
+        (!self.is_empty()).then(|| self.to_node(self.root()))
+    }
+
+    /// 递归转换节点
+    fn to_node(&self, id: NodeId) -> TreeNode {
+        let node = &self.nodes[id];
+        TreeNode {
+            path: self.path(id).to_string_lossy().into_owned(),
+            is_dir: node.is_dir,
+            kind: node.kind,
+            children: node
+                .children
+                .clone()
+                .map(|children| children.map(|child| self.to_node(child)).collect()),
+            summary: node.summary.clone(),
+            marker: node.marker.clone(),
+            symlink_target: node.symlink_target.clone(),
+        }
+    }
+}
+
+/// 将嵌套的 `TreeNode` 转换为紧凑项目树，转换过程中逐步释放原有节点
+impl From<TreeNode> for ArenaTree {
+    fn from(root: TreeNode) -> Self {
+        let mut nodes = Vec::new();
+        let mut queue = VecDeque::new();
+        let name = root.path.clone();
+        queue.push_back(push_node(&mut nodes, root, name, None));
+        while let Some((id, children)) = queue.pop_front() {
+            let Some(children) = children else {
+                continue;
+            };
+            let start = nodes.len();
+            for child in children {
+                let name = child.name().to_string();
+                queue.push_back(push_node(&mut nodes, child, name, Some(id)));
+            }
+            nodes[id].children = Some(start..nodes.len());
+        }
+        ArenaTree { nodes }
+    }
+}
+
+/// 根据 `TreeNode` 的引用直接构建紧凑项目树，只复制各节点自身的信息，不复制整棵嵌套树
+impl From<&TreeNode> for ArenaTree {
+    fn from(root: &TreeNode) -> Self {
+        let mut nodes = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back((push_ref(&mut nodes, root, root.path.clone(), None), root));
+        while let Some((id, node)) = queue.pop_front() {
+            let Some(children) = &node.children else {
+                continue;
+            };
+            let start = nodes.len();
+            for child in children {
+                let name = child.name().to_string();
+                queue.push_back((push_ref(&mut nodes, child, name, Some(id)), child));
+            }
+            nodes[id].children = Some(start..nodes.len());
+        }
+        ArenaTree { nodes }
+    }
+}
+
+/// 加入节点，返回其下标及尚未加入的子节点
+fn push_node(
+    nodes: &mut Vec<ArenaNode>,
+    node: TreeNode,
+    name: String,
+    parent: Option<NodeId>,
+) -> (NodeId, Option<Vec<TreeNode>>) {
+    nodes.push(ArenaNode {
+        name,
+        is_dir: node.is_dir,
+        kind: node.kind,
+        parent,
+        children: None,
+        summary: node.summary,
+        marker: node.marker,
+        symlink_target: node.symlink_target,
+    });
+    (nodes.len() - 1, node.children)
+}
+
+/// 复制节点自身的信息并加入，返回其下标
+fn push_ref(
+    nodes: &mut Vec<ArenaNode>,
+    node: &TreeNode,
+    name: String,
+    parent: Option<NodeId>,
+) -> NodeId {
+    nodes.push(ArenaNode {
+        name,
+        is_dir: node.is_dir,
+        kind: node.kind,
+        parent,
+        children: None,
+        summary: node.summary.clone(),
+        marker: node.marker.clone(),
+        symlink_target: node.symlink_target.clone(),
+    });
+    nodes.len() - 1
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> TreeNode {
+        let mut root = TreeNode::new("/project", true);
+        let mut src = TreeNode::new("/project/src", true);
+        let mut lib = TreeNode::new("/project/src/lib.rs", false);
+        lib.summary.size = 7;
+        src.children = Some(vec![lib]);
+        let mut unloaded = TreeNode::new("/project/target", true);
+        unloaded.children = None;
+        root.children = Some(vec![
+            src,
+            unloaded,
+            TreeNode::new("/project/README.md", false),
+        ]);
+        root
+    }
+
+    #[test]
+    fn test_arena() {
+        let arena = ArenaTree::from(sample());
+        assert_eq!(arena.len(), 5);
+        // 同一目录的子节点相邻
+        assert_eq!(arena.children(0), 1..4);
+        let lib = arena.find("/project/src/lib.rs").unwrap();
+        assert_eq!(arena.get(lib).unwrap().summary.size, 7);
+        assert_eq!(arena.ancestors(lib), vec![0, 1]);
+        assert_eq!(arena.path(lib), Path::new("/project/src/lib.rs"));
+        assert!(arena.find("/project/src/main.rs").is_none());
+        assert!(arena.find("/other").is_none());
+        assert!(
+            arena
+                .get(arena.find("/project/target").unwrap())
+                .unwrap()
+                .children
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tree = ArenaTree::from(sample()).to_tree().unwrap();
+        let expected = sample();
+        assert_eq!(tree.path, expected.path);
+        let children = tree.children.as_ref().unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(
+            children[0].children.as_ref().unwrap()[0].path,
+            "/project/src/lib.rs"
+        );
+        assert!(!children[1].is_loaded());
+
+        // 通过引用构建的结果与消耗节点构建的结果一致
+        let tree = sample();
+        let borrowed = ArenaTree::from(&tree);
+        let owned = ArenaTree::from(tree);
+        assert_eq!(borrowed.len(), owned.len());
+        for ((_, left), (_, right)) in borrowed.iter().zip(owned.iter()) {
+            assert_eq!(left.name, right.name);
+            assert_eq!(left.parent, right.parent);
+            assert_eq!(left.children, right.children);
+        }
+    }
+}
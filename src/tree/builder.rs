@@ -0,0 +1,937 @@
+//! # 项目树构建
+//! 负责遍历文件系统并生成树节点，默认不包含总结信息的获取；
+//! 设置总结器后，会在遍历过程中直接获取文件的总结信息，并在构建结束后汇总到各级目录。
+//! `ProjectTree::build`、子树重建以及目录节点的按需展开均通过该模块完成，
+//! 此外也支持根据预先给定的路径列表直接组装项目树。
+use crate::errors::AruiError;
+use crate::fs::{DiskFs, FileSystem, FsMetadata};
+use crate::tree::cancel::CancelToken;
+use crate::tree::checkpoint::{self, Checkpoint, CheckpointState};
+use crate::tree::config::{ChildOrder, ProjectConfig, WalkStrategy};
+use crate::tree::node::{NodeMarker, TreeNode};
+use crate::tree::origin::is_vendor_dir;
+use crate::tree::progress::{ProgressReporter, ProgressTracker};
+use crate::tree::stats::BuildStats;
+use crate::tree::stream::DirRecord;
+use crate::tree::summarizer::Summarizer;
+use crate::tree::summary::NodeSummary;
+use crate::tree::warning::{Warning, WarningKind};
+use crate::utils::normalize_unicode;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+type Result<T> = std::result::Result<T, AruiError>;
+
+/// 广度优先构建时待加载的目录
+struct Pending {
+    /// 目录节点在项目树中的位置，依次为各级子节点的下标
+    index: Vec<usize>,
+    /// 目录路径
+    path: String,
+    /// 相对于构建起点的深度
+    depth: usize,
+    /// 各级祖先目录的规范路径
+    ancestors: Vec<PathBuf>,
+}
+
+impl Pending {
+    /// 写入检查点的记录
+    fn entry(&self) -> (&str, usize) {
+        (&self.path, self.depth)
+    }
+}
+
+/// 项目树构建器
+/// 根据配置遍历目录并生成树节点
+pub(crate) struct TreeBuilder {
+    /// 文件系统，默认为磁盘
+    fs: Arc<dyn FileSystem>,
+    /// 展开的最大深度，为空时完整构建
+    max_depth: Option<usize>,
+    /// 构建起点相对项目根节点的深度，`max_depth` 从项目根节点起算
+    start_depth: usize,
+    /// 是否开启容错模式
+    tolerant: bool,
+    /// 子节点排序方式
+    order: ChildOrder,
+    /// 是否跳过符号链接
+    skip_symlinks: bool,
+    /// 是否跳过特殊文件
+    skip_special: bool,
+    /// 遍历策略
+    strategy: WalkStrategy,
+    /// 需要排除的路径，已转换为 Unicode NFC 形式
+    exclude: Vec<PathBuf>,
+    /// 沙箱根路径，为空时不开启沙箱模式；构建开始后替换为规范路径
+    sandbox_root: Option<PathBuf>,
+    /// 容错模式下记录的警告
+    warnings: Vec<Warning>,
+    /// 扫描统计
+    stats: BuildStats,
+    /// 进度追踪
+    progress: ProgressTracker,
+    /// 取消令牌
+    cancel: Option<CancelToken>,
+    /// 总结器，设置后在构建过程中同时获取总结信息
+    summarizer: Option<Summarizer>,
+    /// 检查点，设置后定期写入构建进度
+    checkpoint: Option<Checkpoint>,
+    /// 构建是否已被取消，取消后已构建的部分节点仍会返回
+    cancelled: bool,
+}
+
+impl Default for TreeBuilder {
+    fn default() -> Self {
+        TreeBuilder {
+            fs: Arc::new(DiskFs),
+            max_depth: None,
+            start_depth: 0,
+            tolerant: false,
+            order: ChildOrder::default(),
+            skip_symlinks: false,
+            skip_special: false,
+            strategy: WalkStrategy::default(),
+            exclude: Vec::new(),
+            sandbox_root: None,
+            warnings: Vec::new(),
+            stats: BuildStats::default(),
+            progress: ProgressTracker::default(),
+            cancel: None,
+            summarizer: None,
+            checkpoint: None,
+            cancelled: false,
+        }
+    }
+}
+
+impl TreeBuilder {
+    /// 根据项目配置创建构建器，配置为空时使用默认配置
+    pub(crate) fn new(config: Option<&ProjectConfig>) -> Self {
+        TreeBuilder {
+            max_depth: config.and_then(|config| config.max_depth),
+            tolerant: config.is_some_and(|config| config.tolerant),
+            order: config.map(|config| config.order).unwrap_or_default(),
+            skip_symlinks: config.is_some_and(|config| config.skip_symlinks),
+            skip_special: config.is_some_and(|config| config.skip_special),
+            strategy: config.map(|config| config.strategy).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// 创建仅展开 `depth` 层的构建器
+    pub(crate) fn shallow(depth: usize) -> Self {
+        TreeBuilder {
+            max_depth: Some(depth),
+            ..Default::default()
+        }
+    }
+
+    /// 设置文件系统
+    pub(crate) fn with_fs(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// 设置展开的最大深度，从项目根节点起算，为空时完整构建
+    pub(crate) fn with_max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// 设置构建起点相对项目根节点的深度，用于重建或展开项目树中的子树
+    pub(crate) fn with_start_depth(mut self, depth: usize) -> Self {
+        self.start_depth = depth;
+        self
+    }
+
+    /// 设置进度报告
+    pub(crate) fn with_progress(mut self, progress: Option<Arc<dyn ProgressReporter>>) -> Self {
+        self.progress = ProgressTracker::new(progress);
+        self
+    }
+
+    /// 设置取消令牌
+    pub(crate) fn with_cancel(mut self, cancel: Option<CancelToken>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// 设置需要排除的路径，位于这些路径之下的节点均不会被构建
+    pub(crate) fn with_excludes<I>(mut self, excludes: I) -> Self
+    where
+        I: IntoIterator<Item = PathBuf>,
+    {
+        self.exclude = excludes.into_iter().map(normalize_unicode).collect();
+        self
+    }
+
+    /// 设置沙箱根路径，设置后开启沙箱模式，指向该路径之外的节点仅做标记
+    pub(crate) fn with_sandbox_root(mut self, root: Option<PathBuf>) -> Self {
+        self.sandbox_root = root;
+        self
+    }
+
+    /// 设置总结器，构建时同时获取总结信息
+    pub(crate) fn with_summarizer(mut self, summarizer: Summarizer) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// 设置检查点
+    pub(crate) fn with_checkpoint(mut self, checkpoint: Option<Checkpoint>) -> Self {
+        self.checkpoint = checkpoint;
+        self
+    }
+
+    /// 构建是否已被取消
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// 取出扫描统计，耗时由调用方计算
+    /// 设置了总结器时，同时包含总结过程中的统计
+    pub(crate) fn take_stats(&mut self) -> BuildStats {
+        let mut stats = std::mem::take(&mut self.stats);
+        if let Some(summarizer) = &mut self.summarizer {
+            stats.merge(&summarizer.take_stats());
+        }
+        stats
+    }
+
+    /// 取出构建过程中记录的警告，包括总结器记录的警告
+    pub(crate) fn take_warnings(&mut self) -> Vec<Warning> {
+        let mut warnings = std::mem::take(&mut self.warnings);
+        if let Some(summarizer) = &mut self.summarizer {
+            warnings.extend(summarizer.take_warnings());
+        }
+        warnings
+    }
+
+    /// 从指定路径启动，构建该路径对应的节点及其子树
+    /// 若构建过程中被取消，返回已构建的部分节点，可通过 `is_cancelled` 判断；若启动前已被取消则直接返回错误
+    pub(crate) fn build(&mut self, path: &Path) -> Result<TreeNode> {
+        if self.check_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        // 起始路径无法读取时无法构建任何节点，即使在容错模式下也直接返回错误
+        let metadata = self.fs.metadata(path)?;
+        self.prepare_sandbox()?;
+        let mut root = self.create_node(path, &metadata);
+        // 设置了检查点时需要维护待加载目录队列，总是采用广度优先遍历
+        match (self.strategy, &self.checkpoint) {
+            (WalkStrategy::DepthFirst, None) => {
+                self.build_depth_first(&mut root, self.start_depth, &mut Vec::new())?
+            }
+            _ => self.build_breadth_first(&mut root)?,
+        }
+        // 文件的总结信息已在遍历时获取，仅需在内存中汇总到各级目录
+        if self.summarizer.is_some() {
+            root.summary = Summarizer::roll_up(&mut root);
+        }
+        Ok(root)
+    }
+
+    /// 仅构建根路径下的指定子路径，保留其相对层级
+    /// 子路径与根路径之间的中间目录只包含被指定的子路径；子路径不存在时，容错模式下记录警告并跳过，否则返回错误。
+    /// 各子路径分别构建，不写入检查点；子路径的深度从根路径起算，与 `max_depth` 的含义保持一致
+    pub(crate) fn build_included(&mut self, root: &Path, includes: &[String]) -> Result<TreeNode> {
+        let checkpoint = self.checkpoint.take();
+        let start_depth = self.start_depth;
+        let mut root_node = TreeNode::new(root.to_string_lossy().into_owned(), true);
+        let mut index = ChildIndex::default();
+        for include in includes {
+            let relative = relative_to(root, Path::new(include))
+                .ok_or_else(|| AruiError::InvalidPath(include.clone()))?;
+            let relative = self.resolve_relative(root, &relative);
+            let path = root.join(&relative);
+            self.start_depth = start_depth + relative.components().count();
+            let node = match self.build_include(&path) {
+                Ok(node) => node,
+                Err(AruiError::IO(error)) => {
+                    self.tolerate(&path, error)?;
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            *index.insert_path(&mut root_node, root, &relative) = node;
+            index.forget(&path);
+            if self.cancelled {
+                break;
+            }
+        }
+        self.checkpoint = checkpoint;
+        self.start_depth = start_depth;
+        self.sort_tree(&mut root_node);
+        if self.summarizer.is_some() {
+            root_node.summary = Summarizer::roll_up(&mut root_node);
+        }
+        Ok(root_node)
+    }
+
+    /// 逐级解析相对路径，某一级不存在时在其父目录中查找 Unicode 规范化形式相同的条目
+    /// 使 NFC 形式书写的包含路径可以匹配以 NFD 形式存储的目录
+    fn resolve_relative(&self, root: &Path, relative: &Path) -> PathBuf {
+        let mut current = root.to_path_buf();
+        for component in relative.components() {
+            let candidate = current.join(component);
+            if self.fs.exists(&candidate) {
+                current = candidate;
+                continue;
+            }
+            let name = normalize_unicode(component);
+            let matched = self.fs.read_dir(&current).ok().and_then(|entries| {
+                entries.into_iter().find(|entry| {
+                    entry
+                        .file_name()
+                        .is_some_and(|entry_name| normalize_unicode(entry_name) == name)
+                })
+            });
+            current = matched.unwrap_or(candidate);
+        }
+        current
+            .strip_prefix(root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| relative.to_path_buf())
+    }
+
+    /// 构建单个包含的子路径，沙箱模式下逃逸出根路径的子路径仅做标记
+    fn build_include(&mut self, path: &Path) -> Result<TreeNode> {
+        self.prepare_sandbox()?;
+        if let Some(target) = self.escaped(path)? {
+            let metadata = self.fs.metadata(path)?;
+            return Ok(self.outside_node(path, &metadata, target));
+        }
+        self.build(path)
+    }
+
+    /// 从检查点恢复构建，继续加载检查点中待加载的目录
+    /// 恢复时采用广度优先遍历，各目录的祖先规范路径根据其在已构建项目树中的位置重新获取
+    pub(crate) fn resume(&mut self, state: CheckpointState) -> Result<TreeNode> {
+        if self.check_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        let CheckpointState { mut root, pending } = state;
+        self.prepare_sandbox()?;
+        let mut queue = VecDeque::new();
+        let mut queued = HashSet::new();
+        for (path, depth) in pending {
+            let index = index_of(&root, Path::new(&path))
+                .ok_or_else(|| AruiError::NodeNotFound(path.clone()))?;
+            // 待加载的目录必须是尚未加载的目录，且只出现一次，否则加载后其他待加载目录的下标会失效
+            let mut ancestors = Vec::new();
+            let mut node = &root;
+            for &i in &index {
+                ancestors.push(self.fs.canonicalize(Path::new(&node.path))?);
+                node = &node.children.as_ref().unwrap()[i];
+            }
+            if node.is_loaded() || !queued.insert(path.clone()) {
+                return Err(checkpoint::invalid(format!(
+                    "{} is not an unloaded directory",
+                    path
+                )));
+            }
+            queue.push_back(Pending {
+                index,
+                path,
+                depth,
+                ancestors,
+            });
+        }
+        self.drain(&mut root, queue)?;
+        Ok(root)
+    }
+
+    /// 开启沙箱模式时，将沙箱根路径替换为规范路径
+    fn prepare_sandbox(&mut self) -> Result<()> {
+        if let Some(root) = &self.sandbox_root {
+            self.sandbox_root = Some(self.fs.canonicalize(root)?);
+        }
+        Ok(())
+    }
+
+    /// 检查取消令牌，并记录取消状态
+    fn check_cancelled(&mut self) -> bool {
+        if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            self.cancelled = true;
+        }
+        self.cancelled
+    }
+
+    /// 处理构建过程中的错误
+    /// 容错模式下记录警告并继续，否则返回错误
+    fn tolerate(&mut self, path: &Path, error: std::io::Error) -> Result<()> {
+        let kind = WarningKind::of(&error);
+        self.tolerate_as(path, error, kind)
+    }
+
+    /// 处理构建过程中的错误，并指定警告类型
+    fn tolerate_as(&mut self, path: &Path, error: std::io::Error, kind: WarningKind) -> Result<()> {
+        if !self.tolerant {
+            return Err(error.into());
+        }
+        self.stats.errors_skipped += 1;
+        self.warnings
+            .push(Warning::new(path.to_string_lossy(), error.to_string()).with_kind(kind));
+        Ok(())
+    }
+
+    /// 按配置的排序方式对子节点排序
+    fn sort(&self, children: &mut [TreeNode]) {
+        if self.order == ChildOrder::Unordered {
+            return;
+        }
+        children.sort_by(|a, b| {
+            self.order
+                .compare((a.name(), a.is_dir), (b.name(), b.is_dir))
+        });
+    }
+
+    /// 递归地对已加载的子节点排序
+    fn sort_tree(&self, node: &mut TreeNode) {
+        if let Some(children) = node.children.as_mut() {
+            self.sort(children);
+            for child in children {
+                self.sort_tree(child);
+            }
+        }
+    }
+
+    /// 根据元数据创建节点，目录节点的子节点尚未加载
+    fn create_node(&mut self, path: &Path, metadata: &FsMetadata) -> TreeNode {
+        let mut node = TreeNode::from_metadata(path, metadata);
+        node.symlink_target = self.symlink_target(path, metadata);
+        if metadata.is_dir {
+            return node;
+        }
+        // 特殊文件已被标记，仅读取普通文件的内容
+        if let Some(summarizer) = &mut self.summarizer
+            && node.marker.is_none()
+        {
+            node.summary = summarizer.summarize_contents(path, metadata);
+        }
+        self.stats.files_visited += 1;
+        self.progress.file_done();
+        node
+    }
+
+    /// 沙箱模式下检查路径是否逃逸出根路径，逃逸时返回其规范路径
+    fn escaped(&self, path: &Path) -> std::io::Result<Option<PathBuf>> {
+        let Some(root) = &self.sandbox_root else {
+            return Ok(None);
+        };
+        let canonical = self.fs.canonicalize(path)?;
+        if canonical.starts_with(root) {
+            Ok(None)
+        } else {
+            Ok(Some(canonical))
+        }
+    }
+
+    /// 创建逃逸出沙箱根路径的节点，记录警告，并且不加载子节点、不获取总结信息
+    fn outside_node(&mut self, path: &Path, metadata: &FsMetadata, target: PathBuf) -> TreeNode {
+        let target = target.to_string_lossy().into_owned();
+        self.warnings.push(
+            Warning::new(
+                path.to_string_lossy(),
+                format!("points outside the project root: {}", target),
+            )
+            .with_kind(WarningKind::OutsideRoot),
+        );
+        let mut node = TreeNode::new(path.to_string_lossy().into_owned(), metadata.is_dir);
+        node.kind = metadata.kind;
+        node.marker = Some(NodeMarker::OutsideRoot { target });
+        node.symlink_target = self.symlink_target(path, metadata);
+        node
+    }
+
+    /// 读取符号链接指向的目标，路径不是符号链接或无法读取时为空
+    fn symlink_target(&self, path: &Path, metadata: &FsMetadata) -> Option<String> {
+        if !metadata.is_symlink {
+            return None;
+        }
+        let target = self.fs.read_link(path).ok()?;
+        Some(target.to_string_lossy().into_owned())
+    }
+
+    /// 创建指向不存在目标的符号链接节点，记录警告，并且不获取总结信息
+    fn broken_node(&mut self, path: &Path, target: PathBuf) -> TreeNode {
+        let target = target.to_string_lossy().into_owned();
+        self.warnings.push(
+            Warning::new(
+                path.to_string_lossy(),
+                format!("points to a missing target: {}", target),
+            )
+            .with_kind(WarningKind::BrokenSymlink),
+        );
+        let mut node = TreeNode::new(path.to_string_lossy().into_owned(), false);
+        node.symlink_target = Some(target.clone());
+        node.marker = Some(NodeMarker::BrokenSymlink { target });
+        node
+    }
+
+    /// 将子路径转换为 Unicode NFC 形式
+    /// 仅在转换后的路径同样可以访问（如 macOS 等规范化不敏感的文件系统），且同一目录下不存在与之同名的另一条目时生效，
+    /// 避免在区分规范化形式的文件系统上指向不存在或错误的文件
+    fn normalize(&self, path: &Path, siblings: &[PathBuf]) -> PathBuf {
+        let normalized = normalize_unicode(path);
+        if normalized != path && !siblings.contains(&normalized) && self.fs.exists(&normalized) {
+            normalized
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// 路径是否被排除，比较时不受 Unicode 规范化形式的影响
+    fn is_excluded(&self, path: &Path) -> bool {
+        if self.exclude.is_empty() {
+            return false;
+        }
+        let path = normalize_unicode(path);
+        self.exclude.iter().any(|exclude| path.starts_with(exclude))
+    }
+
+    /// 深度优先构建，递归加载目录节点的整棵子树
+    /// - depth：相对于项目根节点的深度
+    /// - ancestors：当前路径上各级目录的规范路径
+    fn build_depth_first(
+        &mut self,
+        node: &mut TreeNode,
+        depth: usize,
+        ancestors: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let Some(canonical) = self.load_children(node, depth, ancestors)? else {
+            return Ok(());
+        };
+        ancestors.push(canonical);
+        if let Some(children) = node.children.as_mut() {
+            for child in children.iter_mut().filter(|child| child.is_dir) {
+                // 被取消时停止加载剩余子目录
+                if self.check_cancelled() {
+                    break;
+                }
+                self.build_depth_first(child, depth + 1, ancestors)?;
+            }
+        }
+        ancestors.pop();
+        Ok(())
+    }
+
+    /// 流式分析，从指定路径启动深度优先遍历，每个目录完成汇总后生成一条 `DirRecord`，并释放其子节点
+    /// 需要预先设置总结器，返回起始节点的总结信息
+    pub(crate) fn stream<F>(&mut self, path: &Path, emit: &mut F) -> Result<NodeSummary>
+    where
+        F: FnMut(DirRecord),
+    {
+        if self.check_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        let metadata = self.fs.metadata(path)?;
+        self.prepare_sandbox()?;
+        let mut root = self.create_node(path, &metadata);
+        self.stream_depth_first(&mut root, 0, &mut Vec::new(), false, emit)?;
+        Ok(root.summary)
+    }
+
+    /// 流式分析单个目录，子目录完成汇总后即释放其子节点
+    /// - vendored：是否位于第三方目录之下，子目录的记录生成时祖先目录尚未汇总，需要提前标记
+    fn stream_depth_first<F>(
+        &mut self,
+        node: &mut TreeNode,
+        depth: usize,
+        ancestors: &mut Vec<PathBuf>,
+        vendored: bool,
+        emit: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(DirRecord),
+    {
+        if !node.is_dir {
+            return Ok(());
+        }
+        if let Some(canonical) = self.load_children(node, depth, ancestors)? {
+            ancestors.push(canonical);
+            let inner = vendored || is_vendor_dir(node.name());
+            for child in node.children.iter_mut().flatten() {
+                if !child.is_dir {
+                    continue;
+                }
+                // 被取消时停止加载剩余子目录
+                if self.check_cancelled() {
+                    break;
+                }
+                self.stream_depth_first(child, depth + 1, ancestors, inner, emit)?;
+                // 子目录已完成汇总，释放其子节点
+                child.children = Some(Vec::new());
+            }
+            ancestors.pop();
+        }
+        node.summary = NodeSummary::collect(node);
+        if vendored {
+            node.summary.set_vendored();
+        }
+        emit(DirRecord {
+            path: node.path.clone(),
+            depth,
+            summary: node.summary.clone(),
+            marker: node.marker.clone(),
+        });
+        Ok(())
+    }
+
+    /// 广度优先构建，逐层加载目录节点，浅层目录总是先于深层目录完成
+    fn build_breadth_first(&mut self, root: &mut TreeNode) -> Result<()> {
+        let queue = VecDeque::from([Pending {
+            index: Vec::new(),
+            path: root.path.clone(),
+            depth: self.start_depth,
+            ancestors: Vec::new(),
+        }]);
+        self.drain(root, queue)
+    }
+
+    /// 依次加载队列中的目录，并将其子目录加入队列末尾
+    /// 设置了检查点时，每加载一定数量的目录写入一次检查点；全部加载完成后删除检查点，被取消时则写入最后一次检查点
+    fn drain(&mut self, root: &mut TreeNode, mut queue: VecDeque<Pending>) -> Result<()> {
+        let mut loaded = 0;
+        while let Some(pending) = queue.front() {
+            // 被取消时停止加载剩余目录
+            if self.check_cancelled() {
+                break;
+            }
+            let node = node_at(root, &pending.index)?;
+            let canonical = self.load_children(node, pending.depth, &pending.ancestors)?;
+            // 加载过程中被取消的目录恢复为未加载状态，保留在队列中
+            if self.cancelled {
+                node.children = None;
+                break;
+            }
+            let Pending {
+                index,
+                depth,
+                mut ancestors,
+                ..
+            } = queue.pop_front().unwrap();
+            if let Some(canonical) = canonical {
+                ancestors.push(canonical);
+                let children = node.children.iter().flatten().enumerate();
+                for (i, child) in children.filter(|(_, child)| child.is_dir) {
+                    let mut index = index.clone();
+                    index.push(i);
+                    queue.push_back(Pending {
+                        index,
+                        path: child.path.clone(),
+                        depth: depth + 1,
+                        ancestors: ancestors.clone(),
+                    });
+                }
+            }
+            loaded += 1;
+            if let Some(checkpoint) = &self.checkpoint
+                && loaded % checkpoint.interval == 0
+            {
+                checkpoint.save(root, queue.iter().map(Pending::entry))?;
+            }
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            if self.cancelled {
+                checkpoint.save(root, queue.iter().map(Pending::entry))?;
+            } else {
+                checkpoint.remove()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 加载目录节点的直接子节点，子目录保持未加载状态
+    /// 返回该目录的规范路径，若无需继续向下构建（文件、超出最大深度、出现循环、目录无法读取）则返回空
+    ///
+    /// 容错模式下，无法读取的子路径会被跳过。
+    /// 符号链接默认会被跟随，若目录的规范路径已出现在祖先链中，说明存在循环（如 `a -> ..`），
+    /// 此时不再加载该目录，而是在节点上标记 `NodeMarker::CycleDetected`
+    fn load_children(
+        &mut self,
+        node: &mut TreeNode,
+        depth: usize,
+        ancestors: &[PathBuf],
+    ) -> Result<Option<PathBuf>> {
+        // 已被标记的节点（如逃逸出沙箱根路径）不再加载
+        if !node.is_dir || node.marker.is_some() {
+            return Ok(None);
+        }
+        // 超出最大深度的目录不加载子节点，等待按需展开
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return Ok(None);
+        }
+        // 以下情况的目录均视为已加载的空目录
+        node.children = Some(Vec::new());
+        let path = PathBuf::from(&node.path);
+        // 检查循环，目录已出现在祖先链中时停止加载
+        let canonical = match self.fs.canonicalize(&path) {
+            Ok(canonical) => canonical,
+            Err(error) => {
+                self.tolerate(&path, error)?;
+                return Ok(None);
+            }
+        };
+        if ancestors.contains(&canonical) {
+            node.marker = Some(NodeMarker::CycleDetected {
+                target: canonical.to_string_lossy().into_owned(),
+            });
+            return Ok(None);
+        }
+        self.stats.dirs_visited += 1;
+        self.progress.enter_dir(&node.path);
+        let entries = match self.fs.read_dir(&path) {
+            Ok(entries) => entries,
+            Err(error) => {
+                self.tolerate(&path, error)?;
+                return Ok(None);
+            }
+        };
+        let mut children = Vec::new();
+        for child_path in entries.iter() {
+            // 被取消时停止加载剩余子节点
+            if self.check_cancelled() {
+                break;
+            }
+            let child_path = self.normalize(child_path, &entries);
+            if self.is_excluded(&child_path) {
+                continue;
+            }
+            // 获取文件元数据，目录中列出的路径不存在时，说明其为指向不存在目标的符号链接
+            let metadata = match self.fs.metadata(&child_path) {
+                Ok(metadata) => metadata,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                    // 损坏的符号链接同样作为节点保留，以便统计
+                    if let Ok(target) = self.fs.read_link(&child_path) {
+                        if !self.skip_symlinks {
+                            children.push(self.broken_node(&child_path, target));
+                        }
+                        continue;
+                    }
+                    self.tolerate_as(&child_path, error, WarningKind::BrokenSymlink)?;
+                    continue;
+                }
+                Err(error) => {
+                    self.tolerate(&child_path, error)?;
+                    continue;
+                }
+            };
+            if self.skip_symlinks && metadata.is_symlink
+                || self.skip_special && metadata.kind.is_special()
+            {
+                continue;
+            }
+            // 沙箱模式下，指向根路径之外的节点仅做标记
+            match self.escaped(&child_path) {
+                Ok(Some(target)) => {
+                    children.push(self.outside_node(&child_path, &metadata, target));
+                    continue;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.tolerate(&child_path, error)?;
+                    continue;
+                }
+            }
+            children.push(self.create_node(&child_path, &metadata));
+        }
+        self.sort(&mut children);
+        node.children = Some(children);
+        Ok(Some(canonical))
+    }
+
+    /// 将已有的节点与文件系统的当前状态比较，将其下过期的子树路径追加到 `stale`，返回检查的节点数量
+    /// - 节点不存在，或在文件与目录之间发生了变化时，节点过期
+    /// - 目录中列出的子路径（经过与构建时相同的过滤）与已有的子节点不一致时，目录过期，且不再检查其下的节点
+    /// - 文件的大小或修改时间与总结信息中记录的不一致时，文件过期；尚未总结的文件无法比较，视为未变化
+    ///
+    /// 已被标记的节点（如损坏的符号链接、特殊文件）与尚未展开的目录仅检查其是否仍然存在
+    pub(crate) fn collect_stale(&self, node: &TreeNode, stale: &mut Vec<String>) -> u64 {
+        let path = Path::new(&node.path);
+        let metadata = match self.fs.metadata(path) {
+            Ok(metadata) => metadata,
+            // 损坏的符号链接仍然存在时视为未变化
+            Err(_) if node.is_broken_symlink() && self.fs.read_link(path).is_ok() => return 1,
+            Err(_) => {
+                stale.push(node.path.clone());
+                return 1;
+            }
+        };
+        if metadata.is_dir != node.is_dir {
+            stale.push(node.path.clone());
+            return 1;
+        }
+        if node.marker.is_some() {
+            return 1;
+        }
+        let Some(children) = &node.children else {
+            let summary = &node.summary;
+            if !node.is_dir
+                && summary.summarized_at.is_some()
+                && (summary.size != metadata.len || summary.updated_at != metadata.modified)
+            {
+                stale.push(node.path.clone());
+            }
+            return 1;
+        };
+        let Ok(entries) = self.fs.read_dir(path) else {
+            stale.push(node.path.clone());
+            return 1;
+        };
+        let listed = entries
+            .iter()
+            .map(|entry| self.normalize(entry, &entries))
+            .filter(|entry| !self.is_excluded(entry) && !self.is_skipped(entry))
+            .collect::<BTreeSet<_>>();
+        let known = children
+            .iter()
+            .map(|child| PathBuf::from(&child.path))
+            .collect::<BTreeSet<_>>();
+        if listed != known {
+            stale.push(node.path.clone());
+            return 1;
+        }
+        1 + children
+            .iter()
+            .map(|child| self.collect_stale(child, stale))
+            .sum::<u64>()
+    }
+
+    /// 目录中列出的路径在构建时是否会被跳过，与 `load_children` 的判断一致
+    fn is_skipped(&self, path: &Path) -> bool {
+        match self.fs.metadata(path) {
+            Ok(metadata) => {
+                self.skip_symlinks && metadata.is_symlink
+                    || self.skip_special && metadata.kind.is_special()
+            }
+            // 损坏的符号链接仅在不跳过符号链接时保留，其余无法获取元数据的路径均被跳过
+            Err(_) => self.skip_symlinks || self.fs.read_link(path).is_err(),
+        }
+    }
+}
+
+/// 根据下标路径获取节点，下标不存在时说明检查点与项目树不一致，返回 `AruiError::InvalidCheckpoint`
+fn node_at<'a>(mut node: &'a mut TreeNode, index: &[usize]) -> Result<&'a mut TreeNode> {
+    for &i in index {
+        node = match node
+            .children
+            .as_mut()
+            .and_then(|children| children.get_mut(i))
+        {
+            Some(child) => child,
+            None => return Err(checkpoint::invalid("pending directory is not in the tree")),
+        };
+    }
+    Ok(node)
+}
+
+/// 获取路径对应节点的下标路径，不存在时返回空
+fn index_of(node: &TreeNode, path: &Path) -> Option<Vec<usize>> {
+    if Path::new(&node.path) == path {
+        return Some(Vec::new());
+    }
+    let children = node.children.as_ref()?;
+    children.iter().enumerate().find_map(|(i, child)| {
+        if !path.starts_with(&child.path) {
+            return None;
+        }
+        let mut index = index_of(child, path)?;
+        index.insert(0, i);
+        Some(index)
+    })
+}
+
+/// 根据路径列表组装项目树，不遍历文件系统
+/// - root：项目根路径
+/// - paths：文件路径列表，相对路径基于根路径解析，绝对路径必须位于根路径之下
+///
+/// 列表中的路径均视为文件，中间路径自动生成目录节点；若某路径同时作为其他路径的父级出现，则视为目录。
+pub(crate) fn assemble<I>(root: &Path, paths: I) -> Result<TreeNode>
+where
+    I: IntoIterator<Item = PathBuf>,
+{
+    let mut root_node = TreeNode::new(root.to_string_lossy().into_owned(), true);
+    let mut index = ChildIndex::default();
+    for path in paths {
+        let relative = relative_to(root, &path)
+            .ok_or_else(|| AruiError::InvalidPath(path.to_string_lossy().into_owned()))?;
+        index.insert_path(&mut root_node, root, &relative);
+    }
+    Ok(root_node)
+}
+
+/// 将路径转换为相对于根路径的规范形式，去除 `.`，拒绝 `..` 及根路径之外的绝对路径
+fn relative_to(root: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(root).ok()?
+    } else {
+        path.strip_prefix(root).unwrap_or(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(name) => normalized.push(name),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(normalized)
+}
+
+/// 组装项目树时使用的临时索引：目录路径 -> 子节点名称 -> 子节点在 `children` 中的位置
+/// 避免每次插入都线性查找同级节点，目录首次被访问时才建立其索引
+#[derive(Default)]
+struct ChildIndex(HashMap<PathBuf, HashMap<OsString, usize>>);
+
+impl ChildIndex {
+    /// 沿相对路径逐级插入节点，缺失的中间目录会被创建，返回路径对应的节点
+    fn insert_path<'a>(
+        &mut self,
+        root_node: &'a mut TreeNode,
+        root: &Path,
+        relative: &Path,
+    ) -> &'a mut TreeNode {
+        let mut node = root_node;
+        let mut current = root.to_path_buf();
+        let mut components = relative.components().peekable();
+        while let Some(component) = components.next() {
+            let is_leaf = components.peek().is_none();
+            // 原本作为文件插入的节点出现了子路径，转换为目录
+            if !node.is_dir {
+                node.is_dir = true;
+            }
+            let children = node.children.get_or_insert_with(Vec::new);
+            let positions = self.0.entry(current.clone()).or_insert_with(|| {
+                children
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, child)| {
+                        Some((Path::new(&child.path).file_name()?.to_os_string(), i))
+                    })
+                    .collect()
+            });
+            current.push(component);
+            let index = match positions.get(component.as_os_str()) {
+                Some(&index) => index,
+                None => {
+                    children.push(TreeNode::new(
+                        current.to_string_lossy().into_owned(),
+                        !is_leaf,
+                    ));
+                    positions.insert(component.as_os_str().to_os_string(), children.len() - 1);
+                    children.len() - 1
+                }
+            };
+            node = &mut children[index];
+        }
+        node
+    }
+
+    /// 路径对应的节点被整体替换后，丢弃该节点及其下各目录的索引
+    fn forget(&mut self, path: &Path) {
+        self.0.retain(|dir, _| !dir.starts_with(path));
+    }
+}
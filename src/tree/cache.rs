@@ -0,0 +1,363 @@
+//! # 总结缓存
+//! 反复分析同一批项目时，大部分文件在两次分析之间并未发生变化，重新读取其内容是不必要的开销。
+//! `SummaryCache` 以文件路径为键，记录文件的大小、修改时间与总结信息，
+//! 总结时若文件的大小与修改时间均未变化，则直接复用缓存的总结信息而不再读取文件。
+//! 缓存可在线程间共享，由多个项目树共同使用。
+//! 缓存同时记录写入时的总结选项（如 `count_words`、`markers`），以不同的选项总结时缓存会被清空，避免复用按旧选项得到的总结信息。
+//!
+//! 开启 `disk-cache` 特性后，缓存可以通过 `save` 保存到文件，并在之后的运行中通过 `load` 加载，
+//! 反复扫描变化不多的仓库时几乎无需读取任何文件内容。
+#[cfg(feature = "disk-cache")]
+use crate::errors::AruiError;
+use crate::fs::FsMetadata;
+use crate::tree::summary::NodeSummary;
+use std::collections::HashMap;
+#[cfg(feature = "disk-cache")]
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// 缓存文件的格式版本，缓存文件或总结信息（`NodeSummary`）的结构发生变化时递增
+#[cfg(feature = "disk-cache")]
+const CACHE_VERSION: u32 = 3;
+
+/// 影响文件总结信息的选项，缓存的总结信息仅在选项一致时复用
+/// `track_top_n` 不影响单个文件的总结信息，命中缓存时会重新记录，因此不包含在内
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "disk-cache", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct SummaryOptions {
+    /// 读取的文件大小上限
+    pub(crate) max_file_size: Option<u64>,
+    /// 是否统计词数与字符数
+    pub(crate) count_words: bool,
+    /// 需要统计的代码标记
+    pub(crate) markers: Vec<String>,
+    /// 是否估算 token 数量
+    pub(crate) count_tokens: bool,
+    /// 已开启的、会改变文件总结信息的特性
+    pub(crate) features: Vec<String>,
+}
+
+impl SummaryOptions {
+    /// 已开启的、会改变文件总结信息的特性
+    pub(crate) fn enabled_features() -> Vec<String> {
+        [
+            ("compress", cfg!(feature = "compress")),
+            ("hash", cfg!(feature = "hash")),
+            ("manifest", cfg!(feature = "manifest")),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(feature, _)| feature.to_string())
+        .collect()
+    }
+}
+
+/// 缓存条目
+#[cfg_attr(feature = "disk-cache", derive(serde::Serialize, serde::Deserialize))]
+struct CacheEntry {
+    /// 缓存时的文件大小
+    len: u64,
+    /// 缓存时的修改时间
+    modified: SystemTime,
+    /// 文件的总结信息
+    summary: NodeSummary,
+}
+
+/// 文件总结信息缓存
+/// 文件系统无法提供修改时间时（如 `MemoryFs`），对应文件不会被缓存；
+/// 以与缓存不同的总结选项总结时会先清空缓存，因此在配置不同的项目树之间共用缓存时无法命中
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::cache::SummaryCache;
+/// use arui_core::tree::root::ProjectTree;
+/// use std::sync::Arc;
+///
+/// let cache = Arc::new(SummaryCache::new());
+/// let mut project = ProjectTree::new("test", "./src", None).with_cache(cache.clone());
+/// project.analyze().unwrap();
+/// assert!(!cache.is_empty());
+/// // 再次分析时直接复用缓存
+/// project.analyze().unwrap();
+/// assert!(cache.hits() > 0);
+/// ```
+#[derive(Default)]
+pub struct SummaryCache {
+    /// 文件路径与缓存条目
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+    /// 缓存条目对应的总结选项，尚未写入任何条目时为空
+    options: Mutex<Option<SummaryOptions>>,
+    /// 命中次数
+    hits: AtomicU64,
+}
+
+impl SummaryCache {
+    /// 创建空的缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 缓存的文件数量
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// 是否没有缓存任何文件
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 累计命中次数
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// 清空缓存
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// 以指定的总结选项使用缓存，与缓存条目对应的选项不一致时清空缓存
+    pub(crate) fn bind(&self, options: SummaryOptions) {
+        let mut current = self.options.lock().unwrap();
+        if current.as_ref() != Some(&options) {
+            self.entries.lock().unwrap().clear();
+            *current = Some(options);
+        }
+    }
+
+    /// 获取文件的缓存总结信息，文件大小或修改时间发生变化时视为未命中
+    pub(crate) fn get(&self, path: &Path, metadata: &FsMetadata) -> Option<NodeSummary> {
+        let modified = metadata.modified?;
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(path)?;
+        if entry.len != metadata.len || entry.modified != modified {
+            return None;
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry.summary.clone())
+    }
+
+    /// 写入文件的总结信息，缺少修改时间时不写入
+    pub(crate) fn insert(&self, path: &Path, metadata: &FsMetadata, summary: &NodeSummary) {
+        let Some(modified) = metadata.modified else {
+            return;
+        };
+        self.entries.lock().unwrap().insert(
+            path.to_path_buf(),
+            CacheEntry {
+                len: metadata.len,
+                modified,
+                summary: summary.clone(),
+            },
+        );
+    }
+}
+
+/// 写入缓存文件的内容
+#[cfg(feature = "disk-cache")]
+#[derive(serde::Serialize)]
+struct CacheFileRef<'a> {
+    version: u32,
+    options: Option<SummaryOptions>,
+    entries: Vec<(&'a Path, &'a CacheEntry)>,
+}
+
+/// 从缓存文件读取的内容
+#[cfg(feature = "disk-cache")]
+#[derive(serde::Deserialize)]
+struct CacheFile {
+    version: u32,
+    options: Option<SummaryOptions>,
+    entries: Vec<(PathBuf, CacheEntry)>,
+}
+
+#[cfg(feature = "disk-cache")]
+impl SummaryCache {
+    /// 从缓存文件加载缓存，需要开启 `disk-cache` 特性
+    /// 缓存文件不存在、格式版本不一致或内容无法解析时返回空的缓存，下次保存时即会重建；
+    /// 缓存文件中记录了写入时的总结选项，之后以不同的选项总结时同样会重建
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::cache::SummaryCache;
+    /// use arui_core::tree::root::ProjectTree;
+    /// use std::sync::Arc;
+    ///
+    /// let path = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// let cache = Arc::new(SummaryCache::load(&path).unwrap());
+    /// let mut project = ProjectTree::new("test", "./src", None).with_cache(cache.clone());
+    /// project.analyze().unwrap();
+    /// cache.save(&path).unwrap();
+    ///
+    /// // 之后的运行中加载缓存，未变化的文件不再读取
+    /// let cache = Arc::new(SummaryCache::load(&path).unwrap());
+    /// let mut project = ProjectTree::new("test", "./src", None).with_cache(cache.clone());
+    /// project.analyze().unwrap();
+    /// assert!(cache.hits() > 0);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<SummaryCache, AruiError> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(SummaryCache::new());
+            }
+            Err(error) => return Err(error.into()),
+        };
+        let (options, entries) = match serde_json::from_slice::<CacheFile>(&contents) {
+            Ok(file) if file.version == CACHE_VERSION => {
+                (file.options, file.entries.into_iter().collect())
+            }
+            _ => (None, HashMap::new()),
+        };
+        Ok(SummaryCache {
+            entries: Mutex::new(entries),
+            options: Mutex::new(options),
+            hits: AtomicU64::new(0),
+        })
+    }
+
+    /// 将缓存保存到文件，需要开启 `disk-cache` 特性
+    /// 先写入临时文件再替换，避免写入过程中被中断导致缓存文件损坏；路径不是合法 UTF-8 的文件不会被保存
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AruiError> {
+        let path = path.as_ref();
+        let options = self.options.lock().unwrap().clone();
+        let entries = self.entries.lock().unwrap();
+        let file = CacheFileRef {
+            version: CACHE_VERSION,
+            options,
+            entries: entries
+                .iter()
+                .filter(|(path, _)| path.to_str().is_some())
+                .map(|(path, entry)| (path.as_path(), entry))
+                .collect(),
+        };
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let mut writer = BufWriter::new(std::fs::File::create(&temp)?);
+        serde_json::to_writer(&mut writer, &file).map_err(std::io::Error::from)?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(temp, path)?;
+        Ok(())
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let cache = SummaryCache::new();
+        let path = Path::new("/project/lib.rs");
+        let metadata = FsMetadata {
+            len: 10,
+            modified: Some(SystemTime::UNIX_EPOCH),
+            ..Default::default()
+        };
+        let summary = NodeSummary {
+            size: 10,
+            count: 2,
+            ..Default::default()
+        };
+        cache.insert(path, &metadata, &summary);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(path, &metadata).unwrap().count, 2);
+        assert_eq!(cache.hits(), 1);
+        // 文件发生变化时未命中
+        let changed = FsMetadata {
+            len: 11,
+            ..metadata.clone()
+        };
+        assert!(cache.get(path, &changed).is_none());
+        // 缺少修改时间时不缓存
+        let unknown = FsMetadata::default();
+        cache.insert(Path::new("/project/main.rs"), &unknown, &summary);
+        assert!(cache.get(Path::new("/project/main.rs"), &unknown).is_none());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_bind() {
+        let cache = SummaryCache::new();
+        let path = Path::new("/project/lib.rs");
+        let metadata = FsMetadata {
+            len: 10,
+            modified: Some(SystemTime::UNIX_EPOCH),
+            ..Default::default()
+        };
+        cache.bind(SummaryOptions::default());
+        cache.insert(path, &metadata, &NodeSummary::default());
+        // 选项一致时保留缓存
+        cache.bind(SummaryOptions::default());
+        assert_eq!(cache.len(), 1);
+        // 选项不同时清空缓存
+        let options = SummaryOptions {
+            markers: vec!["TODO".to_string()],
+            ..Default::default()
+        };
+        cache.bind(options.clone());
+        assert!(cache.is_empty());
+        cache.insert(path, &metadata, &NodeSummary::default());
+        cache.bind(options);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn test_save_and_load() {
+        use crate::tree::language::Language;
+        let path = std::env::temp_dir().join(crate::utils::generate_id());
+        assert!(SummaryCache::load(&path).unwrap().is_empty());
+        let cache = SummaryCache::new();
+        let metadata = FsMetadata {
+            len: 10,
+            modified: Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_nanos(1_500)),
+            ..Default::default()
+        };
+        let mut summary = NodeSummary {
+            size: 10,
+            count: 2,
+            language: Some(Language::Rust),
+            ..Default::default()
+        };
+        summary.markers.insert("TODO".to_string(), 1);
+        cache.bind(SummaryOptions {
+            count_words: true,
+            ..Default::default()
+        });
+        cache.insert(Path::new("/project/lib.rs"), &metadata, &summary);
+        cache.save(&path).unwrap();
+
+        let loaded = SummaryCache::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        let restored = loaded.get(Path::new("/project/lib.rs"), &metadata).unwrap();
+        assert_eq!(restored.count, 2);
+        assert_eq!(restored.language, Some(Language::Rust));
+        assert_eq!(restored.markers["TODO"], 1);
+        // 缓存文件记录了写入时的选项，以不同的选项使用时清空
+        loaded.bind(SummaryOptions {
+            count_words: true,
+            ..Default::default()
+        });
+        assert_eq!(loaded.len(), 1);
+        loaded.bind(SummaryOptions::default());
+        assert!(loaded.is_empty());
+        // 版本不一致或内容损坏的缓存文件视为空
+        std::fs::write(&path, r#"{"version":0,"entries":[]}"#).unwrap();
+        assert!(SummaryCache::load(&path).unwrap().is_empty());
+        std::fs::write(&path, "not json").unwrap();
+        assert!(SummaryCache::load(&path).unwrap().is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+//! # 取消操作
+//! 提供可在线程间共享的取消令牌，用于中断耗时的构建、总结等操作。
+//! 操作会在处理每个节点前检查令牌，一旦被取消即尽快停止，并返回 `AruiError::Cancelled`，已完成的部分结果会被保留。
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 取消令牌
+/// 克隆得到的令牌共享同一取消状态，任意一方调用 `cancel` 后所有持有者均可感知
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::cancel::CancelToken;
+///
+/// let token = CancelToken::new();
+/// let handle = token.clone();
+/// assert_eq!(token.is_cancelled(), false);
+/// handle.cancel();
+/// assert_eq!(token.is_cancelled(), true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// 创建一个未取消的令牌
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 触发取消
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// 是否已被取消
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_across_threads() {
+        let token = CancelToken::new();
+        let handle = token.clone();
+        std::thread::spawn(move || handle.cancel()).join().unwrap();
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,296 @@
+//! # 构建检查点
+//! 扫描超大目录（如多 TB 的网络共享）时，构建过程可能被中断。
+//! 设置检查点后，构建过程会定期将已构建的部分项目树与待加载的目录队列写入文件，之后可通过 `ProjectTree::resume` 从中断处继续。
+//!
+//! 检查点文件为纯文本，每行一条记录，字段以制表符分隔，节点按先序排列，
+//! 路径中的反斜杠、制表符、回车与换行符分别转义为 `\\`、`\t`、`\r` 与 `\n`：
+//! - `D path`：已加载的目录
+//! - `U path`：尚未加载子节点的目录
+//! - `F path`：文件
+//! - `Q depth path`：待加载的目录及其深度
+//!
+//! 检查点仅保存项目树的结构，不保存总结信息与节点标记。
+use crate::errors::AruiError;
+use crate::tree::node::TreeNode;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// 检查点文件的首行标识
+const HEADER: &str = "# arui checkpoint v2";
+
+/// 构建检查点
+/// - path：检查点文件路径
+/// - interval：每加载多少个目录写入一次检查点
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::checkpoint::Checkpoint;
+///
+/// let checkpoint = Checkpoint::new("./scan.checkpoint").with_interval(500);
+/// assert_eq!(checkpoint.interval, 500);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    /// 检查点文件路径
+    pub path: String,
+    /// 每加载多少个目录写入一次检查点，默认为 100
+    pub interval: u64,
+}
+
+/// 从检查点文件中恢复的构建状态
+/// - root：已构建的部分项目树
+/// - pending：待加载的目录路径及其深度
+pub(crate) struct CheckpointState {
+    pub(crate) root: TreeNode,
+    pub(crate) pending: Vec<(String, usize)>,
+}
+
+impl Checkpoint {
+    /// 创建检查点
+    pub fn new<S: Into<String>>(path: S) -> Self {
+        Checkpoint {
+            path: path.into(),
+            interval: 100,
+        }
+    }
+
+    /// 设置写入检查点的间隔，最小为 1
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// 检查点文件是否存在
+    pub fn exists(&self) -> bool {
+        Path::new(&self.path).exists()
+    }
+
+    /// 将部分项目树与待加载目录写入检查点文件
+    /// 先写入临时文件再替换，避免写入过程中被中断导致检查点损坏
+    pub(crate) fn save<'a, I>(&self, root: &TreeNode, pending: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (&'a str, usize)>,
+    {
+        let temp = format!("{}.tmp", self.path);
+        let mut writer = BufWriter::new(fs::File::create(&temp)?);
+        writeln!(writer, "{}", HEADER)?;
+        write_node(&mut writer, root)?;
+        for (path, depth) in pending {
+            writeln!(writer, "Q\t{}\t{}", depth, escape(path))?;
+        }
+        writer.flush()?;
+        drop(writer);
+        fs::rename(temp, &self.path)
+    }
+
+    /// 读取检查点文件，恢复部分项目树与待加载目录
+    /// 文件格式错误时返回 `AruiError::InvalidCheckpoint`
+    pub(crate) fn load(&self) -> Result<CheckpointState, AruiError> {
+        let reader = BufReader::new(fs::File::open(&self.path)?);
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid("missing checkpoint header"));
+        }
+        // 先序排列的节点，栈中依次为当前节点的各级祖先目录
+        let mut stack: Vec<TreeNode> = Vec::new();
+        let mut pending = Vec::new();
+        for line in lines {
+            let line = line?;
+            let (kind, rest) = line
+                .split_once('\t')
+                .ok_or_else(|| invalid(format!("malformed line: {}", line)))?;
+            let node = match kind {
+                "D" => TreeNode::new(unescape(rest)?, true),
+                "U" => {
+                    let mut node = TreeNode::new(unescape(rest)?, true);
+                    node.children = None;
+                    node
+                }
+                "F" => TreeNode::new(unescape(rest)?, false),
+                "Q" => {
+                    let (depth, path) = rest
+                        .split_once('\t')
+                        .ok_or_else(|| invalid(format!("malformed line: {}", line)))?;
+                    let depth = depth.parse().map_err(|_| invalid("malformed depth"))?;
+                    pending.push((unescape(path)?, depth));
+                    continue;
+                }
+                _ => return Err(invalid(format!("unknown record: {}", kind))),
+            };
+            // 弹出不是当前节点父目录的祖先，并挂载到其父节点上
+            while stack.len() > 1
+                && Path::new(&node.path).parent() != Some(Path::new(&stack[stack.len() - 1].path))
+            {
+                fold(&mut stack);
+            }
+            stack.push(node);
+            // 文件和未加载的目录不会包含子节点，直接挂载
+            if stack.len() > 1 && kind != "D" {
+                fold(&mut stack);
+            }
+        }
+        while stack.len() > 1 {
+            fold(&mut stack);
+        }
+        let root = stack.pop().ok_or_else(|| invalid("empty checkpoint"))?;
+        Ok(CheckpointState { root, pending })
+    }
+
+    /// 删除检查点文件，文件不存在时忽略
+    pub(crate) fn remove(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 按先序写入节点及其子节点
+fn write_node<W: Write>(writer: &mut W, node: &TreeNode) -> io::Result<()> {
+    let kind = match (node.is_dir, &node.children) {
+        (false, _) => "F",
+        (true, None) => "U",
+        (true, Some(_)) => "D",
+    };
+    writeln!(writer, "{}\t{}", kind, escape(&node.path))?;
+    for child in node.children.iter().flatten() {
+        write_node(writer, child)?;
+    }
+    Ok(())
+}
+
+/// 将栈顶节点挂载到其父节点上
+fn fold(stack: &mut Vec<TreeNode>) {
+    let node = stack.pop().unwrap();
+    if let Some(parent) = stack.last_mut() {
+        parent.children.get_or_insert_with(Vec::new).push(node);
+    }
+}
+
+/// 转义路径中的反斜杠、制表符与换行符，保证每条记录恰好占一行且字段可以按制表符分隔
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 还原 `escape` 转义的路径
+fn unescape(field: &str) -> Result<String, AruiError> {
+    let mut path = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            path.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => path.push('\\'),
+            Some('t') => path.push('\t'),
+            Some('r') => path.push('\r'),
+            Some('n') => path.push('\n'),
+            _ => return Err(invalid(format!("malformed escape: {}", field))),
+        }
+    }
+    Ok(path)
+}
+
+/// 检查点文件格式错误
+pub(crate) fn invalid<S: Into<String>>(message: S) -> AruiError {
+    AruiError::InvalidCheckpoint(message.into())
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_id;
+
+    #[test]
+    fn test_save_and_load() {
+        let mut root = TreeNode::new("/root", true);
+        let mut src = TreeNode::new("/root/src", true);
+        src.children = Some(vec![TreeNode::new("/root/src/lib.rs", false)]);
+        let mut pending = TreeNode::new("/root/tests", true);
+        pending.children = None;
+        root.children = Some(vec![src, pending, TreeNode::new("/root/README.md", false)]);
+
+        let path = std::env::temp_dir().join(generate_id());
+        let checkpoint = Checkpoint::new(path.to_string_lossy());
+        checkpoint.save(&root, [("/root/tests", 1)]).unwrap();
+        assert!(checkpoint.exists());
+
+        let state = checkpoint.load().unwrap();
+        assert_eq!(state.pending, vec![("/root/tests".to_string(), 1)]);
+        let children = state.root.children.as_ref().unwrap();
+        assert_eq!(children.len(), 3);
+        assert_eq!(
+            children[0].children.as_ref().unwrap()[0].path,
+            "/root/src/lib.rs"
+        );
+        assert!(!children[1].is_loaded());
+        assert!(!children[2].is_dir);
+
+        checkpoint.remove().unwrap();
+        assert!(!checkpoint.exists());
+        // 重复删除不报错
+        checkpoint.remove().unwrap();
+    }
+
+    #[test]
+    fn test_escaped_names() {
+        let mut root = TreeNode::new("/root", true);
+        let mut dir = TreeNode::new("/root/tab\tdir", true);
+        dir.children = None;
+        root.children = Some(vec![
+            dir,
+            TreeNode::new("/root/line\nbreak\r.rs", false),
+            TreeNode::new("/root/back\\slash\\t.rs", false),
+        ]);
+        let path = std::env::temp_dir().join(generate_id());
+        let checkpoint = Checkpoint::new(path.to_string_lossy());
+        checkpoint.save(&root, [("/root/tab\tdir", 1)]).unwrap();
+        let state = checkpoint.load().unwrap();
+        checkpoint.remove().unwrap();
+        assert_eq!(state.pending, vec![("/root/tab\tdir".to_string(), 1)]);
+        let paths: Vec<&str> = state
+            .root
+            .children
+            .iter()
+            .flatten()
+            .map(|child| child.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/root/tab\tdir",
+                "/root/line\nbreak\r.rs",
+                "/root/back\\slash\\t.rs"
+            ]
+        );
+        assert_eq!(escape("a\\t"), "a\\\\t");
+        assert!(unescape("a\\x").is_err());
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        let path = std::env::temp_dir().join(generate_id());
+        fs::write(&path, "not a checkpoint").unwrap();
+        let checkpoint = Checkpoint::new(path.to_string_lossy());
+        assert!(matches!(
+            checkpoint.load(),
+            Err(AruiError::InvalidCheckpoint(_))
+        ));
+        checkpoint.remove().unwrap();
+    }
+}
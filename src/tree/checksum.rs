@@ -0,0 +1,85 @@
+//! # 校验和清单
+//! 需要开启 `hash` 特性。
+//! 根据总结时计算的 SHA-256 摘要生成与 `sha256sum` 兼容的校验和清单，每个文件一行，格式为 `<摘要>  <相对路径>`，
+//! 可直接通过 `sha256sum -c` 校验，用于为发布的文件生成完整性清单。
+//! 清单仅使用已有的总结信息，不会再次读取文件；未计算摘要的文件（如超出大小上限、无法读取或特殊文件）不会写入。
+use crate::tree::node::TreeNode;
+use std::io::{Result, Write};
+use std::path::Path;
+
+/// 将节点下所有文件的校验和写入 `writer`，按相对路径的字典序排列，返回写入的文件数量
+/// - root：根节点
+/// - base：计算相对路径的基准路径，通常为项目根路径，不以其开头的路径原样写入
+///
+/// 路径以 `/` 分隔；路径中包含反斜杠或换行符时，与 `sha256sum` 一致地转义，并在行首加上 `\`
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::checksum;
+/// use arui_core::tree::node::TreeNode;
+///
+/// let mut root = TreeNode::new("./release", true);
+/// let mut file = TreeNode::new("./release/app.bin", false);
+/// file.summary.digest = Some([0xab; 32]);
+/// root.children = Some(vec![file]);
+/// let mut output = Vec::new();
+/// assert_eq!(checksum::write(&root, "./release", &mut output).unwrap(), 1);
+/// assert_eq!(String::from_utf8(output).unwrap(), format!("{}  app.bin\n", "ab".repeat(32)));
+/// ```
+pub fn write<P: AsRef<Path>, W: Write>(root: &TreeNode, base: P, mut writer: W) -> Result<u64> {
+    let base = base.as_ref();
+    let mut entries = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(children) = &node.children {
+            stack.extend(children);
+        } else if let (false, Some(digest)) = (node.is_dir, node.summary.digest_hex()) {
+            entries.push((relative_path(Path::new(&node.path), base), digest));
+        }
+    }
+    entries.sort();
+    for (path, digest) in &entries {
+        if path.contains(['\\', '\n', '\r']) {
+            let escaped = path
+                .replace('\\', "\\\\")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r");
+            writeln!(writer, "\\{}  {}", digest, escaped)?;
+        } else {
+            writeln!(writer, "{}  {}", digest, path)?;
+        }
+    }
+    Ok(entries.len() as u64)
+}
+
+/// 相对于基准路径的路径，各部分以 `/` 连接
+fn relative_path(path: &Path, base: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaped_path() {
+        let mut root = TreeNode::new("/release", true);
+        let mut file = TreeNode::new("/release/a\\b\nc", false);
+        file.summary.digest = Some([0; 32]);
+        let mut unhashed = TreeNode::new("/release/huge.bin", false);
+        unhashed.summary.size = 1;
+        root.children = Some(vec![file, unhashed]);
+        let mut output = Vec::new();
+        assert_eq!(write(&root, "/release", &mut output).unwrap(), 1);
+        let expected = format!("\\{}  a\\\\b\\nc\n", "0".repeat(64));
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+}
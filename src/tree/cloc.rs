@@ -0,0 +1,164 @@
+//! # 语言统计表
+//! 将项目中各语言的文件数量与代码、注释、空白行数输出为 cloc 或 tokei 风格的文本表格，
+//! 布局与两者的默认输出一致，可以直接替换解析其输出的既有脚本。
+//! 统计只包含识别出语言的文件，与 cloc、tokei 一样忽略无法识别的文件；未获取总结信息的项目树中各项统计为 0。
+use crate::tree::language::Language;
+use crate::tree::node::TreeNode;
+use crate::tree::summary::LineStats;
+use std::collections::HashMap;
+use std::io::{Result, Write};
+
+/// 表格风格
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TableStyle {
+    /// cloc 风格：列依次为 files、blank、comment、code，按代码行数降序排列，合计行为 `SUM:`
+    #[default]
+    Cloc,
+    /// tokei 风格：列依次为 Files、Lines、Code、Comments、Blanks，按语言名称排列，合计行为 `Total`
+    Tokei,
+}
+
+/// 单个语言的统计信息
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LanguageRow {
+    /// 文件数量
+    pub files: u64,
+    /// 代码、注释与空白行数
+    pub lines: LineStats,
+}
+
+/// 按语言汇总节点下所有文件的统计信息
+pub fn collect(root: &TreeNode) -> HashMap<Language, LanguageRow> {
+    let mut rows: HashMap<Language, LanguageRow> = HashMap::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(children) = &node.children {
+            stack.extend(children);
+        } else if let (false, Some(language)) = (node.is_dir, node.summary.language) {
+            let row = rows.entry(language).or_default();
+            row.files += 1;
+            row.lines.merge(&node.summary.lines);
+        }
+    }
+    rows
+}
+
+/// 将节点下各语言的统计表写入 `writer`
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::cloc::{self, TableStyle};
+/// use arui_core::tree::language::Language;
+/// use arui_core::tree::node::TreeNode;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// let mut file = TreeNode::new("./project/main.rs", false);
+/// file.summary.language = Some(Language::Rust);
+/// file.summary.lines.code = 10;
+/// root.children = Some(vec![file]);
+/// let mut output = Vec::new();
+/// cloc::write(&root, TableStyle::Cloc, &mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.lines().any(|line| line.starts_with("Rust ") && line.ends_with(" 10")));
+/// ```
+pub fn write<W: Write>(root: &TreeNode, style: TableStyle, mut writer: W) -> Result<()> {
+    let mut rows: Vec<(&str, LanguageRow)> = collect(root)
+        .into_iter()
+        .map(|(language, row)| (language.name(), row))
+        .collect();
+    let mut total = LanguageRow::default();
+    for (_, row) in &rows {
+        total.files += row.files;
+        total.lines.merge(&row.lines);
+    }
+    let (rule, label) = match style {
+        TableStyle::Cloc => {
+            rows.sort_by(|(a, x), (b, y)| y.lines.code.cmp(&x.lines.code).then(a.cmp(b)));
+            ("-".repeat(79), "SUM:")
+        }
+        TableStyle::Tokei => {
+            rows.sort_by_key(|(name, _)| *name);
+            ("=".repeat(79), "Total")
+        }
+    };
+    writeln!(writer, "{}", rule)?;
+    writeln!(writer, "{}", header(style))?;
+    writeln!(writer, "{}", rule)?;
+    for (name, row) in &rows {
+        writeln!(writer, "{}", format_row(style, name, row))?;
+    }
+    writeln!(writer, "{}", rule)?;
+    writeln!(writer, "{}", format_row(style, label, &total))?;
+    writeln!(writer, "{}", rule)
+}
+
+/// 表头行
+fn header(style: TableStyle) -> String {
+    match style {
+        TableStyle::Cloc => format!(
+            "{:<24} {:>9} {:>14} {:>14} {:>14}",
+            "Language", "files", "blank", "comment", "code"
+        ),
+        TableStyle::Tokei => format!(
+            " {:<18} {:>6} {:>12} {:>12} {:>12} {:>12}",
+            "Language", "Files", "Lines", "Code", "Comments", "Blanks"
+        ),
+    }
+}
+
+/// 单个语言或合计的数据行，列宽与表头一致
+fn format_row(style: TableStyle, name: &str, row: &LanguageRow) -> String {
+    let lines = &row.lines;
+    match style {
+        TableStyle::Cloc => format!(
+            "{:<24} {:>9} {:>14} {:>14} {:>14}",
+            name, row.files, lines.blanks, lines.comments, lines.code
+        ),
+        TableStyle::Tokei => format!(
+            " {:<18} {:>6} {:>12} {:>12} {:>12} {:>12}",
+            name,
+            row.files,
+            lines.total(),
+            lines.code,
+            lines.comments,
+            lines.blanks
+        ),
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout() {
+        let row = LanguageRow {
+            files: 2,
+            lines: LineStats {
+                code: 1000,
+                comments: 50,
+                blanks: 100,
+            },
+        };
+        // 与 cloc、tokei 的输出逐字符对齐
+        assert_eq!(
+            header(TableStyle::Cloc),
+            "Language                     files          blank        comment           code"
+        );
+        assert_eq!(
+            format_row(TableStyle::Cloc, "Rust", &row),
+            "Rust                             2            100             50           1000"
+        );
+        assert_eq!(
+            header(TableStyle::Tokei),
+            " Language            Files        Lines         Code     Comments       Blanks"
+        );
+        assert_eq!(
+            format_row(TableStyle::Tokei, "Rust", &row),
+            " Rust                    2         1150         1000           50          100"
+        );
+    }
+}
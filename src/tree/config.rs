@@ -1,17 +1,115 @@
 //! # 项目树行为配置
 //! 通过设置配置字段，我们可以控制构建、分析等操作中的细节行为，比如排除符合某规则的路径、忽略某文件的总结信息。
 use derive_builder::Builder;
+use std::cmp::Ordering;
+
+/// 常用的代码标记
+pub const DEFAULT_MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// 子节点排序方式
+/// - `DirsFirst` 目录在前，同类节点按名称的字典序排列（默认）
+/// - `Name` 仅按名称的字典序排列
+/// - `Unordered` 保持文件系统返回的顺序，不同平台上的结果可能不同
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum ChildOrder {
+    #[default]
+    DirsFirst,
+    Name,
+    Unordered,
+}
+
+impl ChildOrder {
+    /// 比较两个节点的先后顺序
+    /// - name：节点名称
+    /// - is_dir：是否是目录
+    pub(crate) fn compare(&self, a: (&str, bool), b: (&str, bool)) -> Ordering {
+        match self {
+            ChildOrder::DirsFirst => b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)),
+            ChildOrder::Name => a.0.cmp(b.0),
+            ChildOrder::Unordered => Ordering::Equal,
+        }
+    }
+}
+
+/// 构建时的遍历策略
+/// - `DepthFirst` 深度优先，逐个目录递归构建完整子树（默认）
+/// - `BreadthFirst` 广度优先，逐层构建，浅层目录总是先于深层目录完成，适合需要优先展示顶层结构的场景
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum WalkStrategy {
+    #[default]
+    DepthFirst,
+    BreadthFirst,
+}
 
 /// 项目树配置对象
 /// - `include` 需要包含的路径的规则
 /// - `eclude` 需要排除的路径的规则
+/// - `max_depth` 构建时展开的最大深度
+/// - `max_file_size` 总结时读取的文件大小上限
+/// - `tolerant` 容错模式，构建时跳过出错的路径并记录警告
+/// - `order` 构建时子节点的排序方式
+/// - `skip_symlinks` 构建时跳过符号链接
+/// - `skip_special` 构建时跳过特殊文件
+/// - `strategy` 构建时的遍历策略
+/// - `sandboxed` 沙箱模式，限制节点路径不得逃逸出项目根路径
+/// - `count_words` 总结时统计文本文件的词数与字符数
+/// - `markers` 总结时统计的代码标记，如 `TODO`、`FIXME`
+/// - `count_tokens` 总结时估算文本文件的 token 数量
+/// - `track_top_n` 总结时记录的最大文件数量
 #[derive(Default, Debug, Builder, PartialEq, Clone)]
 #[builder(default, setter(into))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ProjectConfig {
-    /// 需要包含的路径
+    /// 需要包含的路径，相对于项目根路径
+    /// 不为空时仅构建这些子路径，并保留其相对层级，匹配时同样不受 Unicode 规范化形式的影响
     pub include: Vec<String>,
-    /// 需要排除的路径
+    /// 需要排除的路径，相对于项目根路径，位于这些路径之下的节点均不会被构建
+    /// 比较时不受 Unicode 规范化形式的影响，NFC 形式书写的路径同样可以匹配 NFD 形式存储的文件名
     pub exclude: Vec<String>,
+    /// 构建时展开的最大深度，为空时完整构建
+    /// 超出该深度的目录节点不加载子节点，可在之后按需展开
+    pub max_depth: Option<usize>,
+    /// 总结时读取的文件大小上限（字节），为空时不限制
+    /// 超出上限的文件仅记录大小，不读取其内容，并记录到项目树的 `warnings` 中
+    pub max_file_size: Option<u64>,
+    /// 容错模式，默认关闭
+    /// 开启后构建过程中遇到无法读取的路径（如无权限的目录）不会中断构建，而是记录到项目树的 `warnings` 中并继续扫描
+    pub tolerant: bool,
+    /// 子节点排序方式，默认目录在前、再按名称排序，保证同一目录在不同平台上的构建结果一致
+    pub order: ChildOrder,
+    /// 是否跳过符号链接，默认关闭
+    /// 关闭时符号链接会被跟随，并自动识别链接造成的目录循环；开启后符号链接不会出现在项目树中
+    pub skip_symlinks: bool,
+    /// 是否跳过特殊文件（如管道、套接字、设备文件），默认关闭
+    /// 关闭时特殊文件会被标记为 `NodeMarker::SpecialFile`，且不读取其内容；开启后特殊文件不会出现在项目树中
+    pub skip_special: bool,
+    /// 遍历策略，默认深度优先
+    pub strategy: WalkStrategy,
+    /// 沙箱模式，默认关闭
+    /// 开启后构建时会校验各节点的规范路径，通过符号链接或 `..` 指向项目根路径之外的节点会被标记为 `NodeMarker::OutsideRoot`，
+    /// 既不加载其子节点也不读取其内容，并记录到项目树的 `warnings` 中。适用于扫描不可信的用户上传内容
+    pub sandboxed: bool,
+    /// 是否统计文本文件的词数与字符数，默认关闭
+    /// 适用于文档、内容类仓库，这类仓库中行数难以反映内容的多少；开启后总结时需要额外遍历一次文本内容
+    pub count_words: bool,
+    /// 总结时统计的代码标记，默认为空，即不统计
+    /// 标记按完整单词区分大小写匹配（`TODO` 不会匹配 `TODOS` 或 `todo`），可使用 `with_default_markers` 添加常用标记
+    pub markers: Vec<String>,
+    /// 是否估算文本文件的 token 数量，默认关闭
+    /// 按每 4 个字符约 1 个 token 估算，可用于将代码库输入大语言模型前预估上下文窗口的占用
+    pub count_tokens: bool,
+    /// 总结时为各目录记录的最大文件数量，为空时不记录
+    /// 设置后根节点与各目录的总结信息均包含其下最大的若干个文件（见 `NodeSummary::largest_files`），无需再次遍历项目树
+    pub track_top_n: Option<usize>,
 }
 
 impl ProjectConfig {
@@ -63,6 +161,87 @@ impl ProjectConfig {
         self.exclude.clear();
         self
     }
+
+    /// 设置构建时展开的最大深度
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// 设置总结时读取的文件大小上限
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = Some(max_file_size);
+        self
+    }
+
+    /// 设置子节点排序方式
+    pub fn with_order(mut self, order: ChildOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// 设置是否跳过符号链接
+    pub fn with_skip_symlinks(mut self, skip_symlinks: bool) -> Self {
+        self.skip_symlinks = skip_symlinks;
+        self
+    }
+
+    /// 设置是否跳过特殊文件
+    pub fn with_skip_special(mut self, skip_special: bool) -> Self {
+        self.skip_special = skip_special;
+        self
+    }
+
+    /// 设置遍历策略
+    pub fn with_strategy(mut self, strategy: WalkStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// 设置是否开启沙箱模式
+    pub fn with_sandbox(mut self, sandboxed: bool) -> Self {
+        self.sandboxed = sandboxed;
+        self
+    }
+
+    /// 设置是否统计词数与字符数
+    pub fn with_count_words(mut self, count_words: bool) -> Self {
+        self.count_words = count_words;
+        self
+    }
+
+    /// 添加需要统计的代码标记
+    pub fn add_markers<I, S>(mut self, markers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.markers.extend(markers.into_iter().map(Into::into));
+        self
+    }
+
+    /// 添加常用的代码标记，见 `DEFAULT_MARKERS`
+    pub fn with_default_markers(self) -> Self {
+        self.add_markers(DEFAULT_MARKERS)
+    }
+
+    /// 设置是否估算 token 数量
+    pub fn with_count_tokens(mut self, count_tokens: bool) -> Self {
+        self.count_tokens = count_tokens;
+        self
+    }
+
+    /// 设置总结时记录的最大文件数量
+    pub fn with_track_top_n(mut self, track_top_n: usize) -> Self {
+        self.track_top_n = Some(track_top_n);
+        self
+    }
+
+    /// 设置是否开启容错模式
+    pub fn with_tolerant(mut self, tolerant: bool) -> Self {
+        self.tolerant = tolerant;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -121,4 +300,49 @@ mod tests {
         assert_eq!(config.include.len(), 0);
         assert_eq!(config.exclude.len(), 0);
     }
+
+    #[test]
+    // 测试子节点排序
+    fn test_child_order() {
+        let mut names = vec![
+            ("b.rs", false),
+            ("src", true),
+            ("a.rs", false),
+            ("docs", true),
+        ];
+        names.sort_by(|a, b| ChildOrder::DirsFirst.compare(*a, *b));
+        assert_eq!(
+            names,
+            vec![
+                ("docs", true),
+                ("src", true),
+                ("a.rs", false),
+                ("b.rs", false)
+            ]
+        );
+        names.sort_by(|a, b| ChildOrder::Name.compare(*a, *b));
+        assert_eq!(
+            names,
+            vec![
+                ("a.rs", false),
+                ("b.rs", false),
+                ("docs", true),
+                ("src", true)
+            ]
+        );
+    }
+
+    #[test]
+    // 测试设置最大深度
+    fn test_max_depth() {
+        let config = ProjectConfig::new();
+        assert_eq!(config.max_depth, None);
+        let config = config.with_max_depth(2);
+        assert_eq!(config.max_depth, Some(2));
+        let built = ProjectConfigBuilder::default()
+            .max_depth(2)
+            .build()
+            .unwrap();
+        assert_eq!(config, built);
+    }
 }
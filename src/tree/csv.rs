@@ -0,0 +1,153 @@
+//! # CSV 导出
+//! 将项目树中的文件节点展开为 CSV 表格，每个文件一行，可直接导入电子表格或 pandas 等工具分析，
+//! 无需自行遍历项目树。
+//! 行按项目树中的顺序（先序遍历）排列，目录节点不输出；未获取总结信息的项目树中各项统计为 0 或空。
+//! 字段中包含逗号、双引号或换行符时按 RFC 4180 的规则加引号转义。
+use crate::errors::AruiError;
+use crate::tree::export::{self, TreeExporter};
+use crate::tree::node::TreeNode;
+use std::io::Write;
+use std::time::UNIX_EPOCH;
+
+/// 表头，依次为：
+/// - path：文件路径
+/// - extension：文件后缀，没有后缀时为空
+/// - language：识别到的语言，无法识别时为空
+/// - size、disk_size：文件大小与实际分配的磁盘大小
+/// - lines、code、comments、blanks：总行数以及代码、注释、空白行数
+/// - binary：是否为二进制文件，未读取内容时为空
+/// - encoding、mime：文本编码与 MIME 类型，无法识别时为空
+/// - origin：代码来源（`generated` 或 `vendored`），项目自身的代码为空
+/// - mtime：最后修改时间（Unix 时间戳，单位为秒），无法获取时为空
+pub const HEADER: [&str; 14] = [
+    "path",
+    "extension",
+    "language",
+    "size",
+    "disk_size",
+    "lines",
+    "code",
+    "comments",
+    "blanks",
+    "binary",
+    "encoding",
+    "mime",
+    "origin",
+    "mtime",
+];
+
+/// 将节点下的所有文件写入 `writer`，包含表头，行以 `\n` 结尾
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::csv;
+/// use arui_core::tree::node::TreeNode;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// root.children = Some(vec![TreeNode::new("./project/a,b.rs", false)]);
+/// let mut output = Vec::new();
+/// csv::write(&root, &mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.starts_with("path,extension,"));
+/// assert!(output.lines().nth(1).unwrap().starts_with("\"./project/a,b.rs\","));
+/// ```
+pub fn write<W: Write>(root: &TreeNode, writer: W) -> Result<(), AruiError> {
+    export::walk(root, &mut CsvExporter::new(writer))
+}
+
+/// CSV 导出器，开始时写入表头，之后每个文件节点写入一行
+pub struct CsvExporter<W: Write> {
+    /// 输出目标
+    writer: W,
+}
+
+impl<W: Write> CsvExporter<W> {
+    /// 创建写入 `writer` 的 CSV 导出器
+    pub fn new(writer: W) -> Self {
+        CsvExporter { writer }
+    }
+}
+
+impl<W: Write> TreeExporter for CsvExporter<W> {
+    fn visit_root(&mut self, _root: &TreeNode) -> Result<(), AruiError> {
+        writeln!(self.writer, "{}", HEADER.join(","))?;
+        Ok(())
+    }
+
+    fn visit_node(&mut self, node: &TreeNode, _depth: usize) -> Result<(), AruiError> {
+        if node.children.is_none() && !node.is_dir {
+            writeln!(self.writer, "{}", row(node).join(","))?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), AruiError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// 文件节点对应的一行，字段顺序与 `HEADER` 一致
+fn row(node: &TreeNode) -> Vec<String> {
+    let summary = &node.summary;
+    let mtime = summary
+        .updated_at
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs().to_string());
+    vec![
+        escape(&node.path),
+        escape(summary.suffixes.first().map_or("", String::as_str)),
+        summary
+            .language
+            .map(|language| language.name())
+            .unwrap_or_default()
+            .to_string(),
+        summary.size.to_string(),
+        summary.disk_size.to_string(),
+        summary.count.to_string(),
+        summary.lines.code.to_string(),
+        summary.lines.comments.to_string(),
+        summary.lines.blanks.to_string(),
+        summary
+            .is_binary
+            .map(|binary| binary.to_string())
+            .unwrap_or_default(),
+        summary
+            .encoding
+            .map(|encoding| encoding.name())
+            .unwrap_or_default()
+            .to_string(),
+        escape(summary.mime.as_deref().unwrap_or_default()),
+        summary
+            .origin
+            .map(|origin| origin.name())
+            .unwrap_or_default()
+            .to_string(),
+        mtime.unwrap_or_default(),
+    ]
+}
+
+/// 按 RFC 4180 转义字段：包含逗号、双引号或换行符时加引号，并将双引号写为两个双引号
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("src/main.rs"), "src/main.rs");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape("line\nbreak"), "\"line\nbreak\"");
+    }
+}
@@ -0,0 +1,273 @@
+//! # 项目树变更
+//! 比较同一项目在两次扫描之间的差异，记录新增、删除与内容发生变化的节点及其变化前后的统计信息，
+//! 由 `ProjectTree::refresh` 生成，适用于长期运行的监控场景。
+//! 开启 `serde` 特性后变更集合可以序列化，开启 `json` 特性后可直接导出为 JSON，
+//! 便于在 CI 中生成机器可读的体积变化报告。
+#[cfg(feature = "json")]
+use crate::errors::AruiError;
+use crate::tree::node::TreeNode;
+use crate::tree::summary::NodeSummary;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// 节点的统计信息，用于比较变化前后的差异
+/// - size：大小
+/// - lines：文本行数
+/// - files：文件数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metrics {
+    /// 大小，目录为其下所有文件之和
+    pub size: u64,
+    /// 文本行数，目录为其下所有文件之和
+    pub lines: u64,
+    /// 文件数量，文件节点为 1
+    pub files: u64,
+}
+
+impl From<&NodeSummary> for Metrics {
+    fn from(summary: &NodeSummary) -> Self {
+        Metrics {
+            size: summary.size,
+            lines: summary.count,
+            files: summary.file_count,
+        }
+    }
+}
+
+/// 单个节点变化前后的统计信息
+/// - before：变化前，新增的节点为空
+/// - after：变化后，删除的节点为空
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricChange {
+    /// 变化前的统计信息
+    pub before: Option<Metrics>,
+    /// 变化后的统计信息
+    pub after: Option<Metrics>,
+}
+
+impl MetricChange {
+    /// 大小的变化量，删除时为负数
+    pub fn size_delta(&self) -> i64 {
+        delta(
+            self.before.map(|metrics| metrics.size),
+            self.after.map(|metrics| metrics.size),
+        )
+    }
+
+    /// 行数的变化量，删除时为负数
+    pub fn lines_delta(&self) -> i64 {
+        delta(
+            self.before.map(|metrics| metrics.lines),
+            self.after.map(|metrics| metrics.lines),
+        )
+    }
+}
+
+/// 项目树变更集合，各列表中的路径按字典序排列
+/// - added：新增的节点
+/// - removed：删除的节点
+/// - modified：总结信息发生变化的文件
+/// - metrics：各变更节点变化前后的统计信息
+/// - total：根节点变化前后的统计信息
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChangeSet {
+    /// 新增的节点路径
+    pub added: Vec<String>,
+    /// 删除的节点路径
+    pub removed: Vec<String>,
+    /// 总结信息（大小、行数）发生变化的文件路径
+    pub modified: Vec<String>,
+    /// 以路径为键，记录 `added`、`removed` 与 `modified` 中各节点变化前后的统计信息
+    /// 节点类型改变（同时出现在 `added` 与 `removed` 中）时，`before` 与 `after` 分别为原节点与新节点
+    pub metrics: BTreeMap<String, MetricChange>,
+    /// 根节点变化前后的统计信息，即整个项目的规模变化
+    pub total: MetricChange,
+}
+
+impl ChangeSet {
+    /// 比较新旧两棵树，生成变更集合
+    /// 节点在新旧树中类型不同（文件与目录互换）时，视为删除后重新新增
+    pub(crate) fn diff(old: &TreeNode, new: &TreeNode) -> Self {
+        let old_nodes = flatten(old);
+        let new_nodes = flatten(new);
+        let mut changes = ChangeSet {
+            total: MetricChange {
+                before: Some(Metrics::from(&old.summary)),
+                after: Some(Metrics::from(&new.summary)),
+            },
+            ..ChangeSet::default()
+        };
+        for (path, node) in &new_nodes {
+            let previous = old_nodes.get(path);
+            match previous {
+                None => changes.added.push(path.to_string()),
+                Some(previous) if previous.is_dir != node.is_dir => {
+                    changes.removed.push(path.to_string());
+                    changes.added.push(path.to_string());
+                }
+                Some(previous) => {
+                    if !node.is_dir
+                        && (previous.summary.size != node.summary.size
+                            || previous.summary.count != node.summary.count)
+                    {
+                        changes.modified.push(path.to_string());
+                    } else {
+                        continue;
+                    }
+                }
+            }
+            changes.metrics.insert(
+                path.to_string(),
+                MetricChange {
+                    before: previous.map(|previous| Metrics::from(&previous.summary)),
+                    after: Some(Metrics::from(&node.summary)),
+                },
+            );
+        }
+        for (path, node) in old_nodes
+            .iter()
+            .filter(|(path, _)| !new_nodes.contains_key(*path))
+        {
+            changes.removed.push(path.to_string());
+            changes.metrics.insert(
+                path.to_string(),
+                MetricChange {
+                    before: Some(Metrics::from(&node.summary)),
+                    after: None,
+                },
+            );
+        }
+        changes.removed.sort();
+        changes
+    }
+
+    /// 是否没有任何变更
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// 将变更集合导出为 JSON 字符串，需要开启 `json` 特性
+    /// - pretty：是否格式化输出
+    ///
+    /// 导出结果为一个对象，字段名与结构体字段一致；`metrics` 中的每项包含 `before` 与 `after`，
+    /// 分别为 `size`、`lines` 与 `files`，不存在时为 `null`
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, pretty: bool) -> Result<String, AruiError> {
+        let json = if pretty {
+            serde_json::to_string_pretty(self)?
+        } else {
+            serde_json::to_string(self)?
+        };
+        Ok(json)
+    }
+}
+
+impl Display for ChangeSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for path in &self.added {
+            writeln!(f, "+ {}", path)?;
+        }
+        for path in &self.removed {
+            writeln!(f, "- {}", path)?;
+        }
+        for path in &self.modified {
+            writeln!(f, "~ {}", path)?;
+        }
+        Ok(())
+    }
+}
+
+/// 变化前后的差值，不存在的一方视为 0
+fn delta(before: Option<u64>, after: Option<u64>) -> i64 {
+    let before = i64::try_from(before.unwrap_or_default()).unwrap_or(i64::MAX);
+    let after = i64::try_from(after.unwrap_or_default()).unwrap_or(i64::MAX);
+    after.saturating_sub(before)
+}
+
+/// 将树展开为以路径为键的节点表
+fn flatten(node: &TreeNode) -> BTreeMap<&str, &TreeNode> {
+    let mut nodes = BTreeMap::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        nodes.insert(node.path.as_str(), node);
+        stack.extend(node.children.iter().flatten());
+    }
+    nodes
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut old = TreeNode::new("/root", true);
+        let mut kept = TreeNode::new("/root/kept.rs", false);
+        kept.summary.size = 10;
+        old.children = Some(vec![
+            kept.clone(),
+            TreeNode::new("/root/removed.rs", false),
+            TreeNode::new("/root/changed", false),
+        ]);
+        let mut new = TreeNode::new("/root", true);
+        let mut modified = kept.clone();
+        modified.summary.count = 2;
+        new.children = Some(vec![
+            modified,
+            TreeNode::new("/root/added.rs", false),
+            TreeNode::new("/root/changed", true),
+        ]);
+
+        let changes = ChangeSet::diff(&old, &new);
+        assert_eq!(changes.added, vec!["/root/added.rs", "/root/changed"]);
+        assert_eq!(changes.removed, vec!["/root/changed", "/root/removed.rs"]);
+        assert_eq!(changes.modified, vec!["/root/kept.rs"]);
+        assert!(ChangeSet::diff(&old, &old).is_empty());
+        assert_eq!(
+            changes.to_string(),
+            "+ /root/added.rs\n+ /root/changed\n- /root/changed\n- /root/removed.rs\n~ /root/kept.rs\n"
+        );
+
+        // 变化前后的统计信息
+        assert_eq!(changes.metrics.len(), 4);
+        let kept = changes.metrics["/root/kept.rs"];
+        assert_eq!(kept.before.unwrap().size, 10);
+        assert_eq!((kept.size_delta(), kept.lines_delta()), (0, 2));
+        let removed = changes.metrics["/root/removed.rs"];
+        assert!(removed.before.is_some() && removed.after.is_none());
+        let changed = changes.metrics["/root/changed"];
+        assert!(changed.before.is_some() && changed.after.is_some());
+        assert!(changes.total.before.is_some());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_report() {
+        let mut old = TreeNode::new("/root", true);
+        let mut file = TreeNode::new("/root/app.js", false);
+        file.summary.size = 100;
+        old.summary.size = 100;
+        old.children = Some(vec![file.clone()]);
+        let mut new = old.clone();
+        file.summary.size = 150;
+        new.summary.size = 150;
+        new.children = Some(vec![file]);
+
+        let changes = ChangeSet::diff(&old, &new);
+        let report: serde_json::Value =
+            serde_json::from_str(&changes.to_json(false).unwrap()).unwrap();
+        assert_eq!(report["modified"], serde_json::json!(["/root/app.js"]));
+        assert_eq!(report["metrics"]["/root/app.js"]["before"]["size"], 100);
+        assert_eq!(report["metrics"]["/root/app.js"]["after"]["size"], 150);
+        assert_eq!(report["total"]["after"]["size"], 150);
+        assert_eq!(changes.total.size_delta(), 50);
+        let restored: ChangeSet = serde_json::from_str(&changes.to_json(true).unwrap()).unwrap();
+        assert_eq!(restored, changes);
+    }
+}
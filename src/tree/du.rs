@@ -0,0 +1,41 @@
+//! # du 风格输出
+//! 按 `du -b` 的格式输出项目树，每行为 `<大小>\t<路径>`，大小为以字节为单位的表观大小，
+//! 目录的大小为其下所有文件之和，便于既有的磁盘占用工具与看板直接读取。
+//! 与 `du` 一致，节点按后序遍历的顺序输出，即目录位于其下所有节点之后，根节点位于最后一行；
+//! 默认只输出目录，`all` 为真时同时输出文件（对应 `du -ab`）。
+//! 目录自身的大小（如目录项占用的块）不计入，因此目录的大小可能略小于 `du -b` 的结果。
+use crate::tree::node::TreeNode;
+use std::io::{Result, Write};
+
+/// 将节点及其下的节点写入 `writer`，行以 `\n` 结尾
+/// - all：是否同时输出文件
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::du;
+/// use arui_core::tree::node::TreeNode;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// let mut file = TreeNode::new("./project/main.rs", false);
+/// file.summary.size = 13;
+/// root.summary.size = 13;
+/// root.children = Some(vec![file]);
+/// let mut output = Vec::new();
+/// du::write(&root, true, &mut output).unwrap();
+/// assert_eq!(String::from_utf8(output).unwrap(), "13\t./project/main.rs\n13\t./project\n");
+/// ```
+pub fn write<W: Write>(root: &TreeNode, all: bool, mut writer: W) -> Result<()> {
+    write_node(root, all, &mut writer)
+}
+
+/// 递归写入节点：先写入子节点，再写入节点自身
+fn write_node<W: Write>(node: &TreeNode, all: bool, writer: &mut W) -> Result<()> {
+    for child in node.children.iter().flatten() {
+        write_node(child, all, writer)?;
+    }
+    if node.is_dir || all {
+        writeln!(writer, "{}\t{}", node.summary.size, node.path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! # 重复文件检测
+//! 需要开启 `hash` 特性。
+//! 根据总结时计算的文件大小与内容摘要，找出项目树中内容完全相同的文件，并计算重复占用的磁盘大小。
+//! 检测仅使用已有的总结信息，不会再次读取文件；空文件与未计算摘要的文件不参与检测。
+use crate::tree::node::TreeNode;
+use std::collections::HashMap;
+
+/// 一组内容完全相同的文件
+/// - size：单个文件的大小
+/// - digest：文件内容的 SHA-256 摘要
+/// - paths：文件路径，按字典序排列
+/// - wasted：重复占用的磁盘大小，即除保留一份外其余文件的大小之和
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// 单个文件的大小
+    pub size: u64,
+    /// 文件内容的 SHA-256 摘要
+    pub digest: [u8; 32],
+    /// 文件路径，按字典序排列
+    pub paths: Vec<String>,
+    /// 重复占用的磁盘大小
+    pub wasted: u64,
+}
+
+/// 找出节点下内容相同的文件，按重复占用的大小从大到小排列，相同时按首个路径排列
+pub(crate) fn find_duplicates(root: &TreeNode) -> Vec<DuplicateGroup> {
+    let mut files: HashMap<(u64, [u8; 32]), Vec<String>> = HashMap::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if let Some(children) = &node.children {
+            stack.extend(children);
+            continue;
+        }
+        if node.is_dir || node.summary.size == 0 {
+            continue;
+        }
+        if let Some(digest) = node.summary.digest {
+            files
+                .entry((node.summary.size, digest))
+                .or_default()
+                .push(node.path.clone());
+        }
+    }
+    let mut groups: Vec<DuplicateGroup> = files
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|((size, digest), mut paths)| {
+            paths.sort();
+            DuplicateGroup {
+                size,
+                digest,
+                wasted: size * (paths.len() as u64 - 1),
+                paths,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.wasted.cmp(&a.wasted).then(a.paths.cmp(&b.paths)));
+    groups
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::fs::MemoryFs;
+    use crate::tree::root::ProjectTree;
+
+    #[test]
+    fn test_find_duplicates() {
+        let fs = MemoryFs::new()
+            .add_file("project/small/a", "ab")
+            .add_file("project/small/b", "ab")
+            .add_file("project/small/c", "ab")
+            .add_file("project/large/a", "abcdef")
+            .add_file("project/large/b", "abcdef")
+            // 大小相同但内容不同
+            .add_file("project/large/c", "abcdeg")
+            .add_file("project/empty/a", "")
+            .add_file("project/empty/b", "");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.find_duplicates().is_empty());
+        tree.analyze().expect("analyze failed");
+        let groups = tree.find_duplicates();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].paths, vec!["project/large/a", "project/large/b"]);
+        assert_eq!((groups[0].size, groups[0].wasted), (6, 6));
+        assert_eq!(groups[1].paths.len(), 3);
+        assert_eq!((groups[1].size, groups[1].wasted), (2, 4));
+    }
+}
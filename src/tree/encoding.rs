@@ -0,0 +1,162 @@
+//! # 文本编码识别
+//! 根据文件内容推断文本编码，用于审查遗留代码库中尚未转换为 UTF-8 的文件。
+//! 识别顺序如下：
+//! - BOM：UTF-8 BOM、UTF-16LE、UTF-16BE
+//! - 没有 BOM 的 UTF-16：ASCII 字符的高位字节为空字节，因此空字节集中出现在奇数或偶数位置
+//! - UTF-8：内容为合法的 UTF-8 文本（纯 ASCII 同样视为 UTF-8）
+//! - GBK：所有非 ASCII 字节均能组成合法的 GBK 双字节字符
+//! - Latin-1：不包含空字节等控制字符的其余内容
+//!
+//! GBK 与 Latin-1 的识别基于启发式规则，较短的文本可能误判。
+use std::fmt::Display;
+
+/// 判断编码时检查的开头字节数
+const SNIFF_LEN: usize = 8192;
+
+/// 文本编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum Encoding {
+    /// UTF-8，包括纯 ASCII 文本
+    Utf8,
+    /// 以 BOM 开头的 UTF-8
+    Utf8Bom,
+    /// 小端序 UTF-16
+    Utf16Le,
+    /// 大端序 UTF-16
+    Utf16Be,
+    /// GBK（兼容 GB2312）
+    Gbk,
+    /// Latin-1（ISO-8859-1）
+    Latin1,
+}
+
+impl Encoding {
+    /// 根据内容推断文本编码，内容不像文本（如包含空字节的二进制文件）时返回空
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::encoding::Encoding;
+    ///
+    /// assert_eq!(Encoding::detect("你好".as_bytes()), Some(Encoding::Utf8));
+    /// assert_eq!(Encoding::detect(b"\xef\xbb\xbfhi"), Some(Encoding::Utf8Bom));
+    /// assert_eq!(Encoding::detect(b"h\x00i\x00"), Some(Encoding::Utf16Le));
+    /// assert_eq!(Encoding::detect(b"\xc4\xe3\xba\xc3"), Some(Encoding::Gbk));
+    /// assert_eq!(Encoding::detect(b"\x89PNG\r\n\x1a\n\x00\x00"), None);
+    /// ```
+    pub fn detect(contents: &[u8]) -> Option<Encoding> {
+        if contents.starts_with(b"\xef\xbb\xbf") {
+            return Some(Encoding::Utf8Bom);
+        }
+        if contents.starts_with(b"\xff\xfe") {
+            return Some(Encoding::Utf16Le);
+        }
+        if contents.starts_with(b"\xfe\xff") {
+            return Some(Encoding::Utf16Be);
+        }
+        let block = &contents[..contents.len().min(SNIFF_LEN)];
+        if let Some(encoding) = utf16_without_bom(block) {
+            return Some(encoding);
+        }
+        if std::str::from_utf8(contents).is_ok() {
+            return Some(Encoding::Utf8);
+        }
+        if contents.contains(&0) {
+            return None;
+        }
+        if is_gbk(contents) {
+            return Some(Encoding::Gbk);
+        }
+        let control = |byte: &u8| byte.is_ascii_control() && !b"\t\n\r\x0c\x1b".contains(byte);
+        if contents.iter().any(control) {
+            return None;
+        }
+        Some(Encoding::Latin1)
+    }
+
+    /// 编码名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 BOM",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Gbk => "GBK",
+            Encoding::Latin1 => "ISO-8859-1",
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 识别没有 BOM 的 UTF-16：空字节仅出现在奇数（小端序）或偶数（大端序）位置，且数量不少于字符数的四成
+fn utf16_without_bom(block: &[u8]) -> Option<Encoding> {
+    let chars = block.len() / 2;
+    if chars == 0 {
+        return None;
+    }
+    let zeros = |offset: usize| {
+        block
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|byte| **byte == 0)
+            .count()
+    };
+    let (even, odd) = (zeros(0), zeros(1));
+    if even == 0 && odd * 10 >= chars * 4 {
+        Some(Encoding::Utf16Le)
+    } else if odd == 0 && even * 10 >= chars * 4 {
+        Some(Encoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// 所有非 ASCII 字节是否均能组成合法的 GBK 双字节字符，且至少包含一个双字节字符
+fn is_gbk(contents: &[u8]) -> bool {
+    let mut bytes = contents.iter();
+    let mut found = false;
+    while let Some(&byte) = bytes.next() {
+        if byte.is_ascii() {
+            continue;
+        }
+        match bytes.next() {
+            Some(&trail) if (0x81..=0xfe).contains(&byte) && is_gbk_trail(trail) => found = true,
+            _ => return false,
+        }
+    }
+    found
+}
+
+/// GBK 双字节字符的第二个字节
+fn is_gbk_trail(byte: u8) -> bool {
+    (0x40..=0xfe).contains(&byte) && byte != 0x7f
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Encoding::detect(b""), Some(Encoding::Utf8));
+        assert_eq!(Encoding::detect(b"plain ascii\n"), Some(Encoding::Utf8));
+        assert_eq!(Encoding::detect(b"\xff\xfeh\x00"), Some(Encoding::Utf16Le));
+        assert_eq!(Encoding::detect(b"\x00h\x00i"), Some(Encoding::Utf16Be));
+        // “中文” 的 GBK 编码
+        assert_eq!(Encoding::detect(b"\xd6\xd0\xce\xc4\n"), Some(Encoding::Gbk));
+        // 末尾的 0xe9 无法组成 GBK 双字节字符
+        assert_eq!(Encoding::detect(b"caf\xe9"), Some(Encoding::Latin1));
+        assert_eq!(Encoding::detect(b"\x01\x02\xff"), None);
+        assert_eq!(Encoding::Utf16Le.to_string(), "UTF-16LE");
+    }
+}
@@ -0,0 +1,232 @@
+//! # 自定义导出
+//! 定义导出器接口 `TreeExporter`：遍历由 `walk`（或 `ProjectTree::export`）统一完成，
+//! 导出器只需处理依次访问到的节点，即可实现自定义的导出格式，无需自行遍历项目树。
+//! 内置的 CSV（`csv::CsvExporter`）与 NDJSON（`ndjson::NdjsonExporter`）导出均基于该接口实现；
+//! `JsonExporter` 同样基于该接口，以流式方式写出嵌套的节点，适合导出无法一次性放入内存的项目树。
+use crate::errors::AruiError;
+use crate::tree::node::TreeNode;
+#[cfg(feature = "json")]
+use std::io::Write;
+
+/// 导出器，按以下顺序被调用：
+/// 1. `visit_root`：开始导出，参数为根节点，可用于写入表头等
+/// 2. `visit_node`：按先序遍历的顺序访问每个节点（包括根节点），父节点总是先于子节点
+/// 3. `finish`：所有节点访问完毕，可用于写入结尾或刷新缓冲区
+///
+/// 任一方法返回错误时导出立即终止
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::errors::AruiError;
+/// use arui_core::tree::export::{self, TreeExporter};
+/// use arui_core::tree::node::TreeNode;
+///
+/// /// 按缩进输出节点名称
+/// struct Outline(String);
+///
+/// impl TreeExporter for Outline {
+///     fn visit_node(&mut self, node: &TreeNode, depth: usize) -> Result<(), AruiError> {
+///         self.0 += &format!("{}{}\n", "  ".repeat(depth), node.name());
+///         Ok(())
+///     }
+/// }
+///
+/// let mut root = TreeNode::new("./project", true);
+/// root.children = Some(vec![TreeNode::new("./project/main.rs", false)]);
+/// let mut outline = Outline(String::new());
+/// export::walk(&root, &mut outline).unwrap();
+/// assert_eq!(outline.0, "project\n  main.rs\n");
+/// ```
+pub trait TreeExporter {
+    /// 开始导出，默认不做任何处理
+    fn visit_root(&mut self, root: &TreeNode) -> Result<(), AruiError> {
+        let _ = root;
+        Ok(())
+    }
+
+    /// 访问节点
+    /// - depth：相对根节点的深度，根节点为 0
+    fn visit_node(&mut self, node: &TreeNode, depth: usize) -> Result<(), AruiError>;
+
+    /// 结束导出，默认不做任何处理
+    fn finish(&mut self) -> Result<(), AruiError> {
+        Ok(())
+    }
+}
+
+/// 以 `root` 为根遍历节点，依次调用导出器的各个方法
+pub fn walk<E: TreeExporter + ?Sized>(root: &TreeNode, exporter: &mut E) -> Result<(), AruiError> {
+    exporter.visit_root(root)?;
+    let mut stack = vec![(root, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        exporter.visit_node(node, depth)?;
+        if let Some(children) = &node.children {
+            stack.extend(children.iter().rev().map(|child| (child, depth + 1)));
+        }
+    }
+    exporter.finish()
+}
+
+/// JSON 导出器，需要开启 `json` 特性
+/// 以嵌套的 JSON 对象写出根节点及其下的所有节点，结果与直接序列化根节点（即紧凑格式的 `to_json` 中的 `root` 字段）一致。
+/// 各节点的字段均由 `TreeNode` 自身的序列化得到，仅 `children` 改为边遍历边写入，
+/// 不会在内存中生成完整的 JSON 字符串；`writer` 无缓冲时建议包装为 `BufWriter`
+#[cfg(feature = "json")]
+pub struct JsonExporter<W: Write> {
+    /// 输出目标
+    writer: W,
+    /// 尚未结束的目录：子节点列表之后的剩余内容，以及是否已经写入子节点
+    open: Vec<(String, bool)>,
+}
+
+/// 序列化结果中子节点列表的开头
+#[cfg(feature = "json")]
+const CHILDREN_KEY: &str = "\"children\":[";
+
+#[cfg(feature = "json")]
+impl<W: Write> JsonExporter<W> {
+    /// 创建写入 `writer` 的 JSON 导出器
+    pub fn new(writer: W) -> Self {
+        JsonExporter {
+            writer,
+            open: Vec::new(),
+        }
+    }
+
+    /// 结束最近一个尚未结束的目录
+    fn close(&mut self) -> Result<(), AruiError> {
+        if let Some((tail, _)) = self.open.pop() {
+            self.writer.write_all(tail.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<W: Write> TreeExporter for JsonExporter<W> {
+    fn visit_node(&mut self, node: &TreeNode, depth: usize) -> Result<(), AruiError> {
+        while self.open.len() > depth {
+            self.close()?;
+        }
+        if let Some((_, written)) = self.open.last_mut() {
+            if *written {
+                self.writer.write_all(b",")?;
+            }
+            *written = true;
+        }
+        if node.children.is_none() {
+            serde_json::to_writer(&mut self.writer, node)?;
+            return Ok(());
+        }
+        // 序列化子节点为空的副本，在子节点列表处拆分：之前的部分立即写入，之后的部分在目录结束时写入
+        // 路径等字符串中的引号均被转义，首个 `"children":[` 即为该字段本身
+        let json = serde_json::to_string(&TreeNode {
+            children: Some(Vec::new()),
+            path: node.path.clone(),
+            summary: node.summary.clone(),
+            marker: node.marker.clone(),
+            symlink_target: node.symlink_target.clone(),
+            ..*node
+        })?;
+        let split = json
+            .find(CHILDREN_KEY)
+            .map(|index| index + CHILDREN_KEY.len())
+            .ok_or_else(|| std::io::Error::other("serialized node has no children field"))?;
+        let (head, tail) = json.split_at(split);
+        self.writer.write_all(head.as_bytes())?;
+        self.open.push((tail.to_string(), false));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), AruiError> {
+        while !self.open.is_empty() {
+            self.close()?;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 记录访问顺序的导出器
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl TreeExporter for Recorder {
+        fn visit_root(&mut self, root: &TreeNode) -> Result<(), AruiError> {
+            self.0.push(format!("root {}", root.path));
+            Ok(())
+        }
+
+        fn visit_node(&mut self, node: &TreeNode, depth: usize) -> Result<(), AruiError> {
+            self.0.push(format!("{} {}", depth, node.path));
+            Ok(())
+        }
+
+        fn finish(&mut self) -> Result<(), AruiError> {
+            self.0.push("finish".to_string());
+            Ok(())
+        }
+    }
+
+    fn fixture() -> TreeNode {
+        let mut root = TreeNode::new("/root", true);
+        let mut src = TreeNode::new("/root/src", true);
+        src.children = Some(vec![
+            TreeNode::new("/root/src/main.rs", false),
+            TreeNode::new("/root/src/lib.rs", false),
+        ]);
+        let mut empty = TreeNode::new("/root/empty", true);
+        empty.children = Some(Vec::new());
+        root.children = Some(vec![
+            src,
+            empty,
+            TreeNode::new("/root/unexpanded", true),
+            TreeNode::new("/root/README.md", false),
+        ]);
+        root
+    }
+
+    #[test]
+    fn test_walk_order() {
+        let mut recorder = Recorder::default();
+        walk(&fixture(), &mut recorder).unwrap();
+        assert_eq!(
+            recorder.0,
+            vec![
+                "root /root",
+                "0 /root",
+                "1 /root/src",
+                "2 /root/src/main.rs",
+                "2 /root/src/lib.rs",
+                "1 /root/empty",
+                "1 /root/unexpanded",
+                "1 /root/README.md",
+                "finish",
+            ]
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_exporter() {
+        let mut root = fixture();
+        // 名称中包含字段名的节点不影响拆分
+        let mut tricky = TreeNode::new("/root/\"children\":[", true);
+        tricky.children = Some(vec![TreeNode::new("/root/\"children\":[/a", false)]);
+        tricky.summary.size = 42;
+        root.children.as_mut().unwrap().push(tricky);
+        let mut output = Vec::new();
+        walk(&root, &mut JsonExporter::new(&mut output)).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            serde_json::to_string(&root).unwrap()
+        );
+    }
+}
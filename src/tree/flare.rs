@@ -0,0 +1,65 @@
+//! # 树图导出
+//! 需要开启 `json` 特性。
+//! 将项目树导出为 d3 的 “flare” 层级 JSON（`{name, children, value}`），可直接用于 d3 与 ECharts 的矩形树图（treemap）
+//! 与旭日图（sunburst），无需再转换数据。
+//! 目录节点仅包含 `children`，文件节点仅包含 `value`，目录的值由图表库根据子节点汇总，避免重复计算；
+//! 尚未展开的目录视为叶子节点，其值为已有的总结信息。
+use crate::tree::node::TreeNode;
+use serde::Serialize;
+
+/// 作为节点值的统计指标
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlareMetric {
+    /// 文件大小（字节）
+    #[default]
+    Size,
+    /// 文本行数
+    Lines,
+}
+
+impl FlareMetric {
+    /// 节点在该指标下的值
+    fn value(&self, node: &TreeNode) -> u64 {
+        match self {
+            FlareMetric::Size => node.summary.size,
+            FlareMetric::Lines => node.summary.count,
+        }
+    }
+}
+
+/// flare 格式的节点
+#[derive(Serialize)]
+pub(crate) struct FlareNode<'a> {
+    /// 节点名称
+    name: &'a str,
+    /// 子节点，仅目录节点包含
+    #[serde(skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<FlareNode<'a>>>,
+    /// 节点的值，仅叶子节点包含
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<u64>,
+}
+
+impl<'a> FlareNode<'a> {
+    /// 根据项目树节点生成 flare 节点
+    /// - name：节点名称，根节点通常使用项目别名
+    pub(crate) fn new(node: &'a TreeNode, name: &'a str, metric: FlareMetric) -> Self {
+        match &node.children {
+            Some(children) => FlareNode {
+                name,
+                children: Some(
+                    children
+                        .iter()
+                        .map(|child| FlareNode::new(child, child.name(), metric))
+                        .collect(),
+                ),
+                value: None,
+            },
+            None => FlareNode {
+                name,
+                children: None,
+                value: Some(metric.value(node)),
+            },
+        }
+    }
+}
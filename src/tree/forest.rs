@@ -0,0 +1,220 @@
+//! # 多根项目
+//! 一个项目可能由多个互不嵌套的根目录组成（如 `frontend/`、`backend/`、`shared/`），
+//! `ProjectForest` 将多棵项目树作为一个整体管理，统一构建、总结、汇总与打印。
+use crate::errors::AruiError;
+use crate::tree::config::ProjectConfig;
+use crate::tree::node::TreeNode;
+use crate::tree::root::ProjectTree;
+use crate::tree::stats::BuildStats;
+use crate::tree::summary::{NodeSummary, largest_files, median_file_size};
+use crate::tree::visible::ProjectTreeVisible;
+use crate::utils::generate_id;
+use std::fmt::{Display, Formatter};
+
+type Result<T> = std::result::Result<T, AruiError>;
+
+/// 多根项目
+/// 由多棵项目树组成，各项目树保留各自的配置、文件系统等设置
+pub struct ProjectForest {
+    /// 项目 ID，自动生成
+    pub id: String,
+    /// 项目别名，自定义
+    pub name: String,
+    /// 组成项目的各个项目树
+    pub trees: Vec<ProjectTree>,
+}
+
+impl ProjectForest {
+    /// 创建一个空的多根项目
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::forest::ProjectForest;
+    ///
+    /// let mut forest = ProjectForest::new("app")
+    ///     .add_root("src", "./src", None)
+    ///     .add_root("tests", "./tests", None);
+    /// forest.analyze().unwrap();
+    /// assert!(forest.summary().count > 0);
+    /// ```
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        ProjectForest {
+            id: generate_id(),
+            name: name.into(),
+            trees: Vec::new(),
+        }
+    }
+
+    /// 添加一棵项目树
+    pub fn add_tree(mut self, tree: ProjectTree) -> Self {
+        self.trees.push(tree);
+        self
+    }
+
+    /// 根据别名、根路径与配置添加一棵项目树
+    pub fn add_root<S, I>(self, name: S, path: I, config: Option<ProjectConfig>) -> Self
+    where
+        S: Into<String>,
+        I: Into<String>,
+    {
+        self.add_tree(ProjectTree::new(name, path, config))
+    }
+
+    /// 获取指定别名的项目树
+    pub fn tree(&self, name: &str) -> Option<&ProjectTree> {
+        self.trees.iter().find(|tree| tree.name == name)
+    }
+
+    /// 依次构建所有项目树，返回合计的扫描统计
+    /// 任一项目树构建失败时立即返回错误，此前的项目树保留构建结果
+    pub fn build(&mut self) -> Result<BuildStats> {
+        self.each(ProjectTree::build)
+    }
+
+    /// 依次生成所有项目树的总结信息，返回合计的扫描统计
+    pub fn summarize(&mut self) -> Result<BuildStats> {
+        self.each(ProjectTree::summarize)
+    }
+
+    /// 依次构建所有项目树并同时生成总结信息，返回合计的扫描统计
+    pub fn analyze(&mut self) -> Result<BuildStats> {
+        self.each(ProjectTree::analyze)
+    }
+
+    /// 对每棵项目树执行操作，并合计扫描统计
+    fn each<F>(&mut self, mut operation: F) -> Result<BuildStats>
+    where
+        F: FnMut(&mut ProjectTree) -> Result<BuildStats>,
+    {
+        let mut stats = BuildStats::default();
+        for tree in &mut self.trees {
+            stats.merge(&operation(tree)?);
+        }
+        Ok(stats)
+    }
+
+    /// 汇总所有项目树根节点的总结信息
+    /// 尚未构建的项目树不计入
+    pub fn summary(&self) -> NodeSummary {
+        let mut summary = NodeSummary::new();
+        for root in self.trees.iter().filter_map(|tree| tree.root.as_ref()) {
+            summary.merge(&root.summary);
+            if !root.is_dir {
+                summary.merge_file(&root.summary);
+            }
+            summary.summarized_at = summary.summarized_at.max(root.summary.summarized_at);
+        }
+        let roots = || self.trees.iter().filter_map(|tree| tree.root.as_ref());
+        summary.median_file_size = median_file_size(roots());
+        summary.largest_files = largest_files(roots().map(|root| &root.summary), summary.top_n);
+        summary
+    }
+}
+
+// --------------------- ProjectTreeVisible ---------------------
+
+impl ProjectTreeVisible for ProjectForest {
+    /// 列出项目基础信息，以及各个项目树的信息
+    fn show(&self) {
+        println!("Project Forest:");
+        println!("ID: {}", self.id);
+        println!("Name: {}", self.name);
+        for tree in &self.trees {
+            tree.show();
+        }
+    }
+
+    /// 依次打印各个项目树的结构
+    fn print_tree(&self) {
+        if self.trees.is_empty() {
+            println!("Forest is empty");
+        }
+        for tree in &self.trees {
+            println!("[{}]", tree.name);
+            tree.print_tree();
+        }
+    }
+
+    /// 递归打印节点
+    fn print_node(node: &TreeNode, depth: usize) {
+        ProjectTree::print_node(node, depth);
+    }
+}
+
+impl Display for ProjectForest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ProjectForest {{\n\tID: {},\n\tName: {},\n\tTrees: {}\n}}",
+            self.id,
+            self.name,
+            self.trees.len()
+        )
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forest() {
+        let mut forest = ProjectForest::new("test")
+            .add_root("summary", "./tests/examples/tree/summary", None)
+            .add_root("node", "./tests/examples/tree/node", None);
+        assert!(matches!(forest.summarize(), Err(AruiError::NotBuilt)));
+        let stats = forest.analyze().expect("analyze failed");
+
+        let summary = forest.summary();
+        let roots: Vec<&NodeSummary> = forest
+            .trees
+            .iter()
+            .map(|tree| &tree.root.as_ref().unwrap().summary)
+            .collect();
+        assert_eq!(summary.size, roots[0].size + roots[1].size);
+        assert_eq!(summary.count, roots[0].count + roots[1].count);
+        assert_eq!(
+            summary.file_count,
+            roots[0].file_count + roots[1].file_count
+        );
+        // 中位数基于所有项目树中的文件计算
+        let mut sizes = Vec::new();
+        let mut stack: Vec<&TreeNode> = forest
+            .trees
+            .iter()
+            .map(|tree| tree.root.as_ref().unwrap())
+            .collect();
+        while let Some(node) = stack.pop() {
+            if node.is_dir {
+                stack.extend(node.children.iter().flatten());
+            } else {
+                sizes.push(node.summary.size);
+            }
+        }
+        sizes.sort();
+        let middle = sizes.len() / 2;
+        let median = if sizes.len() % 2 == 0 {
+            (sizes[middle - 1] + sizes[middle]) / 2
+        } else {
+            sizes[middle]
+        };
+        assert_eq!(summary.median_file_size, median);
+        assert!(summary.summarized_at.is_some());
+        assert!(stats.files_visited > 3);
+        assert!(forest.tree("node").is_some());
+        forest.print_tree();
+    }
+
+    #[test]
+    fn test_forest_invalid_root() {
+        let mut forest = ProjectForest::new("test")
+            .add_root("summary", "./tests/examples/tree/summary", None)
+            .add_root("missing", "./not_exist", None);
+        assert!(matches!(forest.build(), Err(AruiError::InvalidPath(_))));
+        // 之前的项目树保留构建结果
+        assert!(forest.trees[0].root.is_some());
+    }
+}
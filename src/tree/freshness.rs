@@ -0,0 +1,36 @@
+//! # 项目树新鲜度
+//! 将已有的项目树（如从缓存恢复的项目树）与文件系统的当前状态比较，找出已经过期的子树，
+//! 由 `ProjectTree::validate_against_fs` 生成。
+//! 比较仅读取元数据而不读取文件内容，之后只需重建过期的子树（`ProjectTree::refresh_stale`），
+//! 即可以较低的成本将项目树更新为最新状态。
+use std::fmt::Display;
+
+/// 新鲜度检查结果
+/// - stale：过期的子树路径
+/// - checked：检查的节点数量
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Freshness {
+    /// 过期的子树路径（即节点的 `path`），按字典序排列，互不包含
+    /// - 文件：大小或修改时间与记录的不一致
+    /// - 目录：新增或删除了子节点，此时不再检查其下的节点
+    pub stale: Vec<String>,
+    /// 检查的节点数量，过期目录之下的节点不计入
+    pub checked: u64,
+}
+
+impl Freshness {
+    /// 项目树是否与文件系统一致
+    pub fn is_fresh(&self) -> bool {
+        self.stale.is_empty()
+    }
+}
+
+impl Display for Freshness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "checked: {}, stale: {}", self.checked, self.stale.len())?;
+        for path in &self.stale {
+            writeln!(f, "~ {}", path)?;
+        }
+        Ok(())
+    }
+}
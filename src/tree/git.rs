@@ -0,0 +1,258 @@
+//! # Git 提交信息
+//! 需要开启 `git` 特性。
+//! 读取项目所在 Git 仓库的提交历史，为每个已跟踪的文件记录最近一次修改它的提交（提交 ID、作者与时间），
+//! 目录则汇总为其下最近一次提交，即“最近由谁修改”，使项目树可以作为项目的活跃度报告。
+//! 历史从 `HEAD` 开始按时间倒序遍历，每个提交仅与其第一个父提交比较，所有文件均找到对应提交后提前结束。
+//! 未被跟踪的文件与仓库之外的项目没有提交信息。
+use crate::errors::AruiError;
+use crate::tree::node::TreeNode;
+use crate::tree::summarizer::Summarizer;
+use git2::{DiffOptions, Repository, Sort, TreeWalkMode, TreeWalkResult};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 一次提交的信息
+/// - id：提交 ID 的十六进制形式
+/// - author：作者名称
+/// - email：作者邮箱，无法解析时为空
+/// - time：作者提交时间
+/// - message：提交说明的首行，无法解析时为空
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct GitCommit {
+    /// 提交 ID 的十六进制形式
+    pub id: String,
+    /// 作者名称，无法解析时为空字符串
+    pub author: String,
+    /// 作者邮箱，无法解析时为空
+    pub email: Option<String>,
+    /// 作者提交时间
+    pub time: SystemTime,
+    /// 提交说明的首行，无法解析时为空
+    pub message: Option<String>,
+}
+
+impl GitCommit {
+    /// 是否比另一个提交更新，时间相同时按提交 ID 比较，保证汇总结果与子节点的顺序无关
+    pub(crate) fn is_newer_than(&self, other: &GitCommit) -> bool {
+        (self.time, &self.id) > (other.time, &other.id)
+    }
+
+    /// 从 git2 的提交对象转换
+    fn from_commit(commit: &git2::Commit) -> Self {
+        let author = commit.author();
+        let seconds = author.when().seconds();
+        let time = if seconds >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::from_secs(seconds as u64)
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+        };
+        GitCommit {
+            id: commit.id().to_string(),
+            author: author.name().unwrap_or_default().to_string(),
+            email: author.email().map(String::from),
+            time,
+            message: commit.summary().map(String::from),
+        }
+    }
+}
+
+/// 为节点下的文件记录最近一次提交，并重新汇总各级目录的总结信息
+/// 节点路径需位于某个 Git 仓库的工作区之内，否则返回错误
+pub(crate) fn annotate(root: &mut TreeNode) -> Result<(), AruiError> {
+    let repository = Repository::discover(&root.path)?;
+    let workdir = repository
+        .workdir()
+        .ok_or_else(|| AruiError::InvalidPath(format!("{} is a bare repository", root.path)))?
+        .canonicalize()?;
+    let base = Path::new(&root.path).canonicalize()?;
+    // 项目根路径相对仓库工作区的路径，仅查找该路径下文件的提交
+    let prefix = base
+        .strip_prefix(&workdir)
+        .map_err(|_| AruiError::InvalidPath(root.path.clone()))?
+        .to_path_buf();
+    let commits = last_commits(&repository, &prefix)?;
+    let root_path = PathBuf::from(&root.path);
+    assign(root, &root_path, &prefix, &commits);
+    root.summary = Summarizer::roll_up(root);
+    Ok(())
+}
+
+/// 找出 `HEAD` 中前缀路径下各文件最近一次修改它的提交，键为相对仓库工作区的路径
+fn last_commits(
+    repository: &Repository,
+    prefix: &Path,
+) -> Result<HashMap<PathBuf, GitCommit>, git2::Error> {
+    let mut commits = HashMap::new();
+    let head = match repository.head() {
+        Ok(head) => head.peel_to_tree()?,
+        // 尚无任何提交的仓库
+        Err(error) if error.code() == git2::ErrorCode::UnbornBranch => return Ok(commits),
+        Err(error) => return Err(error),
+    };
+    // 需要查找的文件，找到后移除
+    let mut pending = HashSet::new();
+    head.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(git2::ObjectType::Blob)
+            && let Some(name) = entry.name()
+        {
+            let path = Path::new(dir).join(name);
+            if path.starts_with(prefix) {
+                pending.insert(path);
+            }
+        }
+        TreeWalkResult::Ok
+    })?;
+    let mut revwalk = repository.revwalk()?;
+    revwalk.set_sorting(Sort::TIME)?;
+    revwalk.push_head()?;
+    for oid in revwalk {
+        if pending.is_empty() {
+            break;
+        }
+        let commit = repository.find_commit(oid?)?;
+        let changed = changed_paths(repository, &commit, prefix)?;
+        let mut info = None;
+        for path in changed {
+            if pending.remove(&path) {
+                let info = info.get_or_insert_with(|| GitCommit::from_commit(&commit));
+                commits.insert(path, info.clone());
+            }
+        }
+    }
+    Ok(commits)
+}
+
+/// 提交相对其第一个父提交修改的、位于前缀路径下的文件，根提交视为新增了所有文件
+fn changed_paths(
+    repository: &Repository,
+    commit: &git2::Commit,
+    prefix: &Path,
+) -> Result<Vec<PathBuf>, git2::Error> {
+    let tree = commit.tree()?;
+    let parent = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+    let mut options = DiffOptions::new();
+    if !prefix.as_os_str().is_empty() {
+        options.pathspec(prefix);
+    }
+    let diff = repository.diff_tree_to_tree(parent.as_ref(), Some(&tree), Some(&mut options))?;
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().map(Path::to_path_buf))
+        .collect())
+}
+
+/// 递归写入各文件节点的提交信息，未被跟踪的文件清空其提交信息
+fn assign(
+    node: &mut TreeNode,
+    root_path: &Path,
+    prefix: &Path,
+    commits: &HashMap<PathBuf, GitCommit>,
+) {
+    if let Some(children) = &mut node.children {
+        for child in children {
+            assign(child, root_path, prefix, commits);
+        }
+        return;
+    }
+    if node.is_dir {
+        return;
+    }
+    node.summary.last_commit = Path::new(&node.path)
+        .strip_prefix(root_path)
+        .ok()
+        .and_then(|relative| commits.get(&prefix.join(relative)))
+        .cloned();
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::tree::root::ProjectTree;
+    use crate::utils::generate_id;
+    use git2::{Repository, Signature, Time};
+    use std::fs;
+    use std::path::Path;
+
+    /// 提交工作区中的所有文件
+    fn commit_all(repository: &Repository, author: &str, seconds: i64) {
+        let mut index = repository.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new(
+            author,
+            &format!("{}@example.com", author),
+            &Time::new(seconds, 0),
+        )
+        .unwrap();
+        let parent = repository
+            .head()
+            .ok()
+            .map(|head| head.peel_to_commit().unwrap());
+        let parents: Vec<_> = parent.iter().collect();
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                author,
+                &tree,
+                &parents,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_annotate() {
+        let dir = std::env::temp_dir().join(generate_id());
+        let repository = Repository::init(&dir).unwrap();
+        fs::create_dir_all(dir.join("project/src")).unwrap();
+        fs::write(dir.join("project/src/lib.rs"), "// lib").unwrap();
+        fs::write(dir.join("project/README.md"), "# readme").unwrap();
+        fs::write(dir.join("outside.txt"), "outside").unwrap();
+        commit_all(&repository, "alice", 1_000);
+        fs::write(dir.join("project/README.md"), "# updated").unwrap();
+        fs::write(dir.join("outside.txt"), "changed").unwrap();
+        commit_all(&repository, "bob", 2_000);
+        // 仅修改项目之外的文件
+        fs::write(dir.join("outside.txt"), "changed again").unwrap();
+        commit_all(&repository, "carol", 3_000);
+        fs::write(dir.join("project/untracked.txt"), "new").unwrap();
+
+        let project = dir.join("project");
+        let mut tree = ProjectTree::new("test", project.to_string_lossy(), None);
+        tree.analyze().expect("analyze failed");
+        tree.annotate_git().expect("annotate failed");
+        let root = tree.root.as_ref().unwrap();
+        let author = |path: &Path| {
+            root.find(path)
+                .and_then(|node| node.summary.last_commit.as_ref())
+                .map(|commit| commit.author.clone())
+        };
+        assert_eq!(
+            author(&project.join("src/lib.rs")).as_deref(),
+            Some("alice")
+        );
+        assert_eq!(author(&project.join("src")).as_deref(), Some("alice"));
+        assert_eq!(author(&project.join("README.md")).as_deref(), Some("bob"));
+        assert_eq!(author(&project.join("untracked.txt")), None);
+        // 目录汇总为其下最近一次提交
+        let last = root.summary.last_commit.as_ref().unwrap();
+        assert_eq!(last.author, "bob");
+        assert_eq!(last.email.as_deref(), Some("bob@example.com"));
+        assert_eq!(
+            last.time,
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(2_000)
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
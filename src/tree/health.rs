@@ -0,0 +1,487 @@
+//! # 项目健康报告
+//! 根据项目树的总结信息，按一组评分规则计算项目的健康得分，将各项原始统计汇总为一个便于比较的分数。
+//! 每条规则给出 0 到 1 之间的得分与说明，报告按规则的权重加权平均后换算为 0 到 100 的总分；
+//! 不适用于当前项目的规则（如没有任何源码时的测试比例）不参与计算。
+//!
+//! 内置的规则如下，可通过 `HealthReport::default_rules` 获取：
+//! - `TestRatioRule`：测试代码与其他代码的行数之比
+//! - `DocumentationRule`：根目录下是否包含 README、LICENSE 等文档文件
+//! - `BinaryBloatRule`：二进制文件占用的比例
+//! - `StaleFilesRule`：长期未修改的文件比例
+//!
+//! 调用方也可以实现 `HealthRule`，与内置规则组合使用。
+use crate::tree::node::TreeNode;
+use std::fmt::Display;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// 单条规则的评分
+/// - score：0 到 1 之间的得分
+/// - detail：评分依据的说明
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleScore {
+    /// 0 到 1 之间的得分，超出范围时按边界值计算
+    pub score: f64,
+    /// 评分依据的说明
+    pub detail: String,
+}
+
+/// 评分规则
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::fs::MemoryFs;
+/// use arui_core::tree::health::{HealthReport, HealthRule, RuleScore};
+/// use arui_core::tree::node::TreeNode;
+/// use arui_core::tree::root::ProjectTree;
+///
+/// /// 要求项目中不超过 100 个文件
+/// struct SmallProject;
+///
+/// impl HealthRule for SmallProject {
+///     fn name(&self) -> &str {
+///         "small project"
+///     }
+///
+///     fn evaluate(&self, root: &TreeNode) -> Option<RuleScore> {
+///         let files = root.summary.file_count;
+///         Some(RuleScore {
+///             score: if files <= 100 { 1.0 } else { 0.0 },
+///             detail: format!("{} files", files),
+///         })
+///     }
+/// }
+///
+/// let fs = MemoryFs::new().add_file("project/main.rs", "fn main() {}\n");
+/// let mut project = ProjectTree::new("test", "project", None).with_fs(fs);
+/// project.analyze().unwrap();
+/// let mut rules = HealthReport::default_rules();
+/// rules.push(Box::new(SmallProject));
+/// let report = project.health_report_with(&rules).unwrap();
+/// assert_eq!(report.results.last().unwrap().score, 1.0);
+/// ```
+pub trait HealthRule: Send + Sync {
+    /// 规则名称
+    fn name(&self) -> &str;
+
+    /// 规则在总分中的权重，默认为 1
+    fn weight(&self) -> f64 {
+        1.0
+    }
+
+    /// 根据项目树的根节点评分，规则不适用于该项目时返回空
+    fn evaluate(&self, root: &TreeNode) -> Option<RuleScore>;
+}
+
+/// 报告中单条规则的结果
+/// - name：规则名称
+/// - weight：规则的权重
+/// - score：0 到 1 之间的得分
+/// - detail：评分依据的说明
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleResult {
+    /// 规则名称
+    pub name: String,
+    /// 规则的权重
+    pub weight: f64,
+    /// 0 到 1 之间的得分
+    pub score: f64,
+    /// 评分依据的说明
+    pub detail: String,
+}
+
+/// 项目健康报告
+/// - score：0 到 100 之间的总分，即各规则得分按权重加权平均后乘以 100
+/// - results：各条适用规则的结果，按规则的顺序排列
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// 0 到 100 之间的总分，没有适用的规则时为 0
+    pub score: f64,
+    /// 各条适用规则的结果
+    pub results: Vec<RuleResult>,
+}
+
+impl HealthReport {
+    /// 按给定的规则评估项目树，权重不大于 0 的规则不参与总分的计算
+    pub fn evaluate(root: &TreeNode, rules: &[Box<dyn HealthRule>]) -> HealthReport {
+        let results: Vec<RuleResult> = rules
+            .iter()
+            .filter_map(|rule| {
+                let outcome = rule.evaluate(root)?;
+                Some(RuleResult {
+                    name: rule.name().to_string(),
+                    weight: rule.weight(),
+                    score: outcome.score.clamp(0.0, 1.0),
+                    detail: outcome.detail,
+                })
+            })
+            .collect();
+        let (weighted, weights) = results.iter().filter(|result| result.weight > 0.0).fold(
+            (0.0, 0.0),
+            |(weighted, weights), result| {
+                (
+                    weighted + result.score * result.weight,
+                    weights + result.weight,
+                )
+            },
+        );
+        let score = if weights > 0.0 {
+            weighted / weights * 100.0
+        } else {
+            0.0
+        };
+        HealthReport { score, results }
+    }
+
+    /// 内置的评分规则，均使用默认参数
+    pub fn default_rules() -> Vec<Box<dyn HealthRule>> {
+        vec![
+            Box::new(TestRatioRule::default()),
+            Box::new(DocumentationRule::default()),
+            Box::new(BinaryBloatRule::default()),
+            Box::new(StaleFilesRule::default()),
+        ]
+    }
+}
+
+impl Display for HealthReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "health score: {:.1}", self.score)?;
+        for result in &self.results {
+            write!(
+                f,
+                "\n  {}: {:.0}% ({})",
+                result.name,
+                result.score * 100.0,
+                result.detail
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// 测试比例规则：测试代码行数与其他代码行数之比达到目标值时得满分，低于目标值时按比例得分
+/// 仅统计编程语言（见 `Language::is_programming`）的代码行；测试文件根据路径识别（见 `is_test_path`），与源码写在同一文件中的测试（如 Rust 的 `#[cfg(test)]`）无法区分
+/// - target：目标比例，默认为 0.5
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestRatioRule {
+    /// 目标比例
+    pub target: f64,
+}
+
+impl Default for TestRatioRule {
+    fn default() -> Self {
+        TestRatioRule { target: 0.5 }
+    }
+}
+
+impl HealthRule for TestRatioRule {
+    fn name(&self) -> &str {
+        "test ratio"
+    }
+
+    /// 没有任何代码行时不适用
+    fn evaluate(&self, root: &TreeNode) -> Option<RuleScore> {
+        let (mut tests, mut others) = (0, 0);
+        let code_files = files(root).filter(|file| {
+            file.summary
+                .language
+                .is_some_and(|language| language.is_programming())
+        });
+        for file in code_files {
+            // 根目录本身可能位于 tests 等目录下，仅根据根目录以下的部分识别
+            let path = Path::new(&file.path);
+            if is_test_path(path.strip_prefix(&root.path).unwrap_or(path)) {
+                tests += file.summary.lines.code;
+            } else {
+                others += file.summary.lines.code;
+            }
+        }
+        if tests + others == 0 {
+            return None;
+        }
+        let ratio = if others == 0 {
+            f64::INFINITY
+        } else {
+            tests as f64 / others as f64
+        };
+        let score = if self.target > 0.0 {
+            ratio / self.target
+        } else {
+            1.0
+        };
+        Some(RuleScore {
+            score: score.min(1.0),
+            detail: format!("{} test lines, {} other code lines", tests, others),
+        })
+    }
+}
+
+/// 文档规则：根目录下每包含一个要求的文档文件得一份分数
+/// 文件名不区分大小写，以要求的名称开头即视为存在，如 `README.md`、`LICENSE-MIT`
+/// - files：要求的文档文件名，默认为 README、LICENSE、CHANGELOG 与 CONTRIBUTING
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentationRule {
+    /// 要求的文档文件名
+    pub files: Vec<String>,
+}
+
+impl Default for DocumentationRule {
+    fn default() -> Self {
+        DocumentationRule {
+            files: ["README", "LICENSE", "CHANGELOG", "CONTRIBUTING"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl HealthRule for DocumentationRule {
+    fn name(&self) -> &str {
+        "documentation"
+    }
+
+    /// 没有要求任何文档文件时不适用
+    fn evaluate(&self, root: &TreeNode) -> Option<RuleScore> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let names: Vec<String> = root
+            .children
+            .iter()
+            .flatten()
+            .filter(|child| !child.is_dir)
+            .map(|child| child.name().to_lowercase())
+            .collect();
+        let missing: Vec<&str> = self
+            .files
+            .iter()
+            .filter(|file| {
+                let file = file.to_lowercase();
+                !names.iter().any(|name| name.starts_with(&file))
+            })
+            .map(String::as_str)
+            .collect();
+        let found = self.files.len() - missing.len();
+        Some(RuleScore {
+            score: found as f64 / self.files.len() as f64,
+            detail: if missing.is_empty() {
+                "all documents present".to_string()
+            } else {
+                format!("missing {}", missing.join(", "))
+            },
+        })
+    }
+}
+
+/// 二进制膨胀规则：二进制文件占总大小的比例不超过容忍值时得满分，超出部分线性扣分，全部为二进制文件时为 0
+/// - tolerated：容忍的比例，默认为 0.1
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryBloatRule {
+    /// 容忍的比例
+    pub tolerated: f64,
+}
+
+impl Default for BinaryBloatRule {
+    fn default() -> Self {
+        BinaryBloatRule { tolerated: 0.1 }
+    }
+}
+
+impl HealthRule for BinaryBloatRule {
+    fn name(&self) -> &str {
+        "binary bloat"
+    }
+
+    /// 项目总大小为 0 时不适用
+    fn evaluate(&self, root: &TreeNode) -> Option<RuleScore> {
+        let size = root.summary.size;
+        if size == 0 {
+            return None;
+        }
+        let ratio = root.summary.binary.size as f64 / size as f64;
+        let score = if self.tolerated >= 1.0 {
+            1.0
+        } else {
+            (1.0 - ratio) / (1.0 - self.tolerated)
+        };
+        Some(RuleScore {
+            score: score.min(1.0),
+            detail: format!(
+                "{} of {} bytes in binary files",
+                root.summary.binary.size, size
+            ),
+        })
+    }
+}
+
+/// 陈旧文件规则：按最近修改时间早于期限的文件比例扣分
+/// - max_age：期限，默认为 365 天
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleFilesRule {
+    /// 期限
+    pub max_age: Duration,
+}
+
+impl Default for StaleFilesRule {
+    fn default() -> Self {
+        StaleFilesRule {
+            max_age: Duration::from_secs(365 * 24 * 60 * 60),
+        }
+    }
+}
+
+impl HealthRule for StaleFilesRule {
+    fn name(&self) -> &str {
+        "stale files"
+    }
+
+    /// 没有任何文件记录了修改时间时不适用
+    fn evaluate(&self, root: &TreeNode) -> Option<RuleScore> {
+        let now = SystemTime::now();
+        let (mut stale, mut dated) = (0, 0);
+        for updated_at in files(root).filter_map(|file| file.summary.updated_at) {
+            dated += 1;
+            if now
+                .duration_since(updated_at)
+                .is_ok_and(|age| age > self.max_age)
+            {
+                stale += 1;
+            }
+        }
+        if dated == 0 {
+            return None;
+        }
+        Some(RuleScore {
+            score: 1.0 - stale as f64 / dated as f64,
+            detail: format!(
+                "{} of {} files unchanged for over {} days",
+                stale,
+                dated,
+                self.max_age.as_secs() / (24 * 60 * 60)
+            ),
+        })
+    }
+}
+
+/// 路径是否为测试文件
+/// - 位于 `test`、`tests`、`__tests__`、`spec` 或 `specs` 目录下
+/// - 文件名（不含后缀）以 `test_` 开头，或以 `_test`、`_tests`、`_spec`、`.test`、`.spec`、`Test`、`Tests` 结尾
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::health::is_test_path;
+///
+/// assert!(is_test_path("./tests/cli.rs"));
+/// assert!(is_test_path("./src/app.test.ts"));
+/// assert!(is_test_path("./src/main/java/FooTest.java"));
+/// assert!(!is_test_path("./src/latest.rs"));
+/// ```
+pub fn is_test_path<P: AsRef<Path>>(path: P) -> bool {
+    const DIRS: [&str; 5] = ["test", "tests", "__tests__", "spec", "specs"];
+    const SUFFIXES: [&str; 7] = [
+        "_test", "_tests", "_spec", ".test", ".spec", "Test", "Tests",
+    ];
+    let path = path.as_ref();
+    let in_test_dir = path
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .any(|component| DIRS.contains(&component.as_os_str().to_str().unwrap_or_default()));
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    in_test_dir
+        || stem.starts_with("test_")
+        || SUFFIXES
+            .iter()
+            .any(|suffix| stem.len() > suffix.len() && stem.ends_with(suffix))
+}
+
+/// 节点下的所有文件节点
+fn files(root: &TreeNode) -> impl Iterator<Item = &TreeNode> {
+    let mut stack = vec![root];
+    std::iter::from_fn(move || {
+        while let Some(node) = stack.pop() {
+            if let Some(children) = &node.children {
+                stack.extend(children);
+            } else if !node.is_dir {
+                return Some(node);
+            }
+        }
+        None
+    })
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::tree::language::Language;
+    use crate::tree::root::ProjectTree;
+
+    #[test]
+    fn test_health_report() {
+        let fs = MemoryFs::new()
+            .add_file("project/README.md", "# project\n")
+            .add_file("project/LICENSE", "MIT")
+            .add_file(
+                "project/src/lib.rs",
+                "fn a() {}\nfn b() {}\nfn c() {}\nfn d() {}\n",
+            )
+            .add_file("project/tests/lib.rs", "fn t() {}\n")
+            .add_file("project/logo.bin", vec![0u8; 4]);
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.health_report().is_err());
+        tree.analyze().expect("analyze failed");
+        let report = tree.health_report().unwrap();
+        // 内存文件系统没有修改时间，陈旧文件规则不适用
+        let names: Vec<_> = report.results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["test ratio", "documentation", "binary bloat"]);
+        // 测试比例 0.25，目标 0.5
+        assert_eq!(report.results[0].score, 0.5);
+        assert_eq!(report.results[1].score, 0.5);
+        assert_eq!(report.results[1].detail, "missing CHANGELOG, CONTRIBUTING");
+        assert_eq!(report.results[2].score, 1.0);
+        assert!((report.score - 200.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ratio_relative_to_root() {
+        // 根目录位于 tests 目录下时，其中的源码不应被视为测试
+        let mut root = TreeNode::new("/work/tests/fixtures/project", true);
+        let mut children = Vec::new();
+        for (path, code) in [("src/lib.rs", 4), ("tests/lib.rs", 1)] {
+            let mut file = TreeNode::new(format!("{}/{}", root.path, path), false);
+            file.summary.language = Some(Language::Rust);
+            file.summary.lines.code = code;
+            children.push(file);
+        }
+        root.children = Some(children);
+        let outcome = TestRatioRule::default().evaluate(&root).unwrap();
+        assert_eq!(outcome.score, 0.5);
+    }
+
+    #[test]
+    fn test_stale_files() {
+        let now = SystemTime::now();
+        let mut root = TreeNode::new("project", true);
+        let mut children = Vec::new();
+        for age in [1, 400, 800, 2] {
+            let mut file = TreeNode::new(format!("project/{}.txt", age), false);
+            file.summary.updated_at = Some(now - Duration::from_secs(age * 24 * 60 * 60));
+            children.push(file);
+        }
+        root.children = Some(children);
+        let outcome = StaleFilesRule::default().evaluate(&root).unwrap();
+        assert_eq!(outcome.score, 0.5);
+        let report = HealthReport::evaluate(&root, &[Box::new(BinaryBloatRule::default())]);
+        assert!(report.results.is_empty());
+        assert_eq!(report.score, 0.0);
+    }
+}
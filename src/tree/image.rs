@@ -0,0 +1,237 @@
+//! # 图片信息
+//! 根据文件头识别常见的图片格式（PNG、JPEG、GIF、BMP、WebP）并读取其宽高，无需解码图片内容。
+//! 总结时每个图片文件记录其格式与尺寸，目录汇总其下图片的总像素数，并按格式汇总图片数量，
+//! 适用于分析素材目录，而无需借助其他工具。
+//! 格式根据内容识别而非后缀，后缀错误的图片同样可以识别；文件头损坏或被截断时不记录图片信息。
+use std::fmt::Display;
+
+/// 图片格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    WebP,
+}
+
+impl ImageFormat {
+    /// 格式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Gif => "GIF",
+            ImageFormat::Bmp => "BMP",
+            ImageFormat::WebP => "WebP",
+        }
+    }
+}
+
+impl Display for ImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 图片信息
+/// - format：图片格式
+/// - width：宽度（像素）
+/// - height：高度（像素）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ImageInfo {
+    /// 图片格式
+    pub format: ImageFormat,
+    /// 宽度（像素）
+    pub width: u32,
+    /// 高度（像素）
+    pub height: u32,
+}
+
+impl ImageInfo {
+    /// 根据文件头识别图片格式并读取尺寸，不是可识别的图片时返回空
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::image::{ImageFormat, ImageInfo};
+    ///
+    /// let gif = b"GIF89a\x20\x00\x10\x00";
+    /// let info = ImageInfo::parse(gif).unwrap();
+    /// assert_eq!((info.format, info.width, info.height), (ImageFormat::Gif, 32, 16));
+    /// assert_eq!(info.pixels(), 512);
+    /// assert_eq!(ImageInfo::parse(b"not an image"), None);
+    /// ```
+    pub fn parse(contents: &[u8]) -> Option<ImageInfo> {
+        let (format, (width, height)) = if contents.starts_with(b"\x89PNG\r\n\x1a\n") {
+            (ImageFormat::Png, parse_png(contents)?)
+        } else if contents.starts_with(b"\xff\xd8") {
+            (ImageFormat::Jpeg, parse_jpeg(contents)?)
+        } else if contents.starts_with(b"GIF87a") || contents.starts_with(b"GIF89a") {
+            (ImageFormat::Gif, (le16(contents, 6)?, le16(contents, 8)?))
+        } else if contents.starts_with(b"BM") {
+            (ImageFormat::Bmp, parse_bmp(contents)?)
+        } else if contents.starts_with(b"RIFF") && contents.get(8..12) == Some(b"WEBP") {
+            (ImageFormat::WebP, parse_webp(contents)?)
+        } else {
+            return None;
+        };
+        Some(ImageInfo {
+            format,
+            width,
+            height,
+        })
+    }
+
+    /// 像素数量
+    pub fn pixels(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+}
+
+/// PNG：IHDR 块紧随文件签名，其中依次为大端序的宽度与高度
+fn parse_png(contents: &[u8]) -> Option<(u32, u32)> {
+    if contents.get(12..16) != Some(b"IHDR") {
+        return None;
+    }
+    Some((be32(contents, 16)?, be32(contents, 20)?))
+}
+
+/// JPEG：依次跳过各个段，直到帧头（SOF）段，其中依次为大端序的高度与宽度
+fn parse_jpeg(contents: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    loop {
+        if *contents.get(offset)? != 0xff {
+            return None;
+        }
+        let marker = *contents.get(offset + 1)?;
+        match marker {
+            // 填充字节
+            0xff => offset += 1,
+            // 没有长度字段的标记
+            0x01 | 0xd0..=0xd7 => offset += 2,
+            // 图像数据开始或结束前仍未找到帧头
+            0xd9 | 0xda => return None,
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let height = be16(contents, offset + 5)?;
+                let width = be16(contents, offset + 7)?;
+                return Some((width, height));
+            }
+            _ => offset += 2 + be16(contents, offset + 2)? as usize,
+        }
+    }
+}
+
+/// BMP：文件头之后为信息头，旧版（OS/2）信息头的宽高为 16 位，其余版本为 32 位有符号数，高度为负表示自上而下存储
+fn parse_bmp(contents: &[u8]) -> Option<(u32, u32)> {
+    match le32(contents, 14)? {
+        12 => Some((le16(contents, 18)?, le16(contents, 20)?)),
+        40 | 52 | 56 | 64 | 108 | 124 => {
+            let width = le32(contents, 18)? as i32;
+            let height = le32(contents, 22)? as i32;
+            Some((width.unsigned_abs(), height.unsigned_abs()))
+        }
+        _ => None,
+    }
+}
+
+/// WebP：根据首个块的类型区分有损（VP8）、无损（VP8L）与扩展（VP8X）格式
+fn parse_webp(contents: &[u8]) -> Option<(u32, u32)> {
+    match contents.get(12..16)? {
+        b"VP8 " => {
+            // 帧标签之后为起始码与 14 位的宽高
+            if contents.get(23..26) != Some(b"\x9d\x01\x2a") {
+                return None;
+            }
+            Some((le16(contents, 26)? & 0x3fff, le16(contents, 28)? & 0x3fff))
+        }
+        b"VP8L" => {
+            if *contents.get(20)? != 0x2f {
+                return None;
+            }
+            let bits = le32(contents, 21)?;
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        b"VP8X" => Some((le24(contents, 24)? + 1, le24(contents, 27)? + 1)),
+        _ => None,
+    }
+}
+
+/// 读取小端序的 16 位整数
+fn le16(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]) as u32)
+}
+
+/// 读取小端序的 24 位整数
+fn le24(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 3)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]))
+}
+
+/// 读取小端序的 32 位整数
+fn le32(contents: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        contents.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// 读取大端序的 16 位整数
+fn be16(contents: &[u8], offset: usize) -> Option<u32> {
+    let bytes = contents.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]) as u32)
+}
+
+/// 读取大端序的 32 位整数
+fn be32(contents: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        contents.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 解析结果的格式与宽高
+    fn parse(contents: &[u8]) -> Option<(ImageFormat, u32, u32)> {
+        ImageInfo::parse(contents).map(|info| (info.format, info.width, info.height))
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR".to_vec();
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&480u32.to_be_bytes());
+        assert_eq!(parse(&png), Some((ImageFormat::Png, 640, 480)));
+        // 被截断的文件头
+        assert_eq!(parse(&png[..20]), None);
+
+        // APP0 段之后为 SOF0 段
+        let jpeg = b"\xff\xd8\xff\xe0\x00\x04JF\xff\xc0\x00\x11\x08\x00\x78\x00\xa0";
+        assert_eq!(parse(jpeg), Some((ImageFormat::Jpeg, 160, 120)));
+        assert_eq!(parse(b"\xff\xd8\xff\xda\x00\x02"), None);
+
+        let mut bmp = b"BM".to_vec();
+        bmp.resize(14, 0);
+        bmp.extend_from_slice(&40u32.to_le_bytes());
+        bmp.extend_from_slice(&100i32.to_le_bytes());
+        bmp.extend_from_slice(&(-50i32).to_le_bytes());
+        assert_eq!(parse(&bmp), Some((ImageFormat::Bmp, 100, 50)));
+
+        let mut webp = b"RIFF\x00\x00\x00\x00WEBPVP8X".to_vec();
+        webp.resize(24, 0);
+        webp.extend_from_slice(&[0xff, 0x00, 0x00, 0x7f, 0x00, 0x00]);
+        assert_eq!(parse(&webp), Some((ImageFormat::WebP, 256, 128)));
+        let mut lossless = b"RIFF\x00\x00\x00\x00WEBPVP8L\x00\x00\x00\x00\x2f".to_vec();
+        lossless.extend_from_slice(&(9u32 | (19 << 14)).to_le_bytes());
+        assert_eq!(parse(&lossless), Some((ImageFormat::WebP, 10, 20)));
+        assert_eq!(ImageFormat::WebP.to_string(), "WebP");
+    }
+}
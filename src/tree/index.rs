@@ -0,0 +1,227 @@
+//! # 元数据索引归档
+//! 需要开启 `index` 特性。
+//! 将项目树写为只包含元数据的 tar 归档：每个节点对应一个条目，文件条目的内容为空，
+//! 节点的路径、类型与总结信息以 JSON 保存在条目的 PAX 扩展头 `ARUI.node` 中。
+//! 归档的体积只与节点数量有关而与文件大小无关，适合低成本地保存大型数据集的“形状”；
+//! 归档可以直接用 `tar tvf` 查看目录结构与修改时间，也可以通过 `read` 重新加载为项目树。
+//!
+//! 归档的第一个条目为 `INDEX_ENTRY`，内容为项目的 ID、别名、根路径、配置与警告，
+//! 之后按先序遍历的顺序排列各节点，条目路径为以根节点名称开头的相对路径。
+use crate::errors::AruiError;
+use crate::fs::FileKind;
+use crate::tree::config::ProjectConfig;
+use crate::tree::node::{NodeMarker, TreeNode};
+use crate::tree::root::ProjectTree;
+use crate::tree::schema::SCHEMA_VERSION;
+use crate::tree::summary::NodeSummary;
+use crate::tree::warning::Warning;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 归档中保存项目信息的条目名称
+pub const INDEX_ENTRY: &str = ".arui-index.json";
+
+/// 保存节点记录的 PAX 扩展头名称
+pub const NODE_KEY: &str = "ARUI.node";
+
+/// 项目信息
+#[derive(Serialize, Deserialize)]
+struct IndexHeader<'a> {
+    /// 导出格式版本，与 JSON 导出一致
+    schema_version: u32,
+    /// 项目 ID
+    id: Cow<'a, str>,
+    /// 项目别名
+    name: Cow<'a, str>,
+    /// 项目根路径
+    path: Cow<'a, str>,
+    /// 项目树配置
+    config: Cow<'a, Option<ProjectConfig>>,
+    /// 构建时的警告
+    warnings: Cow<'a, [Warning]>,
+}
+
+/// 单个节点的记录，不包含子节点
+#[derive(Serialize, Deserialize)]
+struct NodeRecord<'a> {
+    /// 节点路径
+    path: Cow<'a, str>,
+    /// 相对根节点的深度，根节点为 0
+    depth: usize,
+    /// 是否是目录
+    is_dir: bool,
+    /// 文件类型
+    kind: FileKind,
+    /// 目录是否已经展开，即子节点是否存在
+    expanded: bool,
+    /// 总结信息
+    summary: Cow<'a, NodeSummary>,
+    /// 节点标记
+    marker: Option<NodeMarker>,
+    /// 符号链接指向的目标
+    symlink_target: Option<Cow<'a, str>>,
+}
+
+/// 将项目树写为元数据索引归档，返回写入的节点数量
+/// 项目树未构建时返回 `AruiError::NotBuilt`
+pub fn write<W: Write>(tree: &ProjectTree, writer: W) -> Result<u64, AruiError> {
+    let root = tree.root.as_ref().ok_or(AruiError::NotBuilt)?;
+    let mut builder = tar::Builder::new(writer);
+    let header = IndexHeader {
+        schema_version: SCHEMA_VERSION,
+        id: Cow::Borrowed(&tree.id),
+        name: Cow::Borrowed(&tree.name),
+        path: Cow::Borrowed(&tree.path),
+        config: Cow::Borrowed(&tree.config),
+        warnings: Cow::Borrowed(&tree.warnings),
+    };
+    let contents = serde_json::to_vec(&header)?;
+    let mut entry = tar::Header::new_gnu();
+    entry.set_size(contents.len() as u64);
+    entry.set_mode(0o644);
+    builder.append_data(&mut entry, INDEX_ENTRY, contents.as_slice())?;
+
+    let base = Path::new(&root.path);
+    let top = base
+        .file_name()
+        .map_or_else(|| PathBuf::from("root"), PathBuf::from);
+    let mut count = 0;
+    let mut stack = vec![(root, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        let record = NodeRecord {
+            path: Cow::Borrowed(&node.path),
+            depth,
+            is_dir: node.is_dir,
+            kind: node.kind,
+            expanded: node.children.is_some(),
+            summary: Cow::Borrowed(&node.summary),
+            marker: node.marker.clone(),
+            symlink_target: node.symlink_target.as_deref().map(Cow::Borrowed),
+        };
+        let record = serde_json::to_vec(&record)?;
+        builder.append_pax_extensions([(NODE_KEY, record.as_slice())])?;
+
+        let mut entry = tar::Header::new_gnu();
+        entry.set_size(0);
+        if node.is_dir {
+            entry.set_entry_type(tar::EntryType::Directory);
+            entry.set_mode(0o755);
+        } else {
+            entry.set_mode(0o644);
+        }
+        if let Some(duration) = node
+            .summary
+            .updated_at
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        {
+            entry.set_mtime(duration.as_secs());
+        }
+        let relative = Path::new(&node.path)
+            .strip_prefix(base)
+            .unwrap_or(Path::new(""));
+        builder.append_data(&mut entry, top.join(relative), std::io::empty())?;
+        count += 1;
+
+        if let Some(children) = &node.children {
+            stack.extend(children.iter().rev().map(|child| (child, depth + 1)));
+        }
+    }
+    builder.into_inner()?.flush()?;
+    Ok(count)
+}
+
+/// 读取元数据索引归档，重新加载为项目树
+/// 加载的项目树使用磁盘文件系统，ID 与导出时相同；格式版本高于当前支持的版本时返回 `AruiError::UnsupportedSchema`，
+/// 归档缺少项目信息或节点记录时返回 `AruiError::InvalidIndex`
+pub fn read<R: Read>(reader: R) -> Result<ProjectTree, AruiError> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = archive.entries()?;
+    let mut entry = entries
+        .next()
+        .ok_or_else(|| AruiError::InvalidIndex("empty archive".to_string()))??;
+    if entry.path()?.as_os_str() != INDEX_ENTRY {
+        return Err(AruiError::InvalidIndex(format!(
+            "the first entry is not {}",
+            INDEX_ENTRY
+        )));
+    }
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents)?;
+    let header: IndexHeader = serde_json::from_slice(&contents)?;
+    if header.schema_version > SCHEMA_VERSION {
+        return Err(AruiError::UnsupportedSchema(header.schema_version));
+    }
+
+    // 按先序遍历的顺序还原层级：栈中依次为当前节点的各级祖先
+    let mut stack: Vec<TreeNode> = Vec::new();
+    for entry in entries {
+        let mut entry = entry?;
+        let record = entry
+            .pax_extensions()?
+            .into_iter()
+            .flatten()
+            .filter_map(|extension| extension.ok())
+            .find(|extension| extension.key() == Ok(NODE_KEY))
+            .map(|extension| serde_json::from_slice::<NodeRecord>(extension.value_bytes()))
+            .transpose()?;
+        let Some(record) = record else {
+            let path = entry.path()?.to_string_lossy().into_owned();
+            return Err(AruiError::InvalidIndex(format!(
+                "missing node record: {}",
+                path
+            )));
+        };
+        if record.depth > stack.len() || (record.depth == 0 && !stack.is_empty()) {
+            return Err(AruiError::InvalidIndex(format!(
+                "unexpected depth of {}",
+                record.path
+            )));
+        }
+        collapse(&mut stack, record.depth.max(1))?;
+        stack.push(TreeNode {
+            path: record.path.into_owned(),
+            is_dir: record.is_dir,
+            kind: record.kind,
+            children: record.expanded.then(Vec::new),
+            summary: record.summary.into_owned(),
+            marker: record.marker,
+            symlink_target: record.symlink_target.map(Cow::into_owned),
+        });
+    }
+    collapse(&mut stack, 1)?;
+
+    let mut tree = ProjectTree::new(
+        header.name.into_owned(),
+        header.path.into_owned(),
+        header.config.into_owned(),
+    );
+    tree.id = header.id.into_owned();
+    tree.warnings = header.warnings.into_owned();
+    tree.root = stack.pop();
+    Ok(tree)
+}
+
+/// 将栈顶的节点依次挂到其父节点之下，直到栈中只剩 `depth` 个节点，`depth` 不小于 1
+fn collapse(stack: &mut Vec<TreeNode>, depth: usize) -> Result<(), AruiError> {
+    while stack.len() > depth {
+        let Some(node) = stack.pop() else {
+            break;
+        };
+        let Some(parent) = stack.last_mut() else {
+            break;
+        };
+        match &mut parent.children {
+            Some(children) => children.push(node),
+            None => {
+                return Err(AruiError::InvalidIndex(format!(
+                    "{} is not expanded",
+                    parent.path
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! # JSON 导出选项
+//! 需要开启 `json` 特性。
+//! 控制 `ProjectTree::to_json_with` 与 `write_json_with` 的输出：是否缩进排版，以及每个节点的 `summary` 中保留哪些字段。
+//! 字段名为 `NodeSummary` 序列化后的名称（即结构体字段名），只作用于节点的总结信息，项目 ID 等顶层字段不受影响。
+//! 例如省略各时间戳字段后，同一份内容每次导出的节点部分完全一致，适合作为测试中的确定性快照。
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// 总结信息中与扫描时间或文件时间有关的字段，每次扫描的结果可能不同
+pub const TIMESTAMP_FIELDS: [&str; 5] = [
+    "updated_at",
+    "summarized_at",
+    "newest_mtime",
+    "oldest_mtime",
+    "created_at",
+];
+
+/// JSON 导出选项
+/// - `pretty` 是否缩进排版，否则输出紧凑的单行 JSON
+/// - `fields` 总结信息中保留的字段，为 `None` 时保留全部字段
+/// - `omit` 总结信息中省略的字段，优先于 `fields`
+///
+/// 无法识别的字段名会被忽略
+#[derive(Default, Debug, PartialEq, Eq, Clone)]
+pub struct JsonOptions {
+    pub pretty: bool,
+    pub fields: Option<BTreeSet<String>>,
+    pub omit: BTreeSet<String>,
+}
+
+impl JsonOptions {
+    /// 创建默认选项：紧凑输出，保留全部字段
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// 设置是否缩进排版
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// 只保留指定的总结信息字段，可多次调用以追加字段
+    pub fn with_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields
+            .get_or_insert_with(BTreeSet::new)
+            .extend(fields.into_iter().map(Into::into));
+        self
+    }
+
+    /// 省略指定的总结信息字段
+    pub fn add_omit<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.omit.extend(fields.into_iter().map(Into::into));
+        self
+    }
+
+    /// 省略 `TIMESTAMP_FIELDS` 中的时间戳字段
+    pub fn without_timestamps(self) -> Self {
+        self.add_omit(TIMESTAMP_FIELDS)
+    }
+
+    /// 是否需要筛选字段，否则可以直接序列化
+    pub(crate) fn is_filtered(&self) -> bool {
+        self.fields.is_some() || !self.omit.is_empty()
+    }
+
+    /// 筛选导出结果中各节点的总结信息字段
+    /// - export：完整的导出结果，节点位于 `root` 及各级 `children` 中
+    pub(crate) fn filter(&self, export: &mut Value) {
+        let mut stack: Vec<&mut Value> = export.get_mut("root").into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let Value::Object(node) = node else {
+                continue;
+            };
+            if let Some(Value::Object(summary)) = node.get_mut("summary") {
+                summary.retain(|key, _| {
+                    self.fields
+                        .as_ref()
+                        .is_none_or(|fields| fields.contains(key))
+                        && !self.omit.contains(key)
+                });
+            }
+            if let Some(Value::Array(children)) = node.get_mut("children") {
+                stack.extend(children.iter_mut());
+            }
+        }
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_filter() {
+        let node = |children: Value| {
+            json!({
+                "path": "p",
+                "children": children,
+                "summary": { "size": 1, "lines": {}, "updated_at": 2, "summarized_at": 3 },
+            })
+        };
+        let mut export = json!({ "id": "x", "root": node(json!([node(Value::Null)])) });
+
+        let mut omitted = export.clone();
+        JsonOptions::new().without_timestamps().filter(&mut omitted);
+        let expected = json!({ "size": 1, "lines": {} });
+        assert_eq!(omitted["root"]["summary"], expected);
+        assert_eq!(omitted["root"]["children"][0]["summary"], expected);
+        assert_eq!(omitted["id"], "x");
+
+        let options = JsonOptions::new()
+            .with_fields(["size", "updated_at", "unknown"])
+            .add_omit(["updated_at"]);
+        assert!(options.is_filtered());
+        options.filter(&mut export);
+        assert_eq!(
+            export["root"]["children"][0]["summary"],
+            json!({ "size": 1 })
+        );
+        assert!(!JsonOptions::new().with_pretty(true).is_filtered());
+    }
+}
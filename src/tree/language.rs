@@ -0,0 +1,350 @@
+//! # 语言识别
+//! 根据文件后缀识别文件所使用的编程语言，没有可识别的后缀时再根据首行的 shebang（如 `#!/usr/bin/env python3`）识别。
+//! 总结时每个文件记录其语言，目录按语言汇总文件数量、大小与行数，可通过 `ProjectTree::language_stats` 获取。
+use std::fmt::Display;
+use std::path::Path;
+
+/// 注释语法
+/// - line：单行注释的前缀
+/// - block：块注释的起止标记
+/// - nested：块注释是否可以嵌套
+/// - quotes：字符串的引号
+/// - chars：是否使用单引号表示字符字面量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CommentSyntax {
+    /// 单行注释的前缀
+    pub(crate) line: &'static [&'static str],
+    /// 块注释的起止标记
+    pub(crate) block: Option<(&'static str, &'static str)>,
+    /// 块注释是否可以嵌套（如 Rust 的 `/* /* */ */`）
+    pub(crate) nested: bool,
+    /// 字符串的引号，字符串中的注释标记不生效
+    pub(crate) quotes: &'static [char],
+    /// 是否使用单引号表示字符字面量（如 `'"'`），不构成字符字面量的单引号（如 Rust 的生命周期）不做处理
+    pub(crate) chars: bool,
+}
+
+/// 编程语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum Language {
+    C,
+    Cpp,
+    CSharp,
+    Css,
+    Go,
+    Html,
+    Java,
+    JavaScript,
+    Json,
+    Kotlin,
+    Lua,
+    Markdown,
+    Perl,
+    Php,
+    Python,
+    Ruby,
+    Rust,
+    Shell,
+    Sql,
+    Swift,
+    Text,
+    Toml,
+    TypeScript,
+    Xml,
+    Yaml,
+}
+
+impl Language {
+    /// 根据后缀识别语言，后缀不包含 `.`，不区分大小写
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::language::Language;
+    ///
+    /// assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+    /// assert_eq!(Language::from_extension("TSX"), Some(Language::TypeScript));
+    /// assert_eq!(Language::from_extension("unknown"), None);
+    /// ```
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        let language = match extension.to_lowercase().as_str() {
+            "c" | "h" => Language::C,
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => Language::Cpp,
+            "cs" => Language::CSharp,
+            "css" | "scss" | "sass" | "less" => Language::Css,
+            "go" => Language::Go,
+            "html" | "htm" => Language::Html,
+            "java" => Language::Java,
+            "js" | "mjs" | "cjs" | "jsx" => Language::JavaScript,
+            "json" => Language::Json,
+            "kt" | "kts" => Language::Kotlin,
+            "lua" => Language::Lua,
+            "md" | "markdown" => Language::Markdown,
+            "pl" | "pm" => Language::Perl,
+            "php" => Language::Php,
+            "py" | "pyi" => Language::Python,
+            "rb" => Language::Ruby,
+            "rs" => Language::Rust,
+            "sh" | "bash" | "zsh" => Language::Shell,
+            "sql" => Language::Sql,
+            "swift" => Language::Swift,
+            "txt" => Language::Text,
+            "toml" => Language::Toml,
+            "ts" | "tsx" | "mts" | "cts" => Language::TypeScript,
+            "xml" => Language::Xml,
+            "yml" | "yaml" => Language::Yaml,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// 根据文件首行的 shebang 识别语言，首行不是 shebang 时返回空
+    /// 支持直接指定解释器（`#!/bin/bash`）与通过 `env` 指定解释器（`#!/usr/bin/env -S python3 -u`）两种形式
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::language::Language;
+    ///
+    /// assert_eq!(Language::from_shebang(b"#!/usr/bin/env python3\n"), Some(Language::Python));
+    /// assert_eq!(Language::from_shebang(b"#!/bin/sh\necho hi"), Some(Language::Shell));
+    /// assert_eq!(Language::from_shebang(b"echo hi"), None);
+    /// ```
+    pub fn from_shebang(contents: &[u8]) -> Option<Language> {
+        let interpreter = shebang_interpreter(contents)?;
+        // 去除版本号，如 python3.12
+        let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let language = match name {
+            "sh" | "bash" | "zsh" | "dash" | "ksh" | "mksh" | "ash" | "fish" | "csh" | "tcsh" => {
+                Language::Shell
+            }
+            "python" | "pypy" => Language::Python,
+            "node" | "nodejs" => Language::JavaScript,
+            "deno" | "ts-node" | "bun" => Language::TypeScript,
+            "ruby" => Language::Ruby,
+            "perl" => Language::Perl,
+            "php" => Language::Php,
+            "lua" => Language::Lua,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    /// 识别文件的语言，优先根据后缀识别，无法识别时根据文件内容的 shebang 识别
+    pub fn detect<P: AsRef<Path>>(path: P, contents: &[u8]) -> Option<Language> {
+        path.as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Language::from_extension)
+            .or_else(|| Language::from_shebang(contents))
+    }
+
+    /// 语言的注释语法，没有注释语法的语言（如 JSON、纯文本）两者均为空
+    pub(crate) fn comment_syntax(&self) -> CommentSyntax {
+        const C_LIKE: CommentSyntax = CommentSyntax {
+            line: &["//"],
+            block: Some(("/*", "*/")),
+            nested: false,
+            quotes: &['"'],
+            chars: true,
+        };
+        const HASH: CommentSyntax = CommentSyntax {
+            line: &["#"],
+            block: None,
+            nested: false,
+            quotes: &['"', '\''],
+            chars: false,
+        };
+        const MARKUP: CommentSyntax = CommentSyntax {
+            line: &[],
+            block: Some(("<!--", "-->")),
+            nested: false,
+            quotes: &[],
+            chars: false,
+        };
+        match self {
+            Language::C | Language::Cpp | Language::CSharp | Language::Java => C_LIKE,
+            Language::Kotlin | Language::Rust => CommentSyntax {
+                nested: true,
+                ..C_LIKE
+            },
+            Language::Swift => CommentSyntax {
+                nested: true,
+                chars: false,
+                ..C_LIKE
+            },
+            Language::Go => CommentSyntax {
+                quotes: &['"', '`'],
+                ..C_LIKE
+            },
+            Language::JavaScript | Language::TypeScript => CommentSyntax {
+                quotes: &['"', '\'', '`'],
+                chars: false,
+                ..C_LIKE
+            },
+            Language::Php => CommentSyntax {
+                line: &["//", "#"],
+                quotes: &['"', '\''],
+                chars: false,
+                ..C_LIKE
+            },
+            Language::Css => CommentSyntax {
+                line: &[],
+                quotes: &['"', '\''],
+                chars: false,
+                ..C_LIKE
+            },
+            Language::Perl | Language::Python | Language::Ruby | Language::Shell => HASH,
+            Language::Toml | Language::Yaml => HASH,
+            Language::Lua => CommentSyntax {
+                line: &["--"],
+                block: Some(("--[[", "]]")),
+                ..HASH
+            },
+            Language::Sql => CommentSyntax {
+                line: &["--"],
+                block: Some(("/*", "*/")),
+                ..HASH
+            },
+            Language::Html | Language::Markdown | Language::Xml => MARKUP,
+            Language::Json | Language::Text => CommentSyntax {
+                line: &[],
+                block: None,
+                ..MARKUP
+            },
+        }
+    }
+
+    /// 声明函数的关键字（包括其后的空白），没有简单关键字的语言（如 C、Java 通过返回类型声明函数）为空
+    pub(crate) fn function_keywords(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &["fn "],
+            Language::Go | Language::Swift => &["func "],
+            Language::Kotlin => &["fun "],
+            Language::Python | Language::Ruby => &["def "],
+            Language::JavaScript | Language::TypeScript | Language::Lua | Language::Php => {
+                &["function "]
+            }
+            Language::Shell => &["function "],
+            Language::Perl => &["sub "],
+            _ => &[],
+        }
+    }
+
+    /// 是否为编程语言，标记、数据与纯文本格式（如 Markdown、JSON、YAML、HTML）不属于编程语言
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::language::Language;
+    ///
+    /// assert!(Language::Rust.is_programming());
+    /// assert!(!Language::Toml.is_programming());
+    /// ```
+    pub fn is_programming(&self) -> bool {
+        !matches!(
+            self,
+            Language::Html
+                | Language::Json
+                | Language::Markdown
+                | Language::Text
+                | Language::Toml
+                | Language::Xml
+                | Language::Yaml
+        )
+    }
+
+    /// 语言名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::C => "C",
+            Language::Cpp => "C++",
+            Language::CSharp => "C#",
+            Language::Css => "CSS",
+            Language::Go => "Go",
+            Language::Html => "HTML",
+            Language::Java => "Java",
+            Language::JavaScript => "JavaScript",
+            Language::Json => "JSON",
+            Language::Kotlin => "Kotlin",
+            Language::Lua => "Lua",
+            Language::Markdown => "Markdown",
+            Language::Perl => "Perl",
+            Language::Php => "PHP",
+            Language::Python => "Python",
+            Language::Ruby => "Ruby",
+            Language::Rust => "Rust",
+            Language::Shell => "Shell",
+            Language::Sql => "SQL",
+            Language::Swift => "Swift",
+            Language::Text => "Text",
+            Language::Toml => "TOML",
+            Language::TypeScript => "TypeScript",
+            Language::Xml => "XML",
+            Language::Yaml => "YAML",
+        }
+    }
+}
+
+/// 获取文件首行 shebang 指定的解释器名称（不包含路径），首行不是 shebang 时返回空
+/// 通过 `env` 指定解释器时，返回 `env` 之后的解释器名称；版本号会被保留，如 `python3.12`
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::language::shebang_interpreter;
+///
+/// assert_eq!(shebang_interpreter(b"#!/usr/bin/env -S python3 -u\n"), Some("python3"));
+/// assert_eq!(shebang_interpreter(b"#!/bin/bash -e\n"), Some("bash"));
+/// assert_eq!(shebang_interpreter(b"echo hi"), None);
+/// ```
+pub fn shebang_interpreter(contents: &[u8]) -> Option<&str> {
+    let line = contents.split(|&byte| byte == b'\n').next()?;
+    let line = std::str::from_utf8(line.strip_prefix(b"#!")?).ok()?;
+    let mut words = line.split_whitespace();
+    let interpreter = Path::new(words.next()?).file_name()?.to_str()?;
+    // 通过 env 指定解释器时，跳过 env 的参数
+    if interpreter == "env" {
+        return words.find(|word| !word.starts_with('-'));
+    }
+    Some(interpreter)
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Language::detect("./src/lib.rs", b""), Some(Language::Rust));
+        // 后缀优先于 shebang
+        assert_eq!(
+            Language::detect("./build.js", b"#!/usr/bin/env python\n"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(
+            Language::detect("./bin/run", b"#!/usr/bin/env -S node --harmony\n"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(
+            Language::detect("./bin/tool", b"#!/usr/bin/python3.12\n"),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::detect("./bin/setup", b"#!/usr/bin/fish\n"),
+            Some(Language::Shell)
+        );
+        assert_eq!(Language::detect("./LICENSE", b"MIT License"), None);
+        assert_eq!(Language::Cpp.to_string(), "C++");
+    }
+}
@@ -0,0 +1,202 @@
+//! # 许可证识别
+//! 根据文件名识别许可证文件（如 `LICENSE`、`LICENSE-MIT`、`COPYING.LESSER`、`UNLICENSE`），
+//! 再根据其内容中的特征语句识别对应的 SPDX 许可证标识符，无法识别时记为 `NOASSERTION`。
+//! 总结时每个许可证文件记录其标识符，目录汇总其下所有许可证文件，根节点即为项目中发现的全部许可证，
+//! 可供合规检查直接使用。
+//! 识别基于特征语句而非全文比对，因此无法判断许可证文本是否被修改；
+//! GPL 系列的许可证根据标题识别，缺少标题的全文无法识别；其全文无法区分 `-only` 与 `-or-later`，统一记为 `-only`。
+use crate::tree::language::Language;
+use std::path::Path;
+
+/// 无法识别许可证内容时使用的标识符，与 SPDX 规范一致
+pub const NOASSERTION: &str = "NOASSERTION";
+
+/// 许可证文件名的前缀（不区分大小写）
+const FILE_PREFIXES: [&str; 4] = ["license", "licence", "copying", "unlicense"];
+
+/// 许可证的特征语句，需全部出现（忽略大小写与空白差异），按从具体到宽泛的顺序排列
+/// GPL 系列的全文会相互引用（如 GPL-3.0 第 13 节提及 AGPL，LGPL-3.0 基于 GPL-3.0），因此按标题（名称紧接版本号）识别
+const SIGNATURES: &[(&str, &[&str])] = &[
+    (
+        "AGPL-3.0-only",
+        &["gnu affero general public license version 3"],
+    ),
+    (
+        "LGPL-3.0-only",
+        &["gnu lesser general public license version 3"],
+    ),
+    (
+        "LGPL-2.1-only",
+        &["gnu lesser general public license version 2.1"],
+    ),
+    ("GPL-3.0-only", &["gnu general public license version 3"]),
+    ("GPL-2.0-only", &["gnu general public license version 2"]),
+    ("Apache-2.0", &["apache license", "version 2.0"]),
+    ("MPL-2.0", &["mozilla public license", "2.0"]),
+    ("BSL-1.0", &["boost software license - version 1.0"]),
+    ("CC0-1.0", &["cc0 1.0 universal"]),
+    (
+        "Unlicense",
+        &["this is free and unencumbered software released into the public domain"],
+    ),
+    (
+        "MIT",
+        &[
+            "permission is hereby granted, free of charge",
+            "the above copyright notice and this permission notice shall be included",
+        ],
+    ),
+    (
+        "ISC",
+        &[
+            "permission to use, copy, modify, and/or distribute this software for any purpose",
+            "provided that the above copyright notice and this permission notice appear in all copies",
+        ],
+    ),
+    (
+        "0BSD",
+        &["permission to use, copy, modify, and/or distribute this software for any purpose"],
+    ),
+    (
+        "BSD-3-Clause",
+        &[
+            "redistribution and use in source and binary forms",
+            "endorse or promote products derived from this software",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &["redistribution and use in source and binary forms"],
+    ),
+];
+
+/// 文件名是否为许可证文件，即以 `LICENSE`、`LICENCE`、`COPYING` 或 `UNLICENSE` 开头（不区分大小写），
+/// 其后为空或以 `-`、`_`、`.` 分隔的说明与后缀；后缀为源代码（如 `license.rs`）时不视为许可证文件
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::license;
+///
+/// assert!(license::is_license_file("LICENSE-APACHE"));
+/// assert!(license::is_license_file("copying.lesser"));
+/// assert!(license::is_license_file("LICENSE.md"));
+/// assert!(!license::is_license_file("license.rs"));
+/// assert!(!license::is_license_file("licenses"));
+/// ```
+pub fn is_license_file(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    let separated = FILE_PREFIXES.iter().any(|prefix| {
+        lower
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '_', '.']))
+    });
+    let source = Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(Language::from_extension)
+        .is_some_and(|language| !matches!(language, Language::Markdown | Language::Text));
+    separated && !source
+}
+
+/// 根据内容识别许可证的 SPDX 标识符，无法识别时返回空
+/// 内容以 `SPDX-License-Identifier:` 声明时直接使用其声明的标识符
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::license;
+///
+/// let mit = "MIT License\n\nPermission is hereby granted, free of charge, to any person ...\n\
+///     The above copyright notice and this permission\n notice shall be included in all copies ...";
+/// assert_eq!(license::identify(mit).as_deref(), Some("MIT"));
+/// assert_eq!(license::identify("SPDX-License-Identifier: MIT OR Apache-2.0").as_deref(), Some("MIT OR Apache-2.0"));
+/// assert_eq!(license::identify("All rights reserved."), None);
+/// ```
+pub fn identify(contents: &str) -> Option<String> {
+    if let Some(declared) = contents
+        .lines()
+        .find_map(|line| line.split_once("SPDX-License-Identifier:"))
+        .map(|(_, expression)| expression.trim().trim_end_matches("*/").trim())
+        .filter(|expression| !expression.is_empty())
+    {
+        return Some(declared.to_string());
+    }
+    let normalized = contents
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    SIGNATURES
+        .iter()
+        .find(|(_, phrases)| phrases.iter().all(|phrase| normalized.contains(phrase)))
+        .map(|(id, _)| id.to_string())
+}
+
+/// 识别许可证文件，文件名不是许可证文件时返回空，内容无法识别时返回 `NOASSERTION`
+pub fn detect<P: AsRef<Path>>(path: P, contents: &[u8]) -> Option<String> {
+    let name = path.as_ref().file_name()?.to_str()?;
+    if !is_license_file(name) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(contents);
+    Some(identify(&text).unwrap_or_else(|| NOASSERTION.to_string()))
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identify() {
+        let apache = "                                 Apache License\n\
+                                   Version 2.0, January 2004\n";
+        assert_eq!(identify(apache).as_deref(), Some("Apache-2.0"));
+        let lgpl = "GNU LESSER GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007\n\
+            ... GNU General Public License ...";
+        assert_eq!(identify(lgpl).as_deref(), Some("LGPL-3.0-only"));
+        let gpl = "GNU GENERAL PUBLIC LICENSE\n   Version 2, June 1991";
+        assert_eq!(identify(gpl).as_deref(), Some("GPL-2.0-only"));
+        let bsd = "Redistribution and use in source and binary forms, with or without\n\
+            modification, are permitted provided that ...\n\
+            3. Neither the name of the copyright holder nor the names of its contributors may be used to\n\
+            endorse or promote products derived from this software without specific prior written permission.";
+        assert_eq!(identify(bsd).as_deref(), Some("BSD-3-Clause"));
+        let isc = "Permission to use, copy, modify, and/or distribute this software for any\n\
+            purpose with or without fee is hereby granted, provided that the above\n\
+            copyright notice and this permission notice appear in all copies.";
+        assert_eq!(identify(isc).as_deref(), Some("ISC"));
+    }
+
+    #[test]
+    fn test_identify_gnu_texts() {
+        // 全文中相互引用的其他 GPL 系列许可证不影响识别
+        let text = |name: &str| {
+            std::fs::read_to_string(format!("./tests/examples/tree/license/{}.txt", name)).unwrap()
+        };
+        assert_eq!(identify(&text("GPL-3.0")).as_deref(), Some("GPL-3.0-only"));
+        assert_eq!(
+            identify(&text("LGPL-3.0")).as_deref(),
+            Some("LGPL-3.0-only")
+        );
+        assert_eq!(
+            identify(&text("AGPL-3.0")).as_deref(),
+            Some("AGPL-3.0-only")
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            detect("./LICENSE.md", b"Copyright (c) 2024 Someone").as_deref(),
+            Some(NOASSERTION)
+        );
+        assert_eq!(detect("./src/license.rs", b"MIT"), None);
+        assert!(is_license_file("UNLICENSE"));
+        assert!(is_license_file("LICENCE_MIT.txt"));
+        assert!(is_license_file("LICENSE_THIRD_PARTY"));
+        assert!(!is_license_file("license_test.rs"));
+    }
+}
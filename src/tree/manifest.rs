@@ -0,0 +1,404 @@
+//! # 清单文件解析
+//! 需要开启 `manifest` 特性。
+//! 总结时识别项目中的清单文件（`Cargo.toml`、`package.json` 与 `pyproject.toml`），记录其包名、依赖数量与工作区信息，
+//! 目录汇总其下各类清单的数量与依赖总数，可用于快速了解单仓库中各个包的依赖规模。
+//! 依赖数量按清单中声明的条目计算，不解析锁文件，也不去除不同清单之间重复的依赖；
+//! 内容无法解析的清单文件不记录清单信息。
+use std::fmt::Display;
+use std::path::Path;
+use toml::{Table, Value};
+
+/// 清单类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum ManifestKind {
+    /// Rust 的 `Cargo.toml`
+    Cargo,
+    /// Node.js 的 `package.json`
+    Npm,
+    /// Python 的 `pyproject.toml`
+    Python,
+}
+
+impl ManifestKind {
+    /// 根据文件名识别清单类型，区分大小写
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::manifest::ManifestKind;
+    ///
+    /// assert_eq!(ManifestKind::from_file_name("Cargo.toml"), Some(ManifestKind::Cargo));
+    /// assert_eq!(ManifestKind::from_file_name("cargo.toml"), None);
+    /// ```
+    pub fn from_file_name(name: &str) -> Option<ManifestKind> {
+        match name {
+            "Cargo.toml" => Some(ManifestKind::Cargo),
+            "package.json" => Some(ManifestKind::Npm),
+            "pyproject.toml" => Some(ManifestKind::Python),
+            _ => None,
+        }
+    }
+
+    /// 清单的文件名
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ManifestKind::Cargo => "Cargo.toml",
+            ManifestKind::Npm => "package.json",
+            ManifestKind::Python => "pyproject.toml",
+        }
+    }
+}
+
+impl Display for ManifestKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.file_name())
+    }
+}
+
+/// 清单信息
+/// - kind：清单类型
+/// - name：包名，仅声明工作区的清单（如 Cargo 的虚拟清单）为空
+/// - dependencies：运行时依赖数量
+/// - dev_dependencies：开发依赖数量
+/// - workspace_members：清单声明的工作区成员（通常为通配路径），不是工作区根清单时为空
+/// - workspace_member：是否从所在工作区继承配置或依赖，即是否为某个工作区的成员
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ManifestInfo {
+    /// 清单类型
+    pub kind: ManifestKind,
+    /// 包名，仅声明工作区的清单为空
+    pub name: Option<String>,
+    /// 运行时依赖数量
+    /// - Cargo：`dependencies` 与 `build-dependencies`，包括各平台（`target.*`）下的依赖
+    /// - npm：`dependencies`、`peerDependencies` 与 `optionalDependencies`
+    /// - Python：`project.dependencies` 与 `project.optional-dependencies`，或 Poetry 的 `dependencies`（不包括 `python`）
+    pub dependencies: u64,
+    /// 开发依赖数量
+    /// - Cargo：`dev-dependencies`，包括各平台下的开发依赖
+    /// - npm：`devDependencies`
+    /// - Python：`dependency-groups`，或 Poetry 的依赖分组与 `dev-dependencies`
+    pub dev_dependencies: u64,
+    /// 声明的工作区成员，不是工作区根清单时为空
+    /// - Cargo：`workspace.members`
+    /// - npm：`workspaces` 或 `workspaces.packages`
+    /// - Python：uv 的 `tool.uv.workspace.members`
+    pub workspace_members: Vec<String>,
+    /// 是否为某个工作区的成员
+    /// - Cargo：声明了 `package.workspace`，或任一字段、依赖使用 `workspace = true` 继承自工作区
+    /// - npm：任一依赖的版本使用 `workspace:` 协议
+    /// - Python：uv 的 `tool.uv.sources` 中有依赖使用 `workspace = true`
+    pub workspace_member: bool,
+}
+
+impl ManifestInfo {
+    /// 根据文件路径与内容解析清单，文件名不是可识别的清单或内容无法解析时返回空
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::manifest::{ManifestInfo, ManifestKind};
+    ///
+    /// let contents = br#"{ "name": "app", "dependencies": { "react": "^19.0.0" } }"#;
+    /// let info = ManifestInfo::parse("web/package.json", contents).unwrap();
+    /// assert_eq!(info.kind, ManifestKind::Npm);
+    /// assert_eq!(info.name.as_deref(), Some("app"));
+    /// assert_eq!(info.dependencies, 1);
+    /// assert_eq!(ManifestInfo::parse("web/index.js", contents), None);
+    /// ```
+    pub fn parse<P: AsRef<Path>>(path: P, contents: &[u8]) -> Option<ManifestInfo> {
+        let name = path.as_ref().file_name()?.to_str()?;
+        let kind = ManifestKind::from_file_name(name)?;
+        let text = std::str::from_utf8(contents).ok()?;
+        match kind {
+            ManifestKind::Cargo => parse_cargo(text),
+            ManifestKind::Npm => parse_npm(text),
+            ManifestKind::Python => parse_pyproject(text),
+        }
+    }
+
+    /// 依赖总数，即运行时依赖与开发依赖之和
+    pub fn total_dependencies(&self) -> u64 {
+        self.dependencies + self.dev_dependencies
+    }
+}
+
+/// 解析 `Cargo.toml`
+fn parse_cargo(text: &str) -> Option<ManifestInfo> {
+    let table: Table = text.parse().ok()?;
+    let package = table.get("package").and_then(Value::as_table);
+    let mut info = ManifestInfo {
+        kind: ManifestKind::Cargo,
+        name: package.and_then(|package| string_at(package, &["name"])),
+        dependencies: 0,
+        dev_dependencies: 0,
+        workspace_members: strings_at(&table, &["workspace", "members"]),
+        workspace_member: package
+            .is_some_and(|package| package.contains_key("workspace") || inherits(package)),
+    };
+    // 各平台下的依赖与顶层依赖的结构相同
+    let targets = table
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values())
+        .filter_map(Value::as_table);
+    for scope in std::iter::once(&table).chain(targets) {
+        for (key, dev) in [
+            ("dependencies", false),
+            ("build-dependencies", false),
+            ("dev-dependencies", true),
+        ] {
+            let Some(dependencies) = scope.get(key).and_then(Value::as_table) else {
+                continue;
+            };
+            if dev {
+                info.dev_dependencies += dependencies.len() as u64;
+            } else {
+                info.dependencies += dependencies.len() as u64;
+            }
+            info.workspace_member |= dependencies
+                .values()
+                .filter_map(Value::as_table)
+                .any(inherits);
+        }
+    }
+    Some(info)
+}
+
+/// 解析 `package.json`
+fn parse_npm(text: &str) -> Option<ManifestInfo> {
+    let json: serde_json::Value = serde_json::from_str(text).ok()?;
+    let object = json.as_object()?;
+    let dependencies = |key: &str| {
+        object
+            .get(key)
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flatten()
+    };
+    let runtime = ["dependencies", "peerDependencies", "optionalDependencies"]
+        .into_iter()
+        .flat_map(dependencies);
+    let workspace_member =
+        runtime
+            .clone()
+            .chain(dependencies("devDependencies"))
+            .any(|(_, version)| {
+                version
+                    .as_str()
+                    .is_some_and(|version| version.starts_with("workspace:"))
+            });
+    // `workspaces` 可以是路径数组，也可以是包含 `packages` 的对象（Yarn）
+    let workspaces = object.get("workspaces").and_then(|workspaces| {
+        workspaces
+            .as_array()
+            .or_else(|| workspaces.get("packages")?.as_array())
+    });
+    Some(ManifestInfo {
+        kind: ManifestKind::Npm,
+        name: object
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .map(String::from),
+        dependencies: runtime.count() as u64,
+        dev_dependencies: dependencies("devDependencies").count() as u64,
+        workspace_members: workspaces
+            .into_iter()
+            .flatten()
+            .filter_map(serde_json::Value::as_str)
+            .map(String::from)
+            .collect(),
+        workspace_member,
+    })
+}
+
+/// 解析 `pyproject.toml`，同时支持 PEP 621 的 `project` 表与 Poetry 的 `tool.poetry` 表
+fn parse_pyproject(text: &str) -> Option<ManifestInfo> {
+    let table: Table = text.parse().ok()?;
+    let project = table.get("project").and_then(Value::as_table);
+    let poetry = table_at(&table, &["tool", "poetry"]);
+    let name = project
+        .and_then(|project| string_at(project, &["name"]))
+        .or_else(|| poetry.and_then(|poetry| string_at(poetry, &["name"])));
+    let mut dependencies = project.map_or(0, |project| {
+        let optional = table_at(project, &["optional-dependencies"]).map_or(0, count_groups);
+        array_len(project.get("dependencies")) + optional
+    });
+    // `dependency-groups` 中的条目也可以是引用其他分组的表，仅统计依赖字符串
+    let mut dev_dependencies = table_at(&table, &["dependency-groups"]).map_or(0, |groups| {
+        groups
+            .values()
+            .filter_map(Value::as_array)
+            .flatten()
+            .filter(|entry| entry.is_str())
+            .count() as u64
+    });
+    if let Some(poetry) = poetry {
+        dependencies += table_at(poetry, &["dependencies"]).map_or(0, |dependencies| {
+            dependencies.keys().filter(|name| *name != "python").count() as u64
+        });
+        dev_dependencies += table_at(poetry, &["dev-dependencies"]).map_or(0, Table::len) as u64;
+        dev_dependencies += table_at(poetry, &["group"]).map_or(0, |groups| {
+            groups
+                .values()
+                .filter_map(|group| table_at(group.as_table()?, &["dependencies"]))
+                .map(|dependencies| dependencies.len() as u64)
+                .sum()
+        });
+    }
+    Some(ManifestInfo {
+        kind: ManifestKind::Python,
+        name,
+        dependencies,
+        dev_dependencies,
+        workspace_members: strings_at(&table, &["tool", "uv", "workspace", "members"]),
+        workspace_member: table_at(&table, &["tool", "uv", "sources"])
+            .is_some_and(|sources| sources.values().filter_map(Value::as_table).any(inherits)),
+    })
+}
+
+/// 表中是否有字段使用 `workspace = true` 继承自工作区，如 `version.workspace = true` 或 `serde = { workspace = true }`
+fn inherits(table: &Table) -> bool {
+    table.get("workspace").and_then(Value::as_bool) == Some(true)
+        || table
+            .values()
+            .filter_map(Value::as_table)
+            .any(|field| field.get("workspace").and_then(Value::as_bool) == Some(true))
+}
+
+/// 按键路径查找子表
+fn table_at<'a>(table: &'a Table, keys: &[&str]) -> Option<&'a Table> {
+    keys.iter()
+        .try_fold(table, |table, key| table.get(*key)?.as_table())
+}
+
+/// 按键路径查找字符串
+fn string_at(table: &Table, keys: &[&str]) -> Option<String> {
+    let (last, parents) = keys.split_last()?;
+    table_at(table, parents)?
+        .get(*last)?
+        .as_str()
+        .map(String::from)
+}
+
+/// 按键路径查找字符串数组，忽略其中不是字符串的元素
+fn strings_at(table: &Table, keys: &[&str]) -> Vec<String> {
+    let Some((last, parents)) = keys.split_last() else {
+        return Vec::new();
+    };
+    table_at(table, parents)
+        .and_then(|table| table.get(*last)?.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(String::from)
+        .collect()
+}
+
+/// 数组的长度，不是数组时为 0
+fn array_len(value: Option<&Value>) -> u64 {
+    value.and_then(Value::as_array).map_or(0, Vec::len) as u64
+}
+
+/// 各分组中数组长度之和
+fn count_groups(groups: &Table) -> u64 {
+    groups.values().map(|group| array_len(Some(group))).sum()
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo() {
+        let member = r#"
+            [package]
+            name = "core"
+            version.workspace = true
+
+            [dependencies]
+            serde = { workspace = true }
+            toml = "0.9"
+
+            [target.'cfg(unix)'.dependencies]
+            libc = "0.2"
+
+            [dev-dependencies]
+            tempfile = "3"
+        "#;
+        let info = ManifestInfo::parse("crates/core/Cargo.toml", member.as_bytes()).unwrap();
+        assert_eq!(info.name.as_deref(), Some("core"));
+        assert_eq!((info.dependencies, info.dev_dependencies), (3, 1));
+        assert!(info.workspace_member);
+        assert!(info.workspace_members.is_empty());
+
+        let root = "[workspace]\nmembers = [\"crates/*\", \"cli\"]\n";
+        let info = ManifestInfo::parse("Cargo.toml", root.as_bytes()).unwrap();
+        assert_eq!(info.name, None);
+        assert_eq!(info.workspace_members, vec!["crates/*", "cli"]);
+        assert!(!info.workspace_member);
+        assert_eq!(ManifestInfo::parse("Cargo.toml", b"[package"), None);
+    }
+
+    #[test]
+    fn test_parse_npm() {
+        let contents = r#"{
+            "name": "web",
+            "workspaces": { "packages": ["packages/*"] },
+            "dependencies": { "ui": "workspace:*", "react": "^19.0.0" },
+            "peerDependencies": { "react-dom": "^19.0.0" },
+            "devDependencies": { "typescript": "^5.0.0" }
+        }"#;
+        let info = ManifestInfo::parse("package.json", contents.as_bytes()).unwrap();
+        assert_eq!((info.dependencies, info.dev_dependencies), (3, 1));
+        assert_eq!(info.total_dependencies(), 4);
+        assert_eq!(info.workspace_members, vec!["packages/*"]);
+        assert!(info.workspace_member);
+        assert_eq!(ManifestInfo::parse("package.json", b"[1, 2]"), None);
+    }
+
+    #[test]
+    fn test_parse_pyproject() {
+        let pep621 = r#"
+            [project]
+            name = "service"
+            dependencies = ["fastapi>=0.110", "shared"]
+
+            [project.optional-dependencies]
+            docs = ["mkdocs"]
+
+            [dependency-groups]
+            dev = ["pytest", { include-group = "lint" }]
+            lint = ["ruff"]
+
+            [tool.uv.sources]
+            shared = { workspace = true }
+        "#;
+        let info = ManifestInfo::parse("pyproject.toml", pep621.as_bytes()).unwrap();
+        assert_eq!(info.name.as_deref(), Some("service"));
+        assert_eq!((info.dependencies, info.dev_dependencies), (3, 2));
+        assert!(info.workspace_member);
+
+        let poetry = r#"
+            [tool.poetry]
+            name = "legacy"
+
+            [tool.poetry.dependencies]
+            python = "^3.12"
+            requests = "^2.31"
+
+            [tool.poetry.group.test.dependencies]
+            pytest = "^8.0"
+        "#;
+        let info = ManifestInfo::parse("pyproject.toml", poetry.as_bytes()).unwrap();
+        assert_eq!(info.name.as_deref(), Some("legacy"));
+        assert_eq!((info.dependencies, info.dev_dependencies), (1, 1));
+        assert!(!info.workspace_member);
+    }
+}
@@ -0,0 +1,159 @@
+//! # 内容类型识别
+//! 根据文件后缀识别文件的 MIME 类型，没有可识别的后缀时再根据文件开头的魔数（如 PNG、ELF 的文件头）识别，
+//! 仍无法识别时根据内容是否为文本，归类为 `text/plain` 或 `application/octet-stream`。
+//! 总结时每个文件记录其类型，目录按类型汇总文件数量。
+use crate::tree::node::count::is_binary;
+use std::path::Path;
+
+/// 根据后缀识别 MIME 类型，后缀不包含 `.`，不区分大小写
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::mime;
+///
+/// assert_eq!(mime::from_extension("PNG"), Some("image/png"));
+/// assert_eq!(mime::from_extension("unknown"), None);
+/// ```
+pub fn from_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension.to_lowercase().as_str() {
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "ts" | "tsx" | "mts" | "cts" => "text/typescript",
+        "rs" => "text/x-rust",
+        "c" | "h" => "text/x-c",
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => "text/x-c++",
+        "go" => "text/x-go",
+        "java" => "text/x-java",
+        "py" | "pyi" => "text/x-python",
+        "rb" => "text/x-ruby",
+        "sh" | "bash" | "zsh" => "text/x-shellscript",
+        "toml" => "application/toml",
+        "yml" | "yaml" => "application/yaml",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/vnd.microsoft.icon",
+        "pdf" => "application/pdf",
+        "zip" | "jar" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "bz2" => "application/x-bzip2",
+        "xz" => "application/x-xz",
+        "7z" => "application/x-7z-compressed",
+        "wasm" => "application/wasm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// 根据文件开头的魔数识别 MIME 类型，无法识别时返回空
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::mime;
+///
+/// assert_eq!(mime::from_magic(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+/// assert_eq!(mime::from_magic(b"\x7fELF\x02\x01"), Some("application/x-executable"));
+/// assert_eq!(mime::from_magic(b"hello"), None);
+/// ```
+pub fn from_magic(contents: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"BZh", "application/x-bzip2"),
+        (b"\xfd7zXZ\x00", "application/x-xz"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"\x7fELF", "application/x-executable"),
+        (b"MZ", "application/vnd.microsoft.portable-executable"),
+        (b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+        (b"\xca\xfe\xba\xbe", "application/x-mach-binary"),
+        (b"\x00asm", "application/wasm"),
+        (b"SQLite format 3\x00", "application/vnd.sqlite3"),
+        (b"ID3", "audio/mpeg"),
+        (b"wOFF", "font/woff"),
+        (b"wOF2", "font/woff2"),
+    ];
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| contents.starts_with(signature))
+    {
+        return Some(mime);
+    }
+    // 容器格式的标识位于文件头之后
+    if contents.starts_with(b"RIFF") && contents.get(8..12) == Some(b"WEBP") {
+        return Some("image/webp");
+    }
+    if contents.get(257..262) == Some(b"ustar") {
+        return Some("application/x-tar");
+    }
+    None
+}
+
+/// 识别文件的 MIME 类型
+/// 优先根据后缀识别，其次根据魔数识别，仍无法识别时根据内容是否为二进制（见 `is_binary`），
+/// 归类为 `text/plain` 或 `application/octet-stream`；内容为空时返回空
+pub fn detect<P: AsRef<Path>>(path: P, contents: &[u8]) -> Option<&'static str> {
+    path.as_ref()
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(from_extension)
+        .or_else(|| from_magic(contents))
+        .or_else(|| {
+            if contents.is_empty() {
+                None
+            } else if !is_binary(contents) {
+                Some("text/plain")
+            } else {
+                Some("application/octet-stream")
+            }
+        })
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect("./src/lib.rs", b""), Some("text/x-rust"));
+        // 后缀优先于魔数
+        assert_eq!(detect("./image.png", b"GIF89a"), Some("image/png"));
+        assert_eq!(
+            detect("./bin/tool", b"\x7fELF\x02"),
+            Some("application/x-executable")
+        );
+        assert_eq!(detect("./LICENSE", b"MIT License"), Some("text/plain"));
+        assert_eq!(
+            detect("./blob", b"\x00\x01\x02"),
+            Some("application/octet-stream")
+        );
+        assert_eq!(detect("./empty", b""), None);
+        let mut tar = vec![0; 300];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(detect("./archive", &tar), Some("application/x-tar"));
+    }
+}
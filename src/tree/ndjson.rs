@@ -0,0 +1,178 @@
+//! # NDJSON 导出
+//! 将项目树按先序遍历的顺序逐个节点写为换行分隔的 JSON（NDJSON），每行一个节点，
+//! 边遍历边写入，无需在内存中生成完整的 JSON 字符串，适合导出节点数量巨大的项目树。
+//! 每个节点对象不包含子节点，父节点总是先于子节点输出，可根据 `depth` 与输出顺序还原层级。
+//!
+//! 此外，`write_changes` 将 `ProjectTree::refresh` 得到的变更写为事件日志，每行一个变更事件，
+//! 持续追加到同一文件中即可得到只追加的增量日志，消费者只需跟踪（tail）新增的行，无需反复读取完整快照。
+use crate::errors::AruiError;
+use crate::fs::FileKind;
+use crate::tree::diff::{ChangeSet, Metrics};
+use crate::tree::export::{self, TreeExporter};
+use crate::tree::node::{NodeMarker, TreeNode};
+use crate::tree::summary::NodeSummary;
+use serde::Serialize;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 单个节点的记录
+#[derive(Serialize)]
+struct NodeRecord<'a> {
+    /// 节点路径
+    path: &'a str,
+    /// 相对导出起点的深度，起点为 0
+    depth: usize,
+    /// 是否是目录
+    is_dir: bool,
+    /// 文件类型
+    kind: FileKind,
+    /// 子节点数量，文件或尚未展开的目录为 `null`
+    children: Option<usize>,
+    /// 总结信息
+    summary: &'a NodeSummary,
+    /// 节点标记
+    marker: Option<&'a NodeMarker>,
+    /// 符号链接指向的目标
+    symlink_target: Option<&'a str>,
+}
+
+/// 将节点及其下的所有节点写入 `writer`，每行一个 JSON 对象，行以 `\n` 结尾
+/// 对象包含 `path`、`depth`、`is_dir`、`kind`、`children`（子节点数量）、`summary`、`marker` 与 `symlink_target`
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::ndjson;
+/// use arui_core::tree::node::TreeNode;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// root.children = Some(vec![TreeNode::new("./project/main.rs", false)]);
+/// let mut output = Vec::new();
+/// ndjson::write(&root, &mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert_eq!(output.lines().count(), 2);
+/// assert!(output.starts_with("{\"path\":\"./project\",\"depth\":0,"));
+/// ```
+pub fn write<W: Write>(root: &TreeNode, writer: W) -> Result<(), AruiError> {
+    export::walk(root, &mut NdjsonExporter::new(writer))
+}
+
+/// NDJSON 导出器，每个节点写入一行
+pub struct NdjsonExporter<W: Write> {
+    /// 输出目标
+    writer: W,
+}
+
+impl<W: Write> NdjsonExporter<W> {
+    /// 创建写入 `writer` 的 NDJSON 导出器
+    pub fn new(writer: W) -> Self {
+        NdjsonExporter { writer }
+    }
+}
+
+impl<W: Write> TreeExporter for NdjsonExporter<W> {
+    fn visit_node(&mut self, node: &TreeNode, depth: usize) -> Result<(), AruiError> {
+        let record = NodeRecord {
+            path: &node.path,
+            depth,
+            is_dir: node.is_dir,
+            kind: node.kind,
+            children: node.children.as_ref().map(Vec::len),
+            summary: &node.summary,
+            marker: node.marker.as_ref(),
+            symlink_target: node.symlink_target.as_deref(),
+        };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), AruiError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// 单个变更事件
+#[derive(Serialize)]
+struct ChangeEvent<'a> {
+    /// 变更的检测时间（Unix 时间戳，单位为毫秒），同一批次的事件相同
+    time: u64,
+    /// 事件类型：`removed`、`added` 或 `modified`
+    event: &'static str,
+    /// 节点路径
+    path: &'a str,
+    /// 变化前的统计信息，新增的节点为 `null`
+    before: Option<Metrics>,
+    /// 变化后的统计信息，删除的节点为 `null`
+    after: Option<Metrics>,
+}
+
+/// 将变更集合中的每个变更写为一行事件，返回写入的事件数量，没有变更时不写入任何内容
+/// - changes：变更集合，通常由 `ProjectTree::refresh` 生成
+/// - time：检测到变更的时间
+///
+/// 事件对象包含 `time`、`event`、`path`、`before` 与 `after`，按删除、新增、修改的顺序输出，
+/// 节点类型改变时先输出删除事件再输出新增事件，依次回放即可得到最新状态。
+/// 每个事件通过一次 `write_all` 写入，`writer` 为以追加模式打开的文件时，跟踪日志的消费者不会读到不完整的行
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::diff::ChangeSet;
+/// use arui_core::tree::ndjson;
+/// use std::time::UNIX_EPOCH;
+///
+/// let changes = ChangeSet {
+///     added: vec!["./project/new.rs".to_string()],
+///     ..ChangeSet::default()
+/// };
+/// let mut log = Vec::new();
+/// assert_eq!(ndjson::write_changes(&changes, UNIX_EPOCH, &mut log).unwrap(), 1);
+/// assert_eq!(
+///     String::from_utf8(log).unwrap(),
+///     "{\"time\":0,\"event\":\"added\",\"path\":\"./project/new.rs\",\"before\":null,\"after\":null}\n"
+/// );
+/// ```
+pub fn write_changes<W: Write>(
+    changes: &ChangeSet,
+    time: SystemTime,
+    mut writer: W,
+) -> Result<u64, AruiError> {
+    let time = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64);
+    let events = [
+        ("removed", &changes.removed),
+        ("added", &changes.added),
+        ("modified", &changes.modified),
+    ];
+    let mut count = 0;
+    let mut line = Vec::new();
+    for (event, paths) in events {
+        for path in paths {
+            let metrics = changes.metrics.get(path).copied().unwrap_or_default();
+            let record = ChangeEvent {
+                time,
+                event,
+                path,
+                before: if event == "added" {
+                    None
+                } else {
+                    metrics.before
+                },
+                after: if event == "removed" {
+                    None
+                } else {
+                    metrics.after
+                },
+            };
+            line.clear();
+            serde_json::to_writer(&mut line, &record)?;
+            line.push(b'\n');
+            writer.write_all(&line)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
@@ -3,25 +3,75 @@
 //! 注意！项目树节点一般由 tree 代理，而不应该由用户手动控制，某些情况下可以对节点进行数据获取操作，但应该仅限于此。
 pub mod count;
 pub mod file;
-use crate::{tree::summary::NodeSummary, utils::check_path};
+use crate::errors::AruiError;
+use crate::fs::{DiskFs, FileKind, FileSystem, FsMetadata};
+use crate::tree::builder::TreeBuilder;
+use crate::{
+    tree::summary::NodeSummary,
+    utils::{check_path, normalize_unicode},
+};
 use std::fmt::Display;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 节点标记，用于记录构建过程中识别到的特殊情况
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum NodeMarker {
+    /// 目录通过符号链接指向了自身或祖先目录，为避免无限递归，该节点不包含子节点
+    /// - target：循环指向的规范路径
+    CycleDetected { target: String },
+    /// 沙箱模式下，节点的规范路径位于项目根路径之外，该节点不包含子节点，也不读取其内容
+    /// - target：节点实际指向的规范路径
+    OutsideRoot { target: String },
+    /// 节点为特殊文件（如管道、套接字、设备文件），读取其内容可能无限阻塞，因此不读取其内容
+    /// - kind：特殊文件的类型
+    SpecialFile { kind: FileKind },
+    /// 节点为指向不存在目标的符号链接，不读取其内容
+    /// - target：链接指向的目标
+    BrokenSymlink { target: String },
+}
+
+impl Display for NodeMarker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeMarker::CycleDetected { target } => write!(f, "cycle -> {}", target),
+            NodeMarker::OutsideRoot { target } => write!(f, "outside root -> {}", target),
+            NodeMarker::SpecialFile { kind } => write!(f, "special file: {}", kind),
+            NodeMarker::BrokenSymlink { target } => write!(f, "broken symlink -> {}", target),
+        }
+    }
+}
 
 /// 目录树节点
 /// - 节点为文件时，无子树
 /// - 节点为目录时，有子树
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TreeNode {
     /// 当前节点所处路径
     pub path: String,
     /// 是否是目录
-    /// TODO：后续应该改为自动检测
+    /// 通过 `from_path` 创建或由项目树构建时根据元数据自动检测
     pub is_dir: bool,
+    /// 文件类型，通过 `new` 创建时根据 `is_dir` 推断为普通文件或目录
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: FileKind,
     /// 如果是目录，那么遍历他的子节点；如果为文件，则为空
+    /// 浅构建时尚未展开的目录同样为空，可通过 `expand` 按需加载
     pub children: Option<Vec<TreeNode>>,
     /// 节点总结信息
     /// - 文件：当前文件的总结信息
     /// - 目录：当前目录下所有文件的总结信息的加合
+    #[cfg_attr(feature = "serde", serde(default))]
     pub summary: NodeSummary,
+    /// 节点标记，构建过程中识别到特殊情况时设置
+    pub marker: Option<NodeMarker>,
+    /// 节点为符号链接时，链接指向的目标（即链接中记录的原始路径，可能为相对路径），否则为空
+    /// 目标是否存在可通过 `is_broken_symlink` 判断
+    pub symlink_target: Option<String>,
 }
 
 /// 为节点实现 Display
@@ -54,12 +104,88 @@ impl TreeNode {
     {
         TreeNode {
             is_dir,
+            kind: if is_dir {
+                FileKind::Directory
+            } else {
+                FileKind::Regular
+            },
             path: path.into(),
             children: if is_dir { Some(Vec::new()) } else { None },
             summary: NodeSummary::new(),
+            marker: None,
+            symlink_target: None,
         }
     }
 
+    /// 根据磁盘上的路径创建节点，自动检测是否为目录及文件类型，符号链接会被跟随
+    /// - 目录：子节点尚未加载，可通过 `expand` 按需加载
+    /// - 特殊文件：标记为 `NodeMarker::SpecialFile`
+    ///
+    /// 路径不存在时返回 `AruiError::InvalidPath`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::fs::FileKind;
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// let node = TreeNode::from_path("./src").unwrap();
+    /// assert!(node.is_dir);
+    /// assert_eq!(node.kind, FileKind::Directory);
+    /// assert!(!TreeNode::from_path("./src/lib.rs").unwrap().is_dir);
+    /// assert!(TreeNode::from_path("/not_exist").is_err());
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, AruiError> {
+        let path = path.as_ref();
+        match DiskFs.metadata(path) {
+            Ok(metadata) => Ok(TreeNode::from_metadata(path, &metadata)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Err(AruiError::InvalidPath(path.to_string_lossy().into_owned()))
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// 根据已获取的元数据创建节点，不访问文件系统
+    pub(crate) fn from_metadata(path: &Path, metadata: &FsMetadata) -> Self {
+        let mut node = TreeNode::new(path.to_string_lossy().into_owned(), metadata.is_dir);
+        node.kind = metadata.kind;
+        if metadata.is_dir {
+            node.children = None;
+        } else if metadata.kind.is_special() {
+            node.marker = Some(NodeMarker::SpecialFile {
+                kind: metadata.kind,
+            });
+        }
+        node
+    }
+
+    /// 节点是否为符号链接，由项目树构建时识别
+    pub fn is_symlink(&self) -> bool {
+        self.symlink_target.is_some()
+    }
+
+    /// 节点是否为指向不存在目标的符号链接
+    pub fn is_broken_symlink(&self) -> bool {
+        matches!(self.marker, Some(NodeMarker::BrokenSymlink { .. }))
+    }
+
+    /// 节点是否为隐藏文件或目录，即名称以 `.` 开头（`.` 与 `..` 除外）
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// assert!(TreeNode::new("./.github", true).is_hidden());
+    /// assert!(!TreeNode::new("./src", true).is_hidden());
+    /// assert!(!TreeNode::new(".", true).is_hidden());
+    /// ```
+    pub fn is_hidden(&self) -> bool {
+        let name = self.name();
+        name.starts_with('.') && name != "." && name != ".."
+    }
+
     /// 检测节点路径是否合法
     ///
     /// # Examples
@@ -76,6 +202,174 @@ impl TreeNode {
     pub fn is_valid(&self) -> bool {
         check_path(&self.path).is_ok()
     }
+
+    /// 节点名称，即路径的最后一部分，无法获取时返回完整路径
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// assert_eq!(TreeNode::new("./src/lib.rs", false).name(), "lib.rs");
+    /// ```
+    pub fn name(&self) -> &str {
+        Path::new(&self.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.path)
+    }
+
+    /// 节点的子节点是否已加载，文件节点始终视为已加载
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// let mut node = TreeNode::new("./src", true);
+    /// assert_eq!(node.is_loaded(), true);
+    /// node.children = None;
+    /// assert_eq!(node.is_loaded(), false);
+    /// ```
+    pub fn is_loaded(&self) -> bool {
+        !self.is_dir || self.children.is_some()
+    }
+
+    /// 从磁盘展开目录节点，加载其直接子节点
+    /// 子目录保持未展开状态，已加载的目录或文件节点不做任何操作。
+    /// 注意！展开后节点的总结信息会被重置，需要重新获取。
+    /// 属于项目树的节点应通过 `ProjectTree::expand` 展开，以使用项目树的文件系统、配置、进度报告与取消令牌；
+    /// 单独使用其他文件系统时可调用 `expand_with`。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// let mut node = TreeNode::new("./src", true);
+    /// node.children = None;
+    /// node.expand().unwrap();
+    /// assert_eq!(node.children.unwrap().len() > 0, true);
+    /// ```
+    pub fn expand(&mut self) -> Result<(), AruiError> {
+        self.expand_with(Arc::new(DiskFs))
+    }
+
+    /// 使用指定的文件系统展开目录节点，行为与 `expand` 相同
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::node::TreeNode;
+    /// use std::sync::Arc;
+    ///
+    /// let fs = MemoryFs::new().add_file("project/main.rs", "fn main() {}\n");
+    /// let mut node = TreeNode::new("project", true);
+    /// node.children = None;
+    /// node.expand_with(Arc::new(fs)).unwrap();
+    /// assert_eq!(node.children.unwrap()[0].name(), "main.rs");
+    /// ```
+    pub fn expand_with(&mut self, fs: Arc<dyn FileSystem>) -> Result<(), AruiError> {
+        if self.is_loaded() {
+            return Ok(());
+        }
+        *self = TreeBuilder::shallow(1)
+            .with_fs(fs)
+            .build(Path::new(&self.path))?;
+        Ok(())
+    }
+}
+
+/// 节点导航相关
+/// 子节点的路径总是以父节点的路径为前缀，因此可以从任意节点出发，沿路径逐级向下定位后代节点及其祖先链，
+/// 无需在节点中保存指向父节点的引用。路径比较前均转换为 Unicode NFC 形式，不受文件名规范化形式的影响
+impl TreeNode {
+    /// 查找路径对应的节点，可以是当前节点本身或其任意后代节点
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// let mut node = TreeNode::new("./src", true);
+    /// node.children = Some(vec![TreeNode::new("./src/lib.rs", false)]);
+    /// assert_eq!(node.find("./src/lib.rs").unwrap().name(), "lib.rs");
+    /// assert!(node.find("./src/main.rs").is_none());
+    /// ```
+    pub fn find<P: AsRef<Path>>(&self, path: P) -> Option<&TreeNode> {
+        let path = normalize_unicode(path);
+        let mut node = self;
+        while !node.is_at(&path) {
+            node = node.child_towards(&path)?;
+        }
+        Some(node)
+    }
+
+    /// 查找路径对应的节点，返回可变引用
+    pub fn find_mut<P: AsRef<Path>>(&mut self, path: P) -> Option<&mut TreeNode> {
+        let path = normalize_unicode(path);
+        let mut node = self;
+        while !node.is_at(&path) {
+            node = node
+                .children
+                .iter_mut()
+                .flatten()
+                .find(|child| child.leads_to(&path))?;
+        }
+        Some(node)
+    }
+
+    /// 获取路径对应节点的祖先链，从当前节点开始依次到其父节点，不包含目标节点本身
+    /// 目标节点为当前节点时返回空列表，不存在时返回空
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    ///
+    /// let mut tree = TreeNode::new("./src", true);
+    /// let mut sub = TreeNode::new("./src/tree", true);
+    /// sub.children = Some(vec![TreeNode::new("./src/tree/node.rs", false)]);
+    /// tree.children = Some(vec![sub]);
+    ///
+    /// let ancestors = tree.ancestors("./src/tree/node.rs").unwrap();
+    /// let names: Vec<&str> = ancestors.iter().map(|node| node.name()).collect();
+    /// assert_eq!(names, vec!["src", "tree"]);
+    /// ```
+    pub fn ancestors<P: AsRef<Path>>(&self, path: P) -> Option<Vec<&TreeNode>> {
+        let path = normalize_unicode(path);
+        let mut chain = Vec::new();
+        let mut node = self;
+        while !node.is_at(&path) {
+            chain.push(node);
+            node = node.child_towards(&path)?;
+        }
+        Some(chain)
+    }
+
+    /// 获取路径对应节点的父节点，目标节点为当前节点或不存在时返回空
+    pub fn parent<P: AsRef<Path>>(&self, path: P) -> Option<&TreeNode> {
+        self.ancestors(path)?.pop()
+    }
+
+    /// 获取通往目标路径的子节点，目标路径需为 NFC 形式
+    fn child_towards(&self, path: &Path) -> Option<&TreeNode> {
+        self.children
+            .iter()
+            .flatten()
+            .find(|child| child.leads_to(path))
+    }
+
+    /// 节点路径是否为目标路径，目标路径需为 NFC 形式
+    fn is_at(&self, path: &Path) -> bool {
+        normalize_unicode(&self.path) == path
+    }
+
+    /// 目标路径是否位于节点之下（包括节点本身），目标路径需为 NFC 形式
+    fn leads_to(&self, path: &Path) -> bool {
+        path.starts_with(normalize_unicode(&self.path))
+    }
 }
 
 /// 为节点实现总结信息相关操作
@@ -121,6 +415,40 @@ impl TreeNode {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_navigation() {
+        let mut root = TreeNode::new("/project", true);
+        let mut src = TreeNode::new("/project/src", true);
+        src.children = Some(vec![TreeNode::new("/project/src/lib.rs", false)]);
+        // 名称前缀相同的兄弟节点不会被误匹配
+        root.children = Some(vec![TreeNode::new("/project/src2", true), src]);
+
+        let ancestors = root.ancestors("/project/src/lib.rs").unwrap();
+        assert_eq!(ancestors.len(), 2);
+        assert_eq!(ancestors[1].path, "/project/src");
+        assert_eq!(
+            root.parent("/project/src/lib.rs").unwrap().path,
+            "/project/src"
+        );
+        assert!(root.parent("/project").is_none());
+        assert!(root.ancestors("/project").unwrap().is_empty());
+        assert!(root.find("/project/src2/lib.rs").is_none());
+        assert!(root.find("/other").is_none());
+
+        // NFD 形式的文件名可以通过 NFC 形式的路径找到
+        let nfd = "/project/src/cafe\u{301}.rs";
+        root.find_mut("/project/src")
+            .unwrap()
+            .children
+            .as_mut()
+            .unwrap()
+            .push(TreeNode::new(nfd, false));
+        assert_eq!(root.find("/project/src/caf\u{e9}.rs").unwrap().path, nfd);
+
+        root.find_mut("/project/src/lib.rs").unwrap().summary.size = 3;
+        assert_eq!(root.find("/project/src/lib.rs").unwrap().summary.size, 3);
+    }
+
     #[test]
     fn test_display() {
         let node = TreeNode::new("./tests/examples/tree/summary".to_string(), true);
@@ -132,13 +460,59 @@ mod tests {
     fn test_new() {
         let node = TreeNode::new("./tests/examples/tree/summary".to_string(), true);
         assert_eq!(node.path, "./tests/examples/tree/summary");
-        assert_eq!(node.is_dir, true);
+        assert!(node.is_dir);
         assert!(node.children.is_some());
         // 检查总结信息
         assert_eq!(node.summary.size, 0);
         assert_eq!(node.summary.count, 0);
     }
 
+    #[test]
+    /// 测试根据路径创建节点
+    fn test_from_path() {
+        let mut dir = TreeNode::from_path("./tests/examples/tree").unwrap();
+        assert!(dir.is_dir);
+        assert_eq!(dir.kind, FileKind::Directory);
+        assert!(!dir.is_loaded());
+        dir.expand().unwrap();
+        assert_eq!(dir.children.as_ref().unwrap().len(), 3);
+
+        let file = TreeNode::from_path("./tests/examples/tree/summary/test.txt").unwrap();
+        assert!(!file.is_dir);
+        assert_eq!(file.kind, FileKind::Regular);
+        assert!(file.marker.is_none());
+        assert!(matches!(
+            TreeNode::from_path("./not_exist"),
+            Err(AruiError::InvalidPath(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    /// 测试根据路径创建特殊文件节点
+    fn test_from_special_path() {
+        let node = TreeNode::from_path("/dev/null").unwrap();
+        assert_eq!(node.kind, FileKind::CharDevice);
+        assert_eq!(
+            node.marker,
+            Some(NodeMarker::SpecialFile {
+                kind: FileKind::CharDevice
+            })
+        );
+    }
+
+    #[test]
+    /// 测试按需展开目录节点
+    fn test_expand() {
+        let mut node = TreeNode::new("./tests/examples/tree", true);
+        node.children = None;
+        node.expand().unwrap();
+        let children = node.children.as_ref().unwrap();
+        assert_eq!(children.len(), 3);
+        // 子目录保持未展开
+        assert!(children.iter().all(|child| !child.is_loaded()));
+    }
+
     #[test]
     /// 在测试中调用
     fn test_summary_update() {
@@ -1,19 +1,576 @@
 //! # 文本统计特征相关
 //! - `get_file_count` 获取文件中文本行数
-use std::io::Error;
+//! - `count_lines` 获取任意读取源中的文本行数
+//! - `classify_lines` 将文本行划分为代码、注释与空白
+//! - `is_binary` 判断内容是否为二进制
+//! - `detect_line_ending` 识别内容的换行符风格
+//! - `max_line_length` 获取文本中最长一行的字符数
+//! - `count_words` 获取文本中的词数
+//! - `count_markers` 统计文本中各代码标记出现的次数
+//! - `estimate_tokens` 估算文本的 token 数量
+//! - `count_functions` 粗略统计源码中声明的函数数量
+use crate::tree::language::{CommentSyntax, Language};
+use crate::tree::summary::{LineEnding, LineStats};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Error, Read};
 
 // --------------------- 文件相关 ---------------------
 
+/// 逐块统计行数时每次读取的字节数
+pub(crate) const READ_BUFFER_LEN: usize = 64 * 1024;
+
 /// 获取文件中文本行数
+/// 以固定大小的缓冲区逐块读取文件，内存占用与文件大小无关
 pub fn get_file_count(path: &str) -> Result<u64, Error> {
-    Ok(std::fs::read_to_string(path)?.lines().count() as u64)
+    count_lines(std::fs::File::open(path)?)
+}
+
+/// 获取读取源中的文本行数
+/// 以固定大小的缓冲区逐块读取，不会将全部内容读入内存；直接按字节统计换行符，内容无需为合法的 UTF-8 文本（如 Latin-1、GBK 文本）；末尾没有换行符的最后一行同样计入
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::count_lines;
+///
+/// assert_eq!(count_lines(&b"caf\xe9\r\nna\xefve"[..]).unwrap(), 2);
+/// assert_eq!(count_lines(&b"a\n\n"[..]).unwrap(), 2);
+/// assert_eq!(count_lines(&b""[..]).unwrap(), 0);
+/// ```
+pub fn count_lines<R: Read>(mut reader: R) -> Result<u64, Error> {
+    let mut buffer = vec![0; READ_BUFFER_LEN];
+    let mut lines = 0;
+    let mut last = None;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        lines += buffer[..read].iter().filter(|byte| **byte == b'\n').count() as u64;
+        last = buffer.get(read - 1).copied();
+    }
+    if last.is_some_and(|byte| byte != b'\n') {
+        lines += 1;
+    }
+    Ok(lines)
+}
+
+/// 判断内容时检查的开头字节数
+const SNIFF_LEN: usize = 8192;
+
+/// 根据内容开头的一段字节判断内容是否为二进制
+/// 包含空字节，或不是合法的 UTF-8 文本时视为二进制；末尾被截断的多字节字符不影响判断
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::is_binary;
+///
+/// assert!(!is_binary("你好，世界".as_bytes()));
+/// assert!(is_binary(b"\x7fELF\x02\x01\x00"));
+/// assert!(is_binary(b"\xff\xfe"));
+/// ```
+pub fn is_binary(contents: &[u8]) -> bool {
+    let block = &contents[..contents.len().min(SNIFF_LEN)];
+    if block.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(block) {
+        Ok(_) => false,
+        // 开头一段恰好截断了多字节字符
+        Err(error) => error.error_len().is_some(),
+    }
+}
+
+/// 获取文本中最长一行的字符数（Unicode 标量值的数量），不包括换行符
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::max_line_length;
+///
+/// assert_eq!(max_line_length("ab\r\n你好世界\n"), 4);
+/// assert_eq!(max_line_length(""), 0);
+/// ```
+pub fn max_line_length(contents: &str) -> u64 {
+    contents
+        .lines()
+        .map(|line| line.chars().count() as u64)
+        .max()
+        .unwrap_or_default()
+}
+
+/// 获取文本中的词数
+/// 以空白分隔词语；中日文字符之间通常没有空白，因此每个汉字、假名均单独计为一个词
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::count_words;
+///
+/// assert_eq!(count_words("Hello,  world!\n"), 2);
+/// assert_eq!(count_words("使用 Rust 编写"), 5);
+/// assert_eq!(count_words(" \n"), 0);
+/// ```
+pub fn count_words(contents: &str) -> u64 {
+    let mut words = 0;
+    let mut in_word = false;
+    for c in contents.chars() {
+        if c.is_whitespace() {
+            in_word = false;
+        } else if is_cjk(c) {
+            words += 1;
+            in_word = false;
+        } else if !in_word {
+            words += 1;
+            in_word = true;
+        }
+    }
+    words
+}
+
+/// 统计文本中各代码标记（如 `TODO`、`FIXME`）出现的次数，未出现的标记不记录
+/// 标记按完整单词区分大小写匹配，前后紧邻字母、数字或下划线时不计入
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::count_markers;
+///
+/// let markers = ["TODO".to_string(), "FIXME".to_string()];
+/// let counts = count_markers("// TODO: a\n// TODO_LIST, FIXME!\n", &markers);
+/// assert_eq!((counts["TODO"], counts["FIXME"]), (1, 1));
+/// ```
+pub fn count_markers(contents: &str, markers: &[String]) -> HashMap<String, u64> {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let mut counts = HashMap::new();
+    for marker in markers.iter().filter(|marker| !marker.is_empty()) {
+        let count = contents
+            .match_indices(marker.as_str())
+            .filter(|(index, _)| {
+                !is_word(contents[..*index].chars().next_back())
+                    && !is_word(contents[index + marker.len()..].chars().next())
+            })
+            .count() as u64;
+        if count > 0 {
+            counts.insert(marker.clone(), count);
+        }
+    }
+    counts
+}
+
+/// 估算文本的 token 数量，按每 4 个字符约 1 个 token 计算并向上取整
+/// 该估算与常见的大语言模型分词器在英文与代码上的结果相近，中文等文本的实际数量通常更多
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::estimate_tokens;
+///
+/// assert_eq!(estimate_tokens("fn main() {}"), 3);
+/// assert_eq!(estimate_tokens("a"), 1);
+/// assert_eq!(estimate_tokens(""), 0);
+/// ```
+pub fn estimate_tokens(contents: &str) -> u64 {
+    (contents.chars().count() as u64).div_ceil(4)
+}
+
+/// 声明函数前可能出现的修饰符
+const FUNCTION_MODIFIERS: [&str; 17] = [
+    "pub",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "export",
+    "default",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "local",
+    "override",
+    "open",
+    "suspend",
+    "inline",
+];
+
+/// 根据语言的函数声明关键字（如 Rust 的 `fn `、Python 的 `def `、JavaScript 的 `function `）粗略统计函数数量
+/// 去除行首的修饰符（如 `pub`、`async`、`export`）后，以关键字开头的行计为一个函数，以单行注释前缀开头的行不计入。
+/// 该统计基于启发式规则，无法识别箭头函数、通过返回类型声明的函数（如 C、Java）以及字符串中的内容，仅作为复杂度的粗略参考
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::Language;
+/// use arui_core::tree::node::count::count_functions;
+///
+/// let rust = "pub(crate) async fn a() {}\n// fn commented() {}\nfn b() {}\nlet f = 1;\n";
+/// assert_eq!(count_functions(rust, Some(Language::Rust)), 2);
+/// assert_eq!(count_functions("def a():\n  pass\n", Some(Language::Python)), 1);
+/// assert_eq!(count_functions("fn a() {}", None), 0);
+/// ```
+pub fn count_functions(contents: &str, language: Option<Language>) -> u64 {
+    let Some(language) = language else {
+        return 0;
+    };
+    let keywords = language.function_keywords();
+    if keywords.is_empty() {
+        return 0;
+    }
+    let comments = language.comment_syntax().line;
+    contents
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| !comments.iter().any(|prefix| line.starts_with(prefix)))
+        .filter(|line| {
+            let mut rest = *line;
+            // 逐个去除修饰符，如 `pub(crate)`、`extern "C"`
+            while let Some(word) = rest.split_whitespace().next() {
+                let modifier = word.split(['(', '"']).next().unwrap_or(word);
+                if !FUNCTION_MODIFIERS.contains(&modifier) {
+                    break;
+                }
+                rest = rest[word.len()..].trim_start();
+                if word == "extern" && rest.starts_with('"') {
+                    rest = rest[1..]
+                        .split_once('"')
+                        .map_or("", |(_, rest)| rest.trim_start());
+                }
+            }
+            keywords.iter().any(|keyword| rest.starts_with(keyword))
+        })
+        .count() as u64
+}
+
+/// 是否为中日文的汉字或假名
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30ff}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{20000}'..='\u{2ffff}'
+    )
+}
+
+/// 识别内容的换行符风格，没有换行符时返回空
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::count::detect_line_ending;
+/// use arui_core::tree::summary::LineEnding;
+///
+/// assert_eq!(detect_line_ending(b"a\nb\n"), Some(LineEnding::Lf));
+/// assert_eq!(detect_line_ending(b"a\r\nb\r\n"), Some(LineEnding::Crlf));
+/// assert_eq!(detect_line_ending(b"a\r\nb\n"), Some(LineEnding::Mixed));
+/// assert_eq!(detect_line_ending(b"a"), None);
+/// ```
+pub fn detect_line_ending(contents: &[u8]) -> Option<LineEnding> {
+    let (mut lf, mut crlf) = (false, false);
+    for (index, _) in contents
+        .iter()
+        .enumerate()
+        .filter(|(_, byte)| **byte == b'\n')
+    {
+        if index > 0 && contents[index - 1] == b'\r' {
+            crlf = true;
+        } else {
+            lf = true;
+        }
+        if lf && crlf {
+            return Some(LineEnding::Mixed);
+        }
+    }
+    match (lf, crlf) {
+        (true, _) => Some(LineEnding::Lf),
+        (_, true) => Some(LineEnding::Crlf),
+        _ => None,
+    }
+}
+
+/// 根据语言的注释语法，将文本行划分为代码、注释与空白，划分结果的总行数与 `count_lines` 一致
+/// - 空白行：仅包含空白字符的行
+/// - 注释行：除注释外仅包含空白字符的行，包括位于块注释之内的行
+/// - 代码行：其余的行，包括行尾带有注释的代码
+///
+/// 字符串与字符字面量中的注释标记不生效，支持嵌套块注释的语言（如 Rust）按嵌套层数判断块注释的结束；
+/// 字符串仅在同一行内识别，跨行的字符串从第二行起按普通代码处理。
+/// 语言为空或没有注释语法时，除空白行外均视为代码行
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::language::Language;
+/// use arui_core::tree::node::count::classify_lines;
+///
+/// let stats = classify_lines("// comment\n\nfn main() {}\n", Some(Language::Rust));
+/// assert_eq!((stats.code, stats.comments, stats.blanks), (1, 1, 1));
+/// ```
+pub fn classify_lines(contents: &str, language: Option<Language>) -> LineStats {
+    let mut classifier = LineClassifier::new(language);
+    for line in contents.lines() {
+        classifier.push(line);
+    }
+    classifier.stats
+}
+
+/// 逐行划分代码、注释与空白，记录跨行的块注释状态，规则见 `classify_lines`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LineClassifier {
+    /// 语言的注释语法，为空时除空白行外均视为代码行
+    syntax: Option<CommentSyntax>,
+    /// 当前所在块注释的嵌套层数
+    depth: usize,
+    /// 已划分的行数
+    pub(crate) stats: LineStats,
+}
+
+impl LineClassifier {
+    /// 创建指定语言的划分器
+    pub(crate) fn new(language: Option<Language>) -> Self {
+        LineClassifier {
+            syntax: language.map(|language| language.comment_syntax()),
+            ..Default::default()
+        }
+    }
+
+    /// 划分一行文本，`line` 不包含换行符
+    pub(crate) fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if line.is_empty() {
+            self.stats.blanks += 1;
+        } else if self.scan(line) {
+            self.stats.code += 1;
+        } else {
+            self.stats.comments += 1;
+        }
+    }
+
+    /// 扫描一行非空文本，更新块注释的嵌套层数，返回该行是否包含注释之外的内容
+    fn scan(&mut self, line: &str) -> bool {
+        let Some(syntax) = self.syntax else {
+            return true;
+        };
+        let mut code = false;
+        // 当前所在字符串的引号
+        let mut quote: Option<char> = None;
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            let mut skip = c.len_utf8();
+            if let Some((start, end)) = syntax.block.filter(|_| self.depth > 0) {
+                if rest.starts_with(end) {
+                    self.depth -= 1;
+                    skip = end.len();
+                } else if syntax.nested && rest.starts_with(start) {
+                    self.depth += 1;
+                    skip = start.len();
+                }
+            } else if let Some(open) = quote {
+                if c == '\\' {
+                    skip += rest[skip..].chars().next().map_or(0, char::len_utf8);
+                } else if c == open {
+                    quote = None;
+                }
+            } else if let Some((start, _)) =
+                syntax.block.filter(|(start, _)| rest.starts_with(start))
+            {
+                // 块注释的起始标记可能以单行注释的前缀开头（如 Lua 的 `--[[`），因此优先判断
+                self.depth += 1;
+                skip = start.len();
+            } else if syntax.line.iter().any(|prefix| rest.starts_with(prefix)) {
+                break;
+            } else {
+                code |= !c.is_whitespace();
+                if syntax.quotes.contains(&c) {
+                    quote = Some(c);
+                } else if c == '\'' && syntax.chars {
+                    skip = char_literal_len(rest).unwrap_or(skip);
+                }
+            }
+            rest = &rest[skip..];
+        }
+        code
+    }
+}
+
+/// `text` 开头的字符字面量（如 `'a'`、`'\''`）的字节数，不构成字符字面量时为空
+fn char_literal_len(text: &str) -> Option<usize> {
+    let inner = text.strip_prefix('\'')?;
+    let len = if let Some(escaped) = inner.strip_prefix('\\') {
+        // 转义序列的第一个字符可能为单引号
+        let first = escaped.chars().next()?.len_utf8();
+        1 + first + escaped[first..].find('\'')?
+    } else {
+        let mut chars = inner.chars();
+        let first = chars.next()?;
+        if chars.next()? != '\'' {
+            return None;
+        }
+        first.len_utf8()
+    };
+    // 开头与结尾的单引号
+    Some(len + 2)
+}
+
+/// 逐块统计文本内容：按换行符拆分为行后逐行统计，仅保留尚未结束的一行，内存占用与内容长度无关
+/// 统计结果与对完整内容分别调用 `count_lines`、`detect_line_ending`、`classify_lines`、`max_line_length`、
+/// `count_functions`、`count_words`、`count_markers` 与 `estimate_tokens` 一致；
+/// 不是合法 UTF-8 的行以替换字符代替无法解码的字节后再统计
+#[derive(Debug, Default)]
+pub(crate) struct TextCounter<'a> {
+    /// 内容的语言
+    language: Option<Language>,
+    /// 需要统计的代码标记
+    marker_names: &'a [String],
+    /// 代码、注释与空白行的划分
+    classifier: LineClassifier,
+    /// 尚未结束的一行
+    pending: Vec<u8>,
+    /// 是否出现以 `\n` 结尾的行
+    lf: bool,
+    /// 是否出现以 `\r\n` 结尾的行
+    crlf: bool,
+    /// 文本行数
+    pub(crate) lines: u64,
+    /// 最长一行的字符数
+    pub(crate) max_line_length: u64,
+    /// 函数数量
+    pub(crate) functions: u64,
+    /// 词数
+    pub(crate) words: u64,
+    /// 字符数，包括换行符
+    pub(crate) chars: u64,
+    /// 各代码标记出现的次数
+    pub(crate) markers: HashMap<String, u64>,
+    /// 是否存在无法以 UTF-8 解码的字节
+    pub(crate) lossy: bool,
+}
+
+impl<'a> TextCounter<'a> {
+    /// 创建指定语言的统计器，同时统计 `markers` 中的代码标记
+    pub(crate) fn new(language: Option<Language>, markers: &'a [String]) -> Self {
+        TextCounter {
+            language,
+            marker_names: markers,
+            classifier: LineClassifier::new(language),
+            ..Default::default()
+        }
+    }
+
+    /// 统计一块内容，块的边界可以位于行或多字节字符的中间
+    pub(crate) fn write(&mut self, mut chunk: &[u8]) {
+        while let Some(index) = chunk.iter().position(|byte| *byte == b'\n') {
+            if self.pending.is_empty() {
+                self.push_line(&chunk[..index], true);
+            } else {
+                let mut line = std::mem::take(&mut self.pending);
+                line.extend_from_slice(&chunk[..index]);
+                self.push_line(&line, true);
+                // 保留已分配的缓冲区
+                line.clear();
+                self.pending = line;
+            }
+            chunk = &chunk[index + 1..];
+        }
+        self.pending.extend_from_slice(chunk);
+    }
+
+    /// 统计末尾没有换行符的最后一行
+    pub(crate) fn finish(&mut self) {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.push_line(&line, false);
+        }
+    }
+
+    /// 代码、注释与空白行的数量
+    pub(crate) fn line_stats(&self) -> LineStats {
+        self.classifier.stats
+    }
+
+    /// 换行符风格，没有换行符时为空
+    pub(crate) fn line_ending(&self) -> Option<LineEnding> {
+        match (self.lf, self.crlf) {
+            (true, true) => Some(LineEnding::Mixed),
+            (true, _) => Some(LineEnding::Lf),
+            (_, true) => Some(LineEnding::Crlf),
+            _ => None,
+        }
+    }
+
+    /// 估算的 token 数量，与 `estimate_tokens` 的规则一致
+    pub(crate) fn tokens(&self) -> u64 {
+        self.chars.div_ceil(4)
+    }
+
+    /// 统计一行内容，`line` 不包含换行符
+    /// - terminated：该行是否以换行符结尾
+    fn push_line(&mut self, line: &[u8], terminated: bool) {
+        self.lines += 1;
+        if terminated {
+            if line.ends_with(b"\r") {
+                self.crlf = true;
+            } else {
+                self.lf = true;
+            }
+        }
+        let text = String::from_utf8_lossy(line);
+        self.lossy |= matches!(text, Cow::Owned(_));
+        self.chars += text.chars().count() as u64 + terminated as u64;
+        self.words += count_words(&text);
+        for (marker, count) in count_markers(&text, self.marker_names) {
+            *self.markers.entry(marker).or_default() += count;
+        }
+        // 与 `str::lines` 一致，仅去除换行符之前的 `\r`
+        let line = if terminated {
+            text.strip_suffix('\r').unwrap_or(&text)
+        } else {
+            &text
+        };
+        self.max_line_length = self.max_line_length.max(line.chars().count() as u64);
+        self.functions += count_functions(line, self.language);
+        self.classifier.push(line);
+    }
 }
 
 // --------------------- 单元测试 ---------------------
 
 #[cfg(test)]
 mod tests {
-    use crate::tree::node::count::get_file_count;
+    use crate::tree::language::Language;
+    use crate::tree::node::count::{
+        READ_BUFFER_LEN, SNIFF_LEN, TextCounter, classify_lines, count_functions, count_lines,
+        count_markers, count_words, detect_line_ending, estimate_tokens, get_file_count, is_binary,
+        max_line_length,
+    };
+    use std::io::{Error, ErrorKind, Read};
+
+    /// 每次最多返回若干字节、首次读取被中断的读取源，用于检查跨越缓冲区边界的统计
+    struct Trickle<'a> {
+        contents: &'a [u8],
+        chunk: usize,
+        interrupted: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if !self.interrupted {
+                self.interrupted = true;
+                return Err(Error::from(ErrorKind::Interrupted));
+            }
+            let len = self.chunk.min(buf.len()).min(self.contents.len());
+            buf[..len].copy_from_slice(&self.contents[..len]);
+            self.contents = &self.contents[len..];
+            Ok(len)
+        }
+    }
 
     #[test]
     fn test_get_line_count_1() {
@@ -28,4 +585,123 @@ mod tests {
         let count = get_file_count(path).unwrap();
         assert_eq!(count, 4);
     }
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines("a\nb\n\nc".as_bytes()).unwrap(), 4);
+        assert_eq!(count_lines("".as_bytes()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_count_lines_streaming() {
+        // 换行符恰好位于缓冲区末尾，其后为不完整的最后一行
+        let mut contents = vec![b'a'; READ_BUFFER_LEN - 1];
+        contents.extend_from_slice(b"\n\xe9tail");
+        assert_eq!(count_lines(contents.as_slice()).unwrap(), 2);
+        contents.truncate(READ_BUFFER_LEN);
+        assert_eq!(count_lines(contents.as_slice()).unwrap(), 1);
+        let trickle = Trickle {
+            contents: b"a\r\nb\n\nc",
+            chunk: 3,
+            interrupted: false,
+        };
+        assert_eq!(count_lines(trickle).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_classify_lines() {
+        let contents =
+            "//! doc\n/* block\n\n still */\nlet a = 1; /* trailing\n*/\n\nlet b = 2; // note\n";
+        let stats = classify_lines(contents, Some(Language::Rust));
+        assert_eq!(stats.comments, 4);
+        assert_eq!(stats.blanks, 2);
+        assert_eq!(stats.code, 2);
+        assert_eq!(stats.total(), count_lines(contents.as_bytes()).unwrap());
+
+        let python = "#!/usr/bin/env python3\n# comment\nprint(1)\n";
+        let stats = classify_lines(python, Some(Language::Python));
+        assert_eq!((stats.code, stats.comments, stats.blanks), (1, 2, 0));
+
+        // 无法识别语言时，除空白行外均为代码行
+        let stats = classify_lines("// a\n\nb\n", None);
+        assert_eq!((stats.code, stats.comments, stats.blanks), (2, 0, 1));
+    }
+
+    #[test]
+    fn test_classify_literals() {
+        // 字符串与字符字面量中的注释标记不生效，生命周期不视为字符字面量
+        let rust = "let a = \"/*\";\nlet b = '\"'; // \"\nfn f<'a>(s: &'a str) {} /* x */\nlet c = '\\''; /*\n*/\n";
+        let stats = classify_lines(rust, Some(Language::Rust));
+        assert_eq!((stats.code, stats.comments, stats.blanks), (4, 1, 0));
+        let js = "const url = 'http://example.com';\nconst s = `/* not a comment`;\n// done\n";
+        let stats = classify_lines(js, Some(Language::JavaScript));
+        assert_eq!((stats.code, stats.comments), (2, 1));
+        let python = "print(\"# not a comment\")\nx = 'a\\'#'  # trailing\n";
+        let stats = classify_lines(python, Some(Language::Python));
+        assert_eq!((stats.code, stats.comments), (2, 0));
+    }
+
+    #[test]
+    fn test_classify_nested_comments() {
+        // Rust 的块注释可以嵌套，内层的结束标记不结束外层注释
+        let rust = "/* outer\n/* inner */\nstill comment\n*/\nfn main() {}\n";
+        let stats = classify_lines(rust, Some(Language::Rust));
+        assert_eq!((stats.code, stats.comments), (1, 4));
+        // C 的块注释不能嵌套，第一个结束标记即结束注释
+        let c = "/* outer\n/* inner */\nint a;\n";
+        let stats = classify_lines(c, Some(Language::C));
+        assert_eq!((stats.code, stats.comments), (1, 2));
+        // 注释结束后同一行中的代码
+        let stats = classify_lines("/* a */ int b;\n/*\n*/ int c;\n", Some(Language::C));
+        assert_eq!((stats.code, stats.comments), (2, 1));
+    }
+
+    #[test]
+    fn test_text_counter() {
+        // 按任意大小分块统计的结果与统计完整内容一致
+        let mut contents = "// TODO: 你好\r\nfn main() {\n    let s = \"/*\";\r\n}\n\n"
+            .as_bytes()
+            .to_vec();
+        contents.extend_from_slice(b"caf\xe9 TODO tail");
+        let text = String::from_utf8_lossy(&contents);
+        let markers = ["TODO".to_string()];
+        let language = Some(Language::Rust);
+        for size in 1..=7 {
+            let mut counter = TextCounter::new(language, &markers);
+            for chunk in contents.chunks(size) {
+                counter.write(chunk);
+            }
+            counter.finish();
+            assert_eq!(counter.lines, count_lines(contents.as_slice()).unwrap());
+            assert_eq!(counter.line_ending(), detect_line_ending(&contents));
+            assert_eq!(counter.line_stats(), classify_lines(&text, language));
+            assert_eq!(counter.max_line_length, max_line_length(&text));
+            assert_eq!(counter.functions, count_functions(&text, language));
+            assert_eq!(counter.words, count_words(&text));
+            assert_eq!(counter.chars, text.chars().count() as u64);
+            assert_eq!(counter.tokens(), estimate_tokens(&text));
+            assert_eq!(counter.markers, count_markers(&text, &markers));
+            assert!(counter.lossy);
+        }
+    }
+
+    #[test]
+    fn test_count_functions() {
+        let js = "export default async function a() {}\nconst b = () => 1;\n  function c() {}\n";
+        assert_eq!(count_functions(js, Some(Language::JavaScript)), 2);
+        let rust = "extern \"C\" fn a();\npub(super) const unsafe fn b() {}\nlet fn_name = 1;\n";
+        assert_eq!(count_functions(rust, Some(Language::Rust)), 2);
+        assert_eq!(count_functions("func main() {}\n", Some(Language::Go)), 1);
+        assert_eq!(count_functions("int main() {}\n", Some(Language::C)), 0);
+    }
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b""));
+        assert!(!is_binary(b"fn main() {}\n"));
+        // 开头一段末尾截断的多字节字符仍视为文本
+        let text = "中".repeat(SNIFF_LEN);
+        assert!(!is_binary(text.as_bytes()));
+        assert!(is_binary(b"text\x00more"));
+    }
 }
@@ -1,6 +1,8 @@
 //! # 节点文件特征相关
 //! - `get_file_size` 获取文件占用磁盘的大小
+//! - `get_file_suffix` 获取文件的后缀
 use std::io::Error;
+use std::path::Path;
 
 // --------------------- 文件相关 ---------------------
 // TODO: 当前文件和目录大小计算分开，可以优化为先计算文件大小再计算目录大小
@@ -11,6 +13,24 @@ pub fn get_file_size(path: &str) -> Result<u64, Error> {
     Ok(std::fs::metadata(path)?.len())
 }
 
+/// 获取文件后缀，不包含 `.`，并统一转换为小写
+/// 没有后缀的文件（包括 `.gitignore` 这类以 `.` 开头的文件）返回空
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::file::get_file_suffix;
+///
+/// assert_eq!(get_file_suffix("./src/lib.rs"), Some("rs".to_string()));
+/// assert_eq!(get_file_suffix("./README.MD"), Some("md".to_string()));
+/// assert_eq!(get_file_suffix("./.gitignore"), None);
+/// ```
+pub fn get_file_suffix<P: AsRef<Path>>(path: P) -> Option<String> {
+    path.as_ref()
+        .extension()
+        .map(|suffix| suffix.to_string_lossy().to_lowercase())
+}
+
 // --------------------- 单元测试 ---------------------
 
 #[cfg(test)]
@@ -0,0 +1,173 @@
+//! # 生成代码与第三方代码识别
+//! 识别看起来由工具生成或从第三方复制而来的文件与目录，使统计时可以将其与项目自身的代码区分开：
+//! - 生成的文件：文件名符合常见的生成文件模式（如 `*_pb2.py`、`*.pb.go`、`*.min.js`、锁文件），
+//!   开头包含生成标记（如 `@generated`、`Code generated ... DO NOT EDIT`），或平均行长度过长（如压缩后的代码）
+//! - 第三方目录：目录名为常见的依赖目录（如 `vendor`、`node_modules`、`third_party`），其下所有文件均视为第三方代码
+//!
+//! 第三方目录根据项目树中的目录名识别，项目根路径之外的祖先目录不参与判断；
+//! 识别基于启发式规则，可能存在误判。
+use std::fmt::Display;
+use std::path::Path;
+
+/// 第三方依赖目录名
+pub const VENDOR_DIRS: [&str; 7] = [
+    "vendor",
+    "node_modules",
+    "third_party",
+    "third-party",
+    "bower_components",
+    "Pods",
+    "site-packages",
+];
+
+/// 生成文件的文件名后缀
+const GENERATED_SUFFIXES: [&str; 12] = [
+    "_pb2.py",
+    "_pb2_grpc.py",
+    "_pb2.pyi",
+    ".pb.go",
+    ".pb.cc",
+    ".pb.h",
+    ".g.dart",
+    ".freezed.dart",
+    ".designer.cs",
+    ".min.js",
+    ".min.css",
+    ".js.map",
+];
+
+/// 由包管理器生成的锁文件
+const LOCK_FILES: [&str; 7] = [
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "Gemfile.lock",
+    "go.sum",
+];
+
+/// 文件开头的生成标记，区分大小写
+const GENERATED_MARKERS: [&str; 5] = [
+    "@generated",
+    "DO NOT EDIT",
+    "<auto-generated",
+    "auto-generated",
+    "Auto-generated",
+];
+
+/// 检查生成标记时读取的开头字节数
+const HEADER_LEN: usize = 1024;
+
+/// 平均行长度超过该值（字节）时视为生成的文件
+const MAX_AVG_LINE_LEN: u64 = 500;
+
+/// 按平均行长度判断时要求的最小文件大小，避免将较短的单行文件视为生成的文件
+const MIN_MINIFIED_SIZE: u64 = 4096;
+
+/// 代码来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum CodeOrigin {
+    /// 由工具生成
+    Generated,
+    /// 来自第三方依赖
+    Vendored,
+}
+
+impl CodeOrigin {
+    /// 来源名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodeOrigin::Generated => "generated",
+            CodeOrigin::Vendored => "vendored",
+        }
+    }
+}
+
+impl Display for CodeOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// 目录名是否为第三方依赖目录，区分大小写
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::origin::is_vendor_dir;
+///
+/// assert!(is_vendor_dir("node_modules"));
+/// assert!(!is_vendor_dir("src"));
+/// ```
+pub fn is_vendor_dir(name: &str) -> bool {
+    VENDOR_DIRS.contains(&name)
+}
+
+/// 根据文件名、开头的内容、大小与行数判断文件是否为生成的文件
+/// - path：文件路径，根据文件名判断
+/// - header：文件开头的内容，仅检查开头的一段，无需读取完整的文件；未读取内容时传入空切片
+/// - size：文件大小（字节），与行数一起计算平均行长度
+/// - lines：文本行数，二进制文件或未统计时传入 0
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::origin::is_generated;
+///
+/// assert!(is_generated("./api/service_pb2.py", b"", 0, 0));
+/// let header = b"// Code generated by stringer. DO NOT EDIT.\n";
+/// assert!(is_generated("./gen.go", header, header.len() as u64, 1));
+/// assert!(!is_generated("./main.go", b"package main\n", 13, 1));
+/// ```
+pub fn is_generated<P: AsRef<Path>>(path: P, header: &[u8], size: u64, lines: u64) -> bool {
+    let name = path
+        .as_ref()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    if LOCK_FILES.contains(&name)
+        || GENERATED_SUFFIXES
+            .iter()
+            .any(|suffix| name.len() > suffix.len() && name.ends_with(suffix))
+    {
+        return true;
+    }
+    let header = String::from_utf8_lossy(&header[..header.len().min(HEADER_LEN)]);
+    if GENERATED_MARKERS
+        .iter()
+        .any(|marker| header.contains(marker))
+    {
+        return true;
+    }
+    lines > 0 && size >= MIN_MINIFIED_SIZE && size / lines > MAX_AVG_LINE_LEN
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_generated() {
+        assert!(is_generated("./Cargo.lock", b"", 0, 0));
+        assert!(is_generated("./dist/app.min.js", b"", 0, 0));
+        // 仅有后缀本身的文件名不视为生成的文件
+        assert!(!is_generated("./.min.js", b"", 0, 0));
+        let header = b"// <auto-generated>\n//     This code was generated by a tool.\n";
+        assert!(is_generated("./Model.cs", header, header.len() as u64, 2));
+        // 压缩后的单行代码，仅需开头的一段内容与大小
+        let minified = "var a=1;".repeat(1000);
+        assert!(is_generated(
+            "./bundle.js",
+            &minified.as_bytes()[..64],
+            8000,
+            1
+        ));
+        assert!(!is_generated("./short.js", b"var a=1;", 8, 1));
+        assert!(!is_generated("./blob.bin", minified.as_bytes(), 8000, 0));
+    }
+}
@@ -0,0 +1,102 @@
+//! # 属主信息
+//! 需要开启 `unix-metadata` 特性。
+//! 总结时记录每个文件的属主与属组（用户 ID、组 ID 及其名称），目录按属主汇总文件数量、大小与行数，
+//! 可用于多用户服务器的扫描，回答“哪些用户占用了多少磁盘”。
+//! 用户名与组名通过系统的用户数据库解析，解析结果会被缓存，非 Unix 平台不记录属主信息。
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// 文件的属主信息
+/// - uid：属主的用户 ID
+/// - gid：属组的组 ID
+/// - user：属主的用户名，无法解析时为空
+/// - group：属组的组名，无法解析时为空
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct Owner {
+    /// 属主的用户 ID
+    pub uid: u32,
+    /// 属组的组 ID
+    pub gid: u32,
+    /// 属主的用户名，无法解析时为空
+    pub user: Option<String>,
+    /// 属组的组名，无法解析时为空
+    pub group: Option<String>,
+}
+
+impl Owner {
+    /// 根据用户 ID 与组 ID 创建属主信息，并解析用户名与组名
+    pub fn resolve(uid: u32, gid: u32) -> Self {
+        Owner {
+            uid,
+            gid,
+            user: user_name(uid),
+            group: group_name(gid),
+        }
+    }
+}
+
+/// 解析用户 ID 对应的用户名，用户不存在或非 Unix 平台时为空
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::owner::user_name;
+///
+/// assert_eq!(user_name(0).as_deref(), Some("root"));
+/// ```
+pub fn user_name(uid: u32) -> Option<String> {
+    static USERS: OnceLock<Mutex<HashMap<u32, Option<String>>>> = OnceLock::new();
+    lookup(&USERS, uid, |uid| {
+        #[cfg(unix)]
+        return uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().into_owned());
+        #[cfg(not(unix))]
+        return None;
+    })
+}
+
+/// 解析组 ID 对应的组名，组不存在或非 Unix 平台时为空
+pub fn group_name(gid: u32) -> Option<String> {
+    static GROUPS: OnceLock<Mutex<HashMap<u32, Option<String>>>> = OnceLock::new();
+    lookup(&GROUPS, gid, |gid| {
+        #[cfg(unix)]
+        return uzers::get_group_by_gid(gid)
+            .map(|group| group.name().to_string_lossy().into_owned());
+        #[cfg(not(unix))]
+        return None;
+    })
+}
+
+/// 查询缓存，未命中时解析并写入缓存
+fn lookup(
+    cache: &OnceLock<Mutex<HashMap<u32, Option<String>>>>,
+    id: u32,
+    resolve: impl FnOnce(u32) -> Option<String>,
+) -> Option<String> {
+    let cache = cache.get_or_init(Default::default);
+    if let Some(name) = cache.lock().unwrap().get(&id) {
+        return name.clone();
+    }
+    let name = resolve(id);
+    cache.lock().unwrap().insert(id, name.clone());
+    name
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve() {
+        let owner = Owner::resolve(0, 0);
+        assert_eq!(owner.user.as_deref(), Some("root"));
+        assert!(owner.group.is_some());
+        // 不存在的用户无法解析名称，且结果会被缓存
+        assert_eq!(user_name(u32::MAX - 1), None);
+        assert_eq!(user_name(u32::MAX - 1), None);
+    }
+}
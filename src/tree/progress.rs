@@ -0,0 +1,156 @@
+//! # 进度报告
+//! 在构建、总结等耗时操作中向调用方报告进度，可用于驱动进度条等界面展示。
+//! 调用方可以实现 `ProgressReporter`，也可以直接传入闭包，闭包会收到对应的 `ProgressEvent`。
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 进度事件，用于闭包形式的进度回调
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent<'a> {
+    /// 进入目录，参数为目录路径
+    EnteredDir(&'a str),
+    /// 当前操作中已处理的文件总数
+    ProcessedFiles(u64),
+    /// 总结过程中已统计的文件字节总数
+    BytesSummarized(u64),
+}
+
+/// 进度报告接口
+/// 所有方法均有默认的空实现，按需实现即可：
+/// - entered_dir：进入目录
+/// - processed_files：已处理的文件总数（累计值）
+/// - bytes_summarized：已统计的文件字节总数（累计值）
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::progress::ProgressEvent;
+/// use arui_core::ProjectTree;
+///
+/// let mut tree = ProjectTree::new("test", "./src", None).with_progress(|event: ProgressEvent| {
+///     if let ProgressEvent::ProcessedFiles(n) = event {
+///         println!("processed {} files", n);
+///     }
+/// });
+/// tree.build().unwrap();
+/// tree.summarize().unwrap();
+/// ```
+pub trait ProgressReporter: Send + Sync {
+    /// 进入目录
+    fn entered_dir(&self, _path: &str) {}
+    /// 已处理的文件总数
+    fn processed_files(&self, _n: u64) {}
+    /// 已统计的文件字节总数
+    fn bytes_summarized(&self, _n: u64) {}
+}
+
+/// 为闭包实现进度报告，将各个回调转换为 `ProgressEvent`
+impl<F> ProgressReporter for F
+where
+    F: Fn(ProgressEvent) + Send + Sync,
+{
+    fn entered_dir(&self, path: &str) {
+        self(ProgressEvent::EnteredDir(path));
+    }
+
+    fn processed_files(&self, n: u64) {
+        self(ProgressEvent::ProcessedFiles(n));
+    }
+
+    fn bytes_summarized(&self, n: u64) {
+        self(ProgressEvent::BytesSummarized(n));
+    }
+}
+
+/// 进度追踪器，负责累计计数并转发给进度报告对象
+/// 未设置报告对象时仅累计计数；克隆得到的追踪器共享同一组计数，可在多个线程中同时使用
+#[derive(Default, Clone)]
+pub(crate) struct ProgressTracker {
+    reporter: Option<Arc<dyn ProgressReporter>>,
+    files: Arc<AtomicU64>,
+    bytes: Arc<AtomicU64>,
+}
+
+impl ProgressTracker {
+    /// 创建进度追踪器
+    pub(crate) fn new(reporter: Option<Arc<dyn ProgressReporter>>) -> Self {
+        ProgressTracker {
+            reporter,
+            ..Default::default()
+        }
+    }
+
+    /// 报告进入目录
+    pub(crate) fn enter_dir(&self, path: &str) {
+        if let Some(reporter) = &self.reporter {
+            reporter.entered_dir(path);
+        }
+    }
+
+    /// 报告处理完成一个文件
+    pub(crate) fn file_done(&self) {
+        let files = self.files.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(reporter) = &self.reporter {
+            reporter.processed_files(files);
+        }
+    }
+
+    /// 报告统计了 `n` 个字节
+    pub(crate) fn bytes_done(&self, n: u64) {
+        let bytes = self.bytes.fetch_add(n, Ordering::Relaxed) + n;
+        if let Some(reporter) = &self.reporter {
+            reporter.bytes_summarized(bytes);
+        }
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        dirs: Mutex<Vec<String>>,
+        files: Mutex<u64>,
+    }
+
+    impl ProgressReporter for Recorder {
+        fn entered_dir(&self, path: &str) {
+            self.dirs.lock().unwrap().push(path.to_string());
+        }
+
+        fn processed_files(&self, n: u64) {
+            *self.files.lock().unwrap() = n;
+        }
+    }
+
+    #[test]
+    fn test_tracker() {
+        let recorder = Arc::new(Recorder::default());
+        let tracker = ProgressTracker::new(Some(recorder.clone()));
+        tracker.enter_dir("./src");
+        tracker.file_done();
+        tracker.file_done();
+        tracker.bytes_done(10);
+        assert_eq!(*recorder.dirs.lock().unwrap(), vec!["./src".to_string()]);
+        assert_eq!(*recorder.files.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_closure_reporter() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let reporter = move |event: ProgressEvent| {
+            if let ProgressEvent::BytesSummarized(n) = event {
+                sink.lock().unwrap().push(n);
+            }
+        };
+        let tracker = ProgressTracker::new(Some(Arc::new(reporter)));
+        tracker.bytes_done(3);
+        tracker.bytes_done(4);
+        assert_eq!(*events.lock().unwrap(), vec![3, 7]);
+    }
+}
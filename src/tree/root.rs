@@ -1,14 +1,73 @@
 //! # 项目树入口
 //! 用于初始化操作和启动目录树分析
+use crate::errors::AruiError;
+use crate::fs::{DiskFs, FileSystem, MemoryFs};
+use crate::tree::arena::ArenaTree;
+use crate::tree::builder::{TreeBuilder, assemble};
+use crate::tree::cache::SummaryCache;
+use crate::tree::cancel::CancelToken;
+use crate::tree::checkpoint::Checkpoint;
+#[cfg(feature = "hash")]
+use crate::tree::checksum;
+use crate::tree::cloc::{self, TableStyle};
 use crate::tree::config::ProjectConfig;
+use crate::tree::csv;
+use crate::tree::diff::ChangeSet;
+use crate::tree::du;
+#[cfg(feature = "hash")]
+use crate::tree::duplicate::{DuplicateGroup, find_duplicates};
+use crate::tree::export::{self, TreeExporter};
+#[cfg(feature = "json")]
+use crate::tree::flare::{FlareMetric, FlareNode};
+use crate::tree::freshness::Freshness;
+#[cfg(feature = "git")]
+use crate::tree::git;
+use crate::tree::health::{HealthReport, HealthRule};
+#[cfg(feature = "index")]
+use crate::tree::index;
+#[cfg(feature = "json")]
+use crate::tree::json::JsonOptions;
+use crate::tree::language::Language;
+#[cfg(feature = "json")]
+use crate::tree::ndjson;
 use crate::tree::node::TreeNode;
-use crate::utils::{check_path, generate_id};
-use std::fs;
-use std::io::Result;
+#[cfg(feature = "parquet")]
+use crate::tree::parquet;
+use crate::tree::progress::ProgressReporter;
+#[cfg(any(feature = "json", feature = "yaml"))]
+use crate::tree::schema::{ExportRef, SchemaHeader};
+#[cfg(feature = "sqlite")]
+use crate::tree::sqlite;
+use crate::tree::stats::BuildStats;
+use crate::tree::stream::DirRecord;
+use crate::tree::summarizer::Summarizer;
+use crate::tree::summary::{ExtStats, NodeSummary};
+use crate::tree::warning::Warning;
+use crate::tree::xml;
+use crate::utils::generate_id;
+use std::io::BufRead;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+type Result<T> = std::result::Result<T, AruiError>;
+
+/// 项目树缓存文件的文件头标识
+#[cfg(feature = "msgpack")]
+const TREE_CACHE_MAGIC: &[u8; 4] = b"ARUI";
+
+/// 项目树缓存文件的格式版本，项目树的结构发生不兼容的变化时递增
+#[cfg(feature = "msgpack")]
+const TREE_CACHE_VERSION: u32 = 4;
 
 /// 项目目录树根节点
 /// 用于初始化操作和启动目录树分析
+///
+/// 开启 `serde` 特性后可序列化与反序列化，用于保存分析结果并在之后重新加载；
+/// 文件系统、进度报告、取消令牌、检查点与总结缓存属于运行时状态，不参与序列化，
+/// 反序列化后文件系统为磁盘，其余为空
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ProjectTree {
     /// 项目 ID，自动生成
     pub id: String,
@@ -20,6 +79,31 @@ pub struct ProjectTree {
     pub root: Option<TreeNode>,
     /// 项目树迭代配置，用于过滤、仅包含等等
     pub config: Option<ProjectConfig>,
+    /// 文件系统，构建与总结均通过它访问文件，默认为磁盘
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_fs"))]
+    pub fs: Arc<dyn FileSystem>,
+    /// 进度报告，在构建和总结过程中接收进度事件
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<Arc<dyn ProgressReporter>>,
+    /// 取消令牌，触发后构建和总结操作会尽快停止
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancel: Option<CancelToken>,
+    /// 构建与总结过程中记录的可恢复问题，如无权限的目录、损坏的符号链接、无法读取或超出大小上限的文件
+    /// 每次 `build`、`analyze` 时重置，`summarize` 时追加
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub warnings: Vec<Warning>,
+    /// 构建检查点，设置后 `build` 会定期写入构建进度，可通过 `resume` 从中断处继续
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub checkpoint: Option<Checkpoint>,
+    /// 总结缓存，可由多个项目树共享，设置后总结时复用未发生变化的文件的总结信息
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cache: Option<Arc<SummaryCache>>,
+}
+
+/// 反序列化时使用的默认文件系统
+#[cfg(feature = "serde")]
+fn default_fs() -> Arc<dyn FileSystem> {
+    Arc::new(DiskFs)
 }
 
 /// 初始化项目及构建属性
@@ -56,10 +140,177 @@ impl ProjectTree {
             path: path.into(),
             root: None,
             config,
+            fs: Arc::new(DiskFs),
+            progress: None,
+            cancel: None,
+            warnings: Vec::new(),
+            checkpoint: None,
+            cache: None,
+        }
+    }
+
+    /// 设置文件系统，例如使用 `MemoryFs` 分析虚拟的目录树
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new().add_file("project/lib.rs", "pub mod tree;\n");
+    /// let project = ProjectTree::new("test", "project", None).with_fs(fs);
+    /// assert!(project.is_valid());
+    /// ```
+    pub fn with_fs<F>(mut self, fs: F) -> Self
+    where
+        F: FileSystem + 'static,
+    {
+        self.fs = Arc::new(fs);
+        self
+    }
+
+    /// 设置进度报告，可以是实现了 `ProgressReporter` 的对象，也可以是接收 `ProgressEvent` 的闭包
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::progress::ProgressEvent;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let mut project = ProjectTree::new("test", "./src", None).with_progress(|event: ProgressEvent| {
+    ///     println!("{:?}", event);
+    /// });
+    /// project.build().unwrap();
+    /// ```
+    pub fn with_progress<P>(mut self, progress: P) -> Self
+    where
+        P: ProgressReporter + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// 设置取消令牌，令牌被触发后 `build`、`summarize` 等操作会尽快停止并返回 `AruiError::Cancelled`，
+    /// 此时项目树中保留已经完成的部分结果
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::errors::AruiError;
+    /// use arui_core::tree::cancel::CancelToken;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let token = CancelToken::new();
+    /// let mut project = ProjectTree::new("test", "./src", None).with_cancel_token(token.clone());
+    /// token.cancel();
+    /// assert!(matches!(project.build(), Err(AruiError::Cancelled)));
+    /// ```
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// 设置总结缓存，缓存可在多个项目树之间共享，详见 `SummaryCache`
+    pub fn with_cache(mut self, cache: Arc<SummaryCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 根据路径列表创建项目树
+    /// - name：项目别名
+    /// - root：根路径
+    /// - paths：文件路径列表，相对路径基于根路径解析，绝对路径必须位于根路径之下
+    ///
+    /// 与 `build` 不同，该操作不遍历文件系统，而是直接按照给定的文件列表组装项目树结构（如 `git ls-files` 的输出），
+    /// 中间目录自动生成。组装完成后可通过 `summarize` 获取总结信息，此时仅统计列表中的文件。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// use std::path::PathBuf;
+    ///
+    /// let paths = vec![PathBuf::from("lib.rs"), PathBuf::from("tree/root.rs")];
+    /// let mut project = ProjectTree::from_paths("test", "./src", paths).unwrap();
+    /// project.summarize().unwrap();
+    /// ```
+    pub fn from_paths<S, I, P>(name: S, root: I, paths: P) -> Result<Self>
+    where
+        S: Into<String>,
+        I: Into<String>,
+        P: IntoIterator<Item = PathBuf>,
+    {
+        let mut tree = ProjectTree::new(name, root, None);
+        tree.root = Some(assemble(Path::new(&tree.path), paths)?);
+        Ok(tree)
+    }
+
+    /// 根据逐行列出文件路径的输入创建项目树，并获取总结信息
+    /// - name：项目别名
+    /// - root：根路径
+    /// - reader：每行一个文件路径的输入，如 `find -type f`、`fd --type file`、`git ls-files` 的输出
+    ///
+    /// 空行会被忽略，行尾的 `\r` 会被去除，路径的解析规则与 `from_paths` 一致。
+    /// 该操作不遍历文件系统，仅读取列表中各个文件的内容以获取总结信息。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// use std::io::Cursor;
+    ///
+    /// let input = Cursor::new("lib.rs\ntree/root.rs\n");
+    /// let project = ProjectTree::from_path_reader("test", "./src", input).unwrap();
+    /// assert!(project.root.as_ref().unwrap().summary.count > 0);
+    /// ```
+    pub fn from_path_reader<S, I, R>(name: S, root: I, reader: R) -> Result<Self>
+    where
+        S: Into<String>,
+        I: Into<String>,
+        R: BufRead,
+    {
+        let mut paths = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if !line.is_empty() {
+                paths.push(PathBuf::from(line));
+            }
         }
+        let mut tree = ProjectTree::from_paths(name, root, paths)?;
+        tree.summarize()?;
+        Ok(tree)
+    }
+
+    /// 直接从归档文件种植一棵项目树，无需解压到磁盘
+    /// - path：归档文件路径，支持 `.zip`（需开启 `zip` 特性）与 `.tar`、`.tar.gz`、`.tgz`（需开启 `tar` 特性）
+    ///
+    /// 归档内容会被读入内存文件系统，并挂载在以归档文件名命名的根路径之下，项目别名同样为归档文件名。
+    /// 该操作会完成构建与总结，得到的项目树结构与总结信息和解压后直接构建的结果一致。
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use arui_core::tree::root::ProjectTree;
+    /// let project = ProjectTree::from_archive("dist.tar.gz").unwrap();
+    /// assert_eq!(project.path, "dist.tar.gz");
+    /// ```
+    pub fn from_archive<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let fs = MemoryFs::from_archive(path.as_ref())?;
+        let name = path
+            .as_ref()
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut tree = ProjectTree::new(name.clone(), name, None).with_fs(fs);
+        tree.analyze()?;
+        Ok(tree)
     }
 
-    /// 动态检查节点路径是否合法
+    /// 动态检查节点路径是否合法，即根路径是否存在于项目树的文件系统中
     ///
     /// # Example
     ///
@@ -70,7 +321,15 @@ impl ProjectTree {
     /// println!("{}", can_build); // should be false
     /// ```
     pub fn is_valid(&self) -> bool {
-        check_path(&self.path).is_ok()
+        self.fs.exists(Path::new(&self.path))
+    }
+
+    /// 检查根路径，不存在时返回错误
+    fn check_root(&self) -> Result<()> {
+        if !self.is_valid() {
+            return Err(AruiError::InvalidPath(self.path.clone()));
+        }
+        Ok(())
     }
 
     /// "种植"一棵项目树
@@ -82,24 +341,57 @@ impl ProjectTree {
     /// - build： 从 `path` 启动，遍历并生成项目树
     /// - summary： 从 `root` 启动，遍历并生成项目树各节点的总结信息
     ///
+    /// 以上两步通过 `analyze` 在一次遍历中完成，任意一步出错时返回错误
+    ///
     /// # Example
     ///
     /// ```rust
     /// use arui_core::tree::root::ProjectTree;
     /// const NAME: &str = "test";
     /// const PATH: &str = ".";
-    /// // 如果只用 `plant`，后续也不需要更新项目树，则可不使用 `mut`
-    /// let project = ProjectTree::plant(NAME, PATH, None);
+    /// // 如果只用 `try_plant`，后续也不需要更新项目树，则可不使用 `mut`
+    /// let project = ProjectTree::try_plant(NAME, PATH, None).unwrap();
+    /// assert!(ProjectTree::try_plant(NAME, "./not_exist", None).is_err());
     /// ```
-    pub fn plant<S, I>(name: S, path: I, config: Option<ProjectConfig>) -> Self
+    pub fn try_plant<S, I>(name: S, path: I, config: Option<ProjectConfig>) -> Result<Self>
     where
         S: Into<String>,
         I: Into<String>,
     {
         let mut tree = ProjectTree::new(name, path, config);
-        tree.build().expect("project build panic");
-        tree.summarize().expect("project summarize panic");
-        tree
+        tree.analyze()?;
+        Ok(tree)
+    }
+
+    /// "种植"一棵项目树，与 `try_plant` 相同，但出错时直接 panic
+    #[deprecated(note = "panics on any IO error, use `ProjectTree::try_plant` instead")]
+    pub fn plant<S, I>(name: S, path: I, config: Option<ProjectConfig>) -> Self
+    where
+        S: Into<String>,
+        I: Into<String>,
+    {
+        ProjectTree::try_plant(name, path, config).expect("project analyze panic")
+    }
+
+    /// 设置构建检查点
+    /// 设置后 `build` 总是采用广度优先遍历，每加载一定数量的目录将部分项目树与待加载目录写入检查点文件，
+    /// 构建完成后删除检查点文件，被取消时写入最后一次检查点。中断后可通过 `resume` 从检查点继续构建
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::checkpoint::Checkpoint;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let path = std::env::temp_dir().join("arui-doc.checkpoint");
+    /// let checkpoint = Checkpoint::new(path.to_string_lossy()).with_interval(10);
+    /// let mut project = ProjectTree::new("test", "./src", None).with_checkpoint(checkpoint);
+    /// project.build().unwrap();
+    /// assert!(!project.checkpoint.as_ref().unwrap().exists());
+    /// ```
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
     }
 
     // ------------------------- 遍历构建节点 -------------------------
@@ -109,56 +401,241 @@ impl ProjectTree {
     /// - path：节点对应文件/目录的路径
     /// - is_dir：是否是文件夹
     /// - children：子节点（is_dir为true时有值）
-    /// 其中并不包含 `summary` 字段的获取，需要单独调用 `summarize` 方法来获取
+    ///
+    /// 其中并不包含 `summary` 字段的获取，需要单独调用 `summarize` 方法来获取。
+    /// 若配置了 `include`，则仅构建根路径下的这些子路径，并保留其相对层级。
+    /// 若配置了 `max_depth`，则仅构建到对应深度，更深的目录节点不加载子节点，可通过 `expand` 按需展开。
+    /// 若构建过程中被取消，`root` 中保留已构建的部分项目树，并返回 `AruiError::Cancelled`。
+    /// 若配置开启了 `tolerant`，无法读取的路径会被跳过并记录到 `warnings` 中。
+    ///
+    /// 构建成功后返回本次扫描的统计信息 `BuildStats`。
     ///
     /// # Example
     ///
     /// ```rust
     /// use arui_core::tree::root::ProjectTree;
     /// let mut project = ProjectTree::new("test", "./src", None);
-    /// project.build().unwrap();
+    /// let stats = project.build().unwrap();
+    /// println!("{}", stats);
     /// ```
-    pub fn build(&mut self) -> Result<()> {
+    pub fn build(&mut self) -> Result<BuildStats> {
+        let start = Instant::now();
         // 如果路径不合法，返回错误
-        // TODO: 修改为自定义错误
-        if !self.is_valid() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "invalid path",
-            ));
-        }
+        self.check_root()?;
         let root_path = PathBuf::from(&self.path);
         // 尝试遍历构建项目树，生成各个节点
-        self.root = Some(Self::build_tree_node(&root_path)?);
-        Ok(())
+        let mut builder = self.builder().with_checkpoint(self.checkpoint.clone());
+        self.warnings.clear();
+        let root = self.build_root(&mut builder, &root_path);
+        self.warnings = builder.take_warnings();
+        self.root = Some(root?);
+        if builder.is_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        Ok(BuildStats {
+            elapsed: start.elapsed(),
+            ..builder.take_stats()
+        })
     }
 
-    /// 递归构建树节点
-    fn build_tree_node(path: &Path) -> Result<TreeNode> {
-        // 获取文件元数据
-        // TODO: 自定义错误处理
-        let metadata = fs::metadata(path)?;
-        let is_dir = metadata.is_dir();
-        // 创建节点
-        let mut node = TreeNode::new(path.to_string_lossy().into_owned(), is_dir);
-        // 如果是目录，递归构建该节点的子节点
-        // TODO：自定义错误
-        if is_dir {
-            let mut children = Vec::new();
-            for entry in fs::read_dir(path)? {
-                let entry = entry?;
-                children.push(Self::build_tree_node(&entry.path())?);
+    /// 从根路径启动构建，配置了 `include` 时仅构建其中的子路径
+    fn build_root(&self, builder: &mut TreeBuilder, root_path: &Path) -> Result<TreeNode> {
+        match &self.config {
+            Some(config) if !config.include.is_empty() => {
+                builder.build_included(root_path, &config.include)
             }
-            node.children = Some(children);
+            _ => builder.build(root_path),
+        }
+    }
+
+    /// 从检查点恢复构建
+    /// 读取检查点中已构建的部分项目树，继续加载其中待加载的目录，行为与 `build` 一致；
+    /// 检查点文件不存在时从头开始构建。检查点不保存总结信息，恢复完成后需调用 `summarize`。
+    ///
+    /// 返回的 `BuildStats` 仅包含本次恢复过程的统计信息。
+    pub fn resume(&mut self) -> Result<BuildStats> {
+        let Some(checkpoint) = self.checkpoint.clone() else {
+            return self.build();
+        };
+        if !checkpoint.exists() {
+            return self.build();
+        }
+        let start = Instant::now();
+        self.check_root()?;
+        let state = checkpoint.load()?;
+        let mut builder = self.builder().with_checkpoint(Some(checkpoint));
+        self.warnings.clear();
+        let root = builder.resume(state);
+        self.warnings = builder.take_warnings();
+        self.root = Some(root?);
+        if builder.is_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        Ok(BuildStats {
+            elapsed: start.elapsed(),
+            ..builder.take_stats()
+        })
+    }
+
+    /// 根据项目配置创建构建器
+    fn builder(&self) -> TreeBuilder {
+        TreeBuilder::new(self.config.as_ref())
+            .with_fs(self.fs.clone())
+            .with_progress(self.progress.clone())
+            .with_cancel(self.cancel.clone())
+            .with_sandbox_root(self.sandbox_root())
+            .with_excludes(self.excludes())
+    }
+
+    /// 需要排除的路径，相对路径基于项目根路径解析
+    fn excludes(&self) -> Vec<PathBuf> {
+        let Some(config) = &self.config else {
+            return Vec::new();
+        };
+        config
+            .exclude
+            .iter()
+            .map(|exclude| self.resolve_path(exclude.as_str()))
+            .collect()
+    }
+
+    /// 开启沙箱模式时返回项目根路径
+    fn sandbox_root(&self) -> Option<PathBuf> {
+        self.config
+            .as_ref()
+            .is_some_and(|config| config.sandboxed)
+            .then(|| PathBuf::from(&self.path))
+    }
+
+    /// 构建项目树并同时生成总结信息
+    /// 与依次调用 `build`、`summarize` 的结果一致，但仅遍历一次文件系统：
+    /// 文件的大小、行数在遍历时直接获取，目录的总结信息在构建结束后于内存中汇总，不再重复访问各个文件。
+    /// 返回的 `BuildStats` 同时包含构建与总结的统计信息。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::new("test", "./src", None);
+    /// let stats = project.analyze().unwrap();
+    /// assert!(stats.bytes_read > 0);
+    /// assert!(project.root.as_ref().unwrap().summary.count > 0);
+    /// ```
+    pub fn analyze(&mut self) -> Result<BuildStats> {
+        let start = Instant::now();
+        // 如果路径不合法，返回错误
+        self.check_root()?;
+        let root_path = PathBuf::from(&self.path);
+        let mut builder = self.builder().with_summarizer(self.summarizer());
+        self.warnings.clear();
+        let root = self.build_root(&mut builder, &root_path);
+        self.warnings = builder.take_warnings();
+        self.root = Some(root?);
+        if builder.is_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        Ok(BuildStats {
+            elapsed: start.elapsed(),
+            ..builder.take_stats()
+        })
+    }
+
+    /// 重新扫描文件系统，将项目树更新为最新状态，并返回本次更新的变更集合
+    /// 若项目树已生成总结信息，则同时更新总结信息，此时 `ChangeSet.modified` 中包含大小或行数发生变化的文件。
+    /// 重新扫描失败（包括被取消）时保留原有的项目树。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let changes = project.refresh().unwrap();
+    /// assert!(changes.is_empty());
+    /// ```
+    pub fn refresh(&mut self) -> Result<ChangeSet> {
+        let old = self.root.take().ok_or(AruiError::NotBuilt)?;
+        let result = if old.summary.summarized_at.is_some() {
+            self.analyze()
+        } else {
+            self.build()
+        };
+        if let Err(error) = result {
+            self.root = Some(old);
+            return Err(error);
         }
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        Ok(ChangeSet::diff(&old, root))
+    }
+
+    /// 检查项目树是否与文件系统的当前状态一致，并返回过期的子树，不会修改项目树
+    /// 仅读取元数据，比较各文件的大小与修改时间以及各目录的子节点，过滤规则（如 `exclude`）与构建时一致，
+    /// 适用于从缓存恢复项目树后，仅重建过期的子树（见 `refresh_stale`）而非重新扫描整个项目；各项判断规则见 `Freshness`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let freshness = project.validate_against_fs().unwrap();
+    /// assert!(freshness.is_fresh());
+    /// ```
+    pub fn validate_against_fs(&self) -> Result<Freshness> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let mut stale = Vec::new();
+        let checked = self.builder().collect_stale(root, &mut stale);
+        stale.sort();
+        Ok(Freshness { stale, checked })
+    }
 
-        Ok(node)
+    /// 流式分析项目，不保留项目树
+    /// 深度优先遍历根路径，每个目录完成汇总后将其 `DirRecord` 交给 `emit` 处理，并立即释放该目录的子节点，
+    /// 适用于文件数量巨大、无法将完整项目树保存在内存中的场景。子目录的记录总是先于父目录生成，根目录的记录最后生成。
+    /// 该操作不会修改 `root`，返回的 `BuildStats` 同时包含构建与总结的统计信息。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::new("test", "./src", None);
+    /// let mut total = 0;
+    /// project
+    ///     .stream(|record| {
+    ///         if record.depth == 0 {
+    ///             total = record.summary.count;
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// assert!(total > 0);
+    /// assert!(project.root.is_none());
+    /// ```
+    pub fn stream<F>(&mut self, mut emit: F) -> Result<BuildStats>
+    where
+        F: FnMut(DirRecord),
+    {
+        let start = Instant::now();
+        self.check_root()?;
+        let root_path = PathBuf::from(&self.path);
+        let mut builder = self.builder().with_summarizer(self.summarizer());
+        self.warnings.clear();
+        let result = builder.stream(&root_path, &mut emit);
+        self.warnings = builder.take_warnings();
+        result?;
+        if builder.is_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        Ok(BuildStats {
+            elapsed: start.elapsed(),
+            ..builder.take_stats()
+        })
     }
 
     // ------------------------- 生成总结信息 -------------------------
 
     /// 生成项目树的总结信息
-    /// 从 `root` 启动，遍历并生成项目树各节点的总结信息
+    /// 从 `root` 启动，遍历并生成项目树各节点的总结信息。
+    /// 若总结过程中被取消，已完成的节点保留其总结信息，并返回 `AruiError::Cancelled`。
+    /// 总结成功后返回本次扫描的统计信息 `BuildStats`。
     ///
     /// # Example
     ///
@@ -169,73 +646,3216 @@ impl ProjectTree {
     /// project.summarize().unwrap();
     /// println!("{}", project.root.as_ref().unwrap());
     /// ```
-    pub fn summarize(&mut self) -> Result<()> {
+    pub fn summarize(&mut self) -> Result<BuildStats> {
+        let start = Instant::now();
         // 如果根节点不存在，返回错误
         if self.root.is_none() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "root is none, please build by `build()` first",
-            ));
+            return Err(AruiError::NotBuilt);
         }
         // 如果根路径不合法，返回错误
-        if !self.is_valid() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "invalid root path, please check it",
-            ));
-        }
+        self.check_root()?;
         // 递归获取总结信息
-        self.root.as_mut().unwrap().upsert_summary();
-        Ok(())
+        let mut summarizer = self.summarizer();
+        let root = self.root.as_mut().unwrap();
+        root.summary = summarizer.summarize(root);
+        self.warnings.extend(summarizer.take_warnings());
+        if summarizer.is_cancelled() {
+            return Err(AruiError::Cancelled);
+        }
+        Ok(BuildStats {
+            elapsed: start.elapsed(),
+            ..summarizer.take_stats()
+        })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tree::visible::ProjectTreeVisible;
+    /// 创建总结器
+    fn summarizer(&self) -> Summarizer {
+        Summarizer::default()
+            .with_fs(self.fs.clone())
+            .with_progress(self.progress.clone())
+            .with_cancel(self.cancel.clone())
+            .with_cache(self.cache.clone())
+            .with_max_file_size(self.config.as_ref().and_then(|config| config.max_file_size))
+            .with_count_words(
+                self.config
+                    .as_ref()
+                    .is_some_and(|config| config.count_words),
+            )
+            .with_markers(
+                self.config
+                    .as_ref()
+                    .map(|config| config.markers.clone())
+                    .unwrap_or_default(),
+            )
+            .with_count_tokens(
+                self.config
+                    .as_ref()
+                    .is_some_and(|config| config.count_tokens),
+            )
+            .with_top_n(self.config.as_ref().and_then(|config| config.track_top_n))
+            .bind_cache()
+    }
 
-    #[test]
-    fn test_new() {
-        let name = "test";
-        let path = ".".to_string();
-        let tree = ProjectTree::new(name, path, None);
-        assert_eq!(tree.id.len(), 36);
-        assert_eq!(tree.name, name.to_string());
-        assert_eq!(tree.path, ".".to_string());
+    /// 按语言汇总的统计信息，按行数从多到少排列
+    /// 项目树未构建或尚未生成总结信息时为空
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::Language;
+    /// use arui_core::tree::root::ProjectTree;
+    /// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let (language, stats) = project.language_stats()[0];
+    /// assert_eq!(language, Language::Rust);
+    /// assert!(stats.lines > 0);
+    /// ```
+    pub fn language_stats(&self) -> Vec<(Language, ExtStats)> {
+        let Some(root) = &self.root else {
+            return Vec::new();
+        };
+        let mut stats: Vec<(Language, ExtStats)> = root
+            .summary
+            .by_language
+            .iter()
+            .map(|(language, stats)| (*language, *stats))
+            .collect();
+        stats.sort_by(|a, b| b.1.lines.cmp(&a.1.lines).then(a.0.cmp(&b.0)));
+        stats
     }
 
-    #[test]
-    fn test_get_valid() {
-        let valid_tree = ProjectTree::new("test".to_string(), "./src".to_string(), None);
-        assert_eq!(valid_tree.is_valid(), true);
-        let invalid_tree = ProjectTree::new("test".to_string(), "/not_exist".to_string(), None);
-        assert_eq!(invalid_tree.is_valid(), false);
+    /// 列出项目树中所有的空目录，按深度优先的顺序排列，未生成总结信息时为空
+    /// 借助总结信息中的 `empty_dir_count` 跳过不包含空目录的子树
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new()
+    ///     .add_file("project/src/lib.rs", "")
+    ///     .add_dir("project/empty")
+    ///     .add_dir("project/nested/empty");
+    /// let mut project = ProjectTree::new("test", "project", None).with_fs(fs);
+    /// project.analyze().unwrap();
+    /// let empty: Vec<&str> = project.empty_dirs().iter().map(|node| node.path.as_str()).collect();
+    /// assert_eq!(empty, vec!["project/empty", "project/nested/empty"]);
+    /// assert_eq!(project.root.as_ref().unwrap().summary.empty_dir_count, 2);
+    /// ```
+    pub fn empty_dirs(&self) -> Vec<&TreeNode> {
+        let mut empty = Vec::new();
+        let mut stack: Vec<&TreeNode> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            if node.summary.is_empty {
+                empty.push(node);
+            }
+            if let Some(children) = &node.children {
+                let children = children
+                    .iter()
+                    .rev()
+                    .filter(|child| child.summary.empty_dir_count > 0);
+                stack.extend(children);
+            }
+        }
+        empty
     }
 
-    #[test]
-    fn test_build_project_tree() {
-        let name = "test";
-        let path = "./src";
-        let mut tree = ProjectTree::new(name, path, None);
-        tree.build().expect("panic");
-        assert_eq!(tree.root.is_some(), true);
-        assert_eq!(tree.name, "test".to_string());
-        assert_eq!(tree.path, "./src".to_string());
-        // 打印一下看看结构是否正确
-        tree.print_tree();
+    /// 找出项目树中内容完全相同的文件，需要开启 `hash` 特性
+    /// 根据总结信息中的大小与摘要分组，按重复占用的大小从大到小排列，未生成总结信息时为空
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new()
+    ///     .add_file("project/a.txt", "same")
+    ///     .add_file("project/copy/a.txt", "same")
+    ///     .add_file("project/b.txt", "other");
+    /// let mut project = ProjectTree::new("test", "project", None).with_fs(fs);
+    /// project.analyze().unwrap();
+    /// let duplicates = project.find_duplicates();
+    /// assert_eq!(duplicates.len(), 1);
+    /// assert_eq!(duplicates[0].paths, vec!["project/a.txt", "project/copy/a.txt"]);
+    /// assert_eq!(duplicates[0].wasted, 4);
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn find_duplicates(&self) -> Vec<DuplicateGroup> {
+        self.root.as_ref().map(find_duplicates).unwrap_or_default()
     }
 
-    #[test]
-    fn test_plant() {
-        let name = "test";
-        let path = "./src";
-        let tree = ProjectTree::plant(name, path, None);
-        assert_eq!(tree.root.is_some(), true);
-        assert_eq!(tree.name, "test".to_string());
-        assert_eq!(tree.path, "./src".to_string());
-        // 打印一下看看结构是否正确
-        tree.print_tree();
-        println!("{}", tree.root.unwrap());
+    /// 将所有文件的 SHA-256 校验和以 `sha256sum` 兼容的格式写入 `writer`，路径相对于项目根路径，返回写入的文件数量
+    /// 需要开启 `hash` 特性并已生成总结信息，未计算摘要的文件不会写入；项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new().add_file("release/app.txt", "hello");
+    /// let mut project = ProjectTree::new("test", "release", None).with_fs(fs);
+    /// project.analyze().unwrap();
+    /// let mut output = Vec::new();
+    /// project.write_checksums(&mut output).unwrap();
+    /// assert_eq!(
+    ///     String::from_utf8(output).unwrap(),
+    ///     "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824  app.txt\n"
+    /// );
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn write_checksums<W: std::io::Write>(&self, writer: W) -> Result<u64> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        Ok(checksum::write(root, &self.path, writer)?)
+    }
+
+    /// 读取项目所在 Git 仓库的提交历史，为各节点记录最近一次修改的提交，需要开启 `git` 特性
+    /// 文件节点记录最近修改该文件的提交，目录节点汇总为其下最近的提交，结果写入 `NodeSummary::last_commit`。
+    /// 项目需位于磁盘上某个 Git 仓库的工作区之内，否则返回 `AruiError::Git`；
+    /// 重新总结会重置提交信息，需要在 `summarize` 或 `analyze` 之后调用。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// // 源码快照可能不在 Git 仓库中
+    /// if project.annotate_git().is_ok() {
+    ///     let root = project.root.as_ref().unwrap();
+    ///     if let Some(commit) = &root.summary.last_commit {
+    ///         println!("last modified by {} in {}", commit.author, commit.id);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "git")]
+    pub fn annotate_git(&mut self) -> Result<()> {
+        let root = self.root.as_mut().ok_or(AruiError::NotBuilt)?;
+        git::annotate(root)
+    }
+
+    /// 按内置的评分规则生成项目健康报告，需要在 `summarize` 或 `analyze` 之后调用
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let report = project.health_report().unwrap();
+    /// assert!((0.0..=100.0).contains(&report.score));
+    /// println!("{}", report);
+    /// ```
+    pub fn health_report(&self) -> Result<HealthReport> {
+        self.health_report_with(&HealthReport::default_rules())
+    }
+
+    /// 按给定的评分规则生成项目健康报告，规则可以是内置规则与自定义规则的任意组合
+    pub fn health_report_with(&self, rules: &[Box<dyn HealthRule>]) -> Result<HealthReport> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        Ok(HealthReport::evaluate(root, rules))
+    }
+
+    // ------------------------- 局部重建 -------------------------
+
+    /// 重建指定路径下的子树
+    /// - path：子树路径，相对路径基于项目根路径解析，绝对路径则直接使用
+    ///
+    /// 仅重新扫描该路径，并用新生成的节点替换原有节点。
+    /// 若项目树已经生成过总结信息，则同时更新该子树的总结信息，并沿祖先链向上重新汇总，直到根节点。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.rebuild_subtree("tree").unwrap();
+    /// // 不存在于项目树中的路径会返回错误
+    /// assert!(project.rebuild_subtree("not_exist").is_err());
+    /// ```
+    pub fn rebuild_subtree<S>(&mut self, path: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let target = self.resolve_path(path);
+        self.rebuild_node(target)
+    }
+
+    /// 检查项目树是否与文件系统一致，并重建所有过期的子树，返回重建前的检查结果
+    /// 适用于从缓存恢复项目树后将其更新为最新状态，成本远低于重新扫描整个项目；
+    /// 过期的子树被删除（如项目根路径已不存在）时返回错误
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// project.refresh_stale().unwrap();
+    /// assert!(project.validate_against_fs().unwrap().is_fresh());
+    /// ```
+    pub fn refresh_stale(&mut self) -> Result<Freshness> {
+        let freshness = self.validate_against_fs()?;
+        for path in &freshness.stale {
+            self.rebuild_node(PathBuf::from(path))?;
+        }
+        Ok(freshness)
+    }
+
+    /// 重建指定节点路径下的子树，子树的最大深度与整棵项目树一致，从项目根节点起算
+    fn rebuild_node(&mut self, target: PathBuf) -> Result<()> {
+        let mut builder = self.builder().with_start_depth(self.depth_of(&target));
+        let result = self.update_subtree(target, |node| {
+            *node = builder.build(Path::new(&node.path))?;
+            if builder.is_cancelled() {
+                return Err(AruiError::Cancelled);
+            }
+            Ok(())
+        });
+        self.warnings.extend(builder.take_warnings());
+        result
+    }
+
+    /// 按需展开指定路径下的目录节点
+    /// - path：目录路径，相对路径基于项目根路径解析，绝对路径则直接使用
+    ///
+    /// 仅加载该目录的直接子节点，子目录仍保持未展开状态，适用于配置了 `max_depth` 的浅构建。
+    /// 若项目树已经生成过总结信息，则同时更新该目录的总结信息，并沿祖先链向上重新汇总。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::{ProjectConfig, ProjectTree};
+    /// let config = ProjectConfig::new().with_max_depth(1);
+    /// let mut project = ProjectTree::new("test", "./src", Some(config));
+    /// project.build().unwrap();
+    /// project.expand("tree").unwrap();
+    /// ```
+    pub fn expand<S>(&mut self, path: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        // 沿用项目配置的构建选项，仅将深度限制为目标目录的下一层
+        let target = self.resolve_path(path);
+        let depth = self.depth_of(&target);
+        let mut builder = self
+            .builder()
+            .with_max_depth(Some(depth + 1))
+            .with_start_depth(depth);
+        let result = self.update_subtree(target, |node| {
+            if !node.is_loaded() {
+                *node = builder.build(Path::new(&node.path))?;
+                if builder.is_cancelled() {
+                    return Err(AruiError::Cancelled);
+                }
+            }
+            Ok(())
+        });
+        self.warnings.extend(builder.take_warnings());
+        result
+    }
+
+    /// 查找路径对应的节点
+    /// - path：节点路径，相对路径基于项目根路径解析
+    ///
+    /// 项目树未构建或节点不存在时返回空
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// assert!(project.find("tree/node.rs").is_some());
+    /// ```
+    pub fn find<S>(&self, path: S) -> Option<&TreeNode>
+    where
+        S: Into<String>,
+    {
+        self.root.as_ref()?.find(self.resolve_path(path))
+    }
+
+    /// 获取路径对应节点的祖先链，从根节点开始依次到其父节点
+    /// - path：节点路径，相对路径基于项目根路径解析
+    ///
+    /// 例如，获取某个文件所属的顶层目录：
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let ancestors = project.ancestors("tree/node/count.rs").unwrap();
+    /// assert_eq!(ancestors[1].name(), "tree");
+    /// ```
+    pub fn ancestors<S>(&self, path: S) -> Option<Vec<&TreeNode>>
+    where
+        S: Into<String>,
+    {
+        self.root.as_ref()?.ancestors(self.resolve_path(path))
+    }
+
+    /// 获取路径对应节点的父节点，根节点或不存在的节点返回空
+    pub fn parent<S>(&self, path: S) -> Option<&TreeNode>
+    where
+        S: Into<String>,
+    {
+        self.root.as_ref()?.parent(self.resolve_path(path))
+    }
+
+    /// 将项目树转换为紧凑表示 `ArenaTree`，项目树未构建时返回空
+    /// 适用于节点数量巨大、需要长期保存项目树的场景
+    pub fn to_arena(&self) -> Option<ArenaTree> {
+        self.root.as_ref().map(ArenaTree::from)
+    }
+
+    /// 以指定路径的节点为根，生成只包含该子树的项目树，用于仅导出项目的一部分
+    /// - path：节点路径，相对路径基于项目根路径解析
+    ///
+    /// 子树的根路径为该节点的路径，别名、ID、配置、文件系统与总结缓存与原项目树相同，警告仅保留子树之下的部分；
+    /// 所有导出方法（如 `to_json`、`to_csv`、`write_checksums`）均可直接用于子树，其中的相对路径基于子树的根路径计算。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`，节点不存在时返回 `AruiError::NodeNotFound`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let tree = project.subtree("tree").unwrap();
+    /// let mut output = Vec::new();
+    /// tree.to_csv(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().lines().skip(1).all(|line| line.starts_with("./src/tree/")));
+    /// ```
+    pub fn subtree<S>(&self, path: S) -> Result<ProjectTree>
+    where
+        S: Into<String>,
+    {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let target = self.resolve_path(path);
+        let node = root
+            .find(&target)
+            .ok_or_else(|| AruiError::NodeNotFound(target.to_string_lossy().into_owned()))?;
+        let warnings = self
+            .warnings
+            .iter()
+            .filter(|warning| Path::new(&warning.path).starts_with(&node.path))
+            .cloned()
+            .collect();
+        Ok(ProjectTree {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            path: node.path.clone(),
+            root: Some(node.clone()),
+            config: self.config.clone(),
+            fs: self.fs.clone(),
+            progress: None,
+            cancel: None,
+            warnings,
+            checkpoint: None,
+            cache: self.cache.clone(),
+        })
+    }
+
+    /// 将项目树导出为 JSON 字符串，需要开启 `json` 特性
+    /// - pretty：是否缩进排版，否则输出紧凑的单行 JSON
+    ///
+    /// 导出结果为一个对象，字段名与结构体字段一致：
+    /// - `schema_version`：导出格式版本，兼容规则见 `schema` 模块
+    /// - `id`、`name`、`path`：项目 ID、别名与根路径
+    /// - `root`：根节点，项目树未构建时为 `null`
+    /// - `config`：项目树配置，未设置时为 `null`
+    /// - `warnings`：警告列表，每项包含 `path`、`kind` 与 `message`
+    ///
+    /// 每个节点包含 `path`、`is_dir`、`kind`、`children`（文件为 `null`）、`summary`、`marker` 与 `symlink_target`，
+    /// 其中 `summary` 为 `NodeSummary` 的公开字段；依赖特性的字段（如 `digest`、`owner`、`compressed_size`）仅在开启对应特性时导出，
+    /// 以未开启这些特性的版本读取时会被忽略，读取缺少这些字段的导出结果时取默认值；
+    /// 文件系统、进度报告、取消令牌、检查点与总结缓存属于运行时状态，不会导出。
+    /// 导出结果可通过 `from_json` 重新加载。
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let json = project.to_json(true).unwrap();
+    /// assert!(json.contains("\"summary\""));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, pretty: bool) -> Result<String> {
+        self.to_json_with(&JsonOptions::new().with_pretty(pretty))
+    }
+
+    /// 按选项将项目树导出为 JSON 字符串，结构与 `to_json` 相同，需要开启 `json` 特性
+    /// 可以选择是否缩进排版以及各节点保留的总结信息字段，详见 `JsonOptions`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::json::JsonOptions;
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let json = project
+    ///     .to_json_with(&JsonOptions::new().without_timestamps())
+    ///     .unwrap();
+    /// assert!(json.contains("\"summary\""));
+    /// assert!(!json.contains("\"summarized_at\""));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json_with(&self, options: &JsonOptions) -> Result<String> {
+        let export = ExportRef::new(self);
+        let json = match (options.is_filtered(), options.pretty) {
+            (false, false) => serde_json::to_string(&export)?,
+            (false, true) => serde_json::to_string_pretty(&export)?,
+            (true, false) => serde_json::to_string(&self.filtered_json(options)?)?,
+            (true, true) => serde_json::to_string_pretty(&self.filtered_json(options)?)?,
+        };
+        Ok(json)
+    }
+
+    /// 将项目树以紧凑的 JSON 写入 `writer`，结构与 `to_json` 相同，需要开启 `json` 特性
+    /// 直接写入而不生成中间字符串，适合导出较大的项目树；`writer` 无缓冲时建议包装为 `BufWriter`
+    #[cfg(feature = "json")]
+    pub fn write_json<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        self.write_json_with(writer, &JsonOptions::new())
+    }
+
+    /// 按选项将项目树以 JSON 写入 `writer`，需要开启 `json` 特性，选项见 `JsonOptions`
+    /// 筛选字段时需要先在内存中生成完整的 JSON 值，导出大型项目树时内存占用会明显增加
+    #[cfg(feature = "json")]
+    pub fn write_json_with<W: std::io::Write>(
+        &self,
+        writer: W,
+        options: &JsonOptions,
+    ) -> Result<()> {
+        let export = ExportRef::new(self);
+        match (options.is_filtered(), options.pretty) {
+            (false, false) => serde_json::to_writer(writer, &export)?,
+            (false, true) => serde_json::to_writer_pretty(writer, &export)?,
+            (true, false) => serde_json::to_writer(writer, &self.filtered_json(options)?)?,
+            (true, true) => serde_json::to_writer_pretty(writer, &self.filtered_json(options)?)?,
+        }
+        Ok(())
+    }
+
+    /// 生成完整的 JSON 值并按选项筛选各节点的总结信息字段
+    #[cfg(feature = "json")]
+    fn filtered_json(&self, options: &JsonOptions) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(ExportRef::new(self))?;
+        options.filter(&mut value);
+        Ok(value)
+    }
+
+    /// 将项目树以 gzip 压缩的紧凑 JSON 写入 `writer`，解压后与 `write_json` 的结果相同，需要同时开启 `json` 与 `gzip` 特性
+    /// 大型项目的完整 JSON 可达数百 MB，压缩后通常只有原来的十分之一左右；写入完成后 gzip 流即结束，
+    /// 项目树未构建时同样写入 `root` 为 `null` 的结果
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let mut output = Vec::new();
+    /// project.write_json_gz(&mut output).unwrap();
+    /// assert_eq!(&output[..2], &[0x1f, 0x8b]);
+    /// ```
+    #[cfg(all(feature = "json", feature = "gzip"))]
+    pub fn write_json_gz<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+        self.write_json(&mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// 从 `to_json` 或 `write_json` 导出的 JSON 重新加载项目树，需要开启 `json` 特性
+    /// 忽略无法识别的字段，缺少的字段取默认值；格式版本高于当前支持的版本时返回 `AruiError::UnsupportedSchema`。
+    /// 加载后的文件系统为磁盘，其余运行时状态为空
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let json = project.to_json(false).unwrap();
+    /// let loaded = ProjectTree::from_json(&json).unwrap();
+    /// assert_eq!(loaded.to_json(false).unwrap(), json);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str::<SchemaHeader>(json)?.check()?;
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// 将项目树导出为 d3 “flare” 格式的 JSON，可直接用于 d3 与 ECharts 的矩形树图与旭日图，需要开启 `json` 特性
+    /// - metric：作为节点值的统计指标，即文件大小或行数
+    ///
+    /// 根节点的名称为项目别名，其余节点为文件名；目录节点仅包含 `children`，文件节点仅包含 `value`。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::flare::FlareMetric;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new().add_file("project/src/main.rs", "fn main() {}\n");
+    /// let mut project = ProjectTree::new("demo", "project", None).with_fs(fs);
+    /// project.analyze().unwrap();
+    /// assert_eq!(
+    ///     project.to_flare(FlareMetric::Lines).unwrap(),
+    ///     r#"{"name":"demo","children":[{"name":"src","children":[{"name":"main.rs","value":1}]}]}"#
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_flare(&self, metric: FlareMetric) -> Result<String> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        Ok(serde_json::to_string(&FlareNode::new(
+            root, &self.name, metric,
+        ))?)
+    }
+
+    /// 将项目树导出为 YAML 字符串，结构与 `to_json` 相同，需要开启 `yaml` 特性
+    /// 各统计表按键排序输出，相同的项目树总是得到相同的结果，适合作为快照提交到仓库中并在代码审查时比较差异；
+    /// 项目 ID、总结时间与文件时间戳在每次扫描时可能不同，比较时需要忽略
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let yaml = project.to_yaml().unwrap();
+    /// assert!(yaml.contains("name: test"));
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String> {
+        Ok(serde_yaml_ng::to_string(&ExportRef::new(self))?)
+    }
+
+    /// 从 `to_yaml` 导出的 YAML 重新加载项目树，需要开启 `yaml` 特性，兼容规则与 `from_json` 相同
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        serde_yaml_ng::from_str::<SchemaHeader>(yaml)?.check()?;
+        Ok(serde_yaml_ng::from_str(yaml)?)
+    }
+
+    /// 生成项目树的扁平列表，每个节点（包括目录）一行，格式为 `路径<TAB>大小<TAB>行数`，按路径的字典序排列
+    /// 类似附带统计信息的 `find` 输出，便于直接交给 `sort`、`awk` 等命令处理；目录的大小与行数为其下所有文件之和。
+    /// 路径中的反斜杠、制表符与换行符分别转义为 `\\`、`\t` 与 `\n`，保证每个节点恰好占一行。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::fs::MemoryFs;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// let fs = MemoryFs::new().add_file("project/src/main.rs", "fn main() {}\n");
+    /// let mut project = ProjectTree::new("test", "project", None).with_fs(fs);
+    /// project.analyze().unwrap();
+    /// assert_eq!(
+    ///     project.to_flat_list().unwrap(),
+    ///     "project\t13\t1\nproject/src\t13\t1\nproject/src/main.rs\t13\t1\n"
+    /// );
+    /// ```
+    pub fn to_flat_list(&self) -> Result<String> {
+        let mut output = Vec::new();
+        self.write_flat_list(&mut output)?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    /// 将扁平列表写入 `writer`，内容与 `to_flat_list` 相同
+    pub fn write_flat_list<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let mut nodes = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            stack.extend(node.children.iter().flatten());
+        }
+        nodes.sort_by(|a, b| a.path.cmp(&b.path));
+        for node in nodes {
+            let path = node
+                .path
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n");
+            writeln!(
+                writer,
+                "{}\t{}\t{}",
+                path, node.summary.size, node.summary.count
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 将项目树以 XML 目录清单的格式写入 `writer`，每个节点包含大小、行数与类型等属性，元素与属性的说明见 `xml::write`
+    /// 项目树未构建时返回 `AruiError::NotBuilt`；`writer` 无缓冲时建议包装为 `BufWriter`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let mut output = Vec::new();
+    /// project.to_xml(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains("<file name=\"lib.rs\""));
+    /// ```
+    pub fn to_xml<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        xml::write(&self.name, root, writer)?;
+        Ok(())
+    }
+
+    /// 重新扫描文件系统（同 `refresh`），并将本次的变更事件追加到 NDJSON 日志文件，需要开启 `json` 特性
+    /// - path：日志文件路径，不存在时创建，已有内容保持不变
+    ///
+    /// 每个变更一行，格式见 `ndjson::write_changes`；没有变更时不写入任何内容。
+    /// 适合定期调用的监控场景，消费者只需跟踪日志中新增的行即可获得增量更新
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let log = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let changes = project.refresh_to_log(&log).unwrap();
+    /// assert!(changes.is_empty());
+    /// # let _ = std::fs::remove_file(log);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn refresh_to_log<P: AsRef<Path>>(&mut self, path: P) -> Result<ChangeSet> {
+        let changes = self.refresh()?;
+        if !changes.is_empty() {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            ndjson::write_changes(&changes, std::time::SystemTime::now(), file)?;
+        }
+        Ok(changes)
+    }
+
+    /// 将项目树以 NDJSON 格式写入 `writer`，每行一个节点，需要开启 `json` 特性
+    /// 节点按先序遍历的顺序边遍历边写入，不会在内存中生成完整的 JSON 字符串，各字段的含义见 `ndjson::write`；
+    /// 项目树未构建时返回 `AruiError::NotBuilt`，`writer` 无缓冲时建议包装为 `BufWriter`
+    #[cfg(feature = "json")]
+    pub fn write_ndjson<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        ndjson::write(root, writer)
+    }
+
+    /// 以紧凑的二进制格式（MessagePack）将项目树保存到文件，需要开启 `msgpack` 特性
+    /// 文件以标识 `ARUI` 与格式版本开头，之后为项目树的内容，运行时状态（文件系统、进度报告等）不会保存；
+    /// 内容以字段名为键写入，开启的特性不同（如是否开启 `hash`）时同样可以读取，缺少的字段取默认值；
+    /// 先写入临时文件再替换，避免写入过程中被中断导致缓存文件损坏
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let path = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// project.save_cache(&path).unwrap();
+    ///
+    /// // 之后的运行中直接恢复扫描结果
+    /// let restored = ProjectTree::load_cache(&path).unwrap();
+    /// assert_eq!(restored.root.unwrap().summary.count, project.root.unwrap().summary.count);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "msgpack")]
+    pub fn save_cache<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Self::replace_file(path.as_ref(), |writer| self.encode_cache(writer))
+    }
+
+    /// 将项目树以 gzip 压缩后保存到文件，需要同时开启 `msgpack` 与 `gzip` 特性
+    /// 内容与 `save_cache` 相同，压缩后通常只有原来的几分之一，`load_cache` 可以直接读取
+    #[cfg(all(feature = "msgpack", feature = "gzip"))]
+    pub fn save_cache_compressed<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Self::replace_file(path.as_ref(), |writer| {
+            let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+            self.encode_cache(&mut encoder)?;
+            encoder.finish()?;
+            Ok(())
+        })
+    }
+
+    /// 写入缓存文件的文件头与项目树的内容
+    #[cfg(feature = "msgpack")]
+    fn encode_cache<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(TREE_CACHE_MAGIC)?;
+        writer.write_all(&TREE_CACHE_VERSION.to_le_bytes())?;
+        rmp_serde::encode::write_named(&mut writer, self)
+            .map_err(|error| AruiError::InvalidCache(error.to_string()))
+    }
+
+    /// 先写入临时文件再替换目标文件，避免写入过程中被中断导致文件损坏
+    #[cfg(feature = "msgpack")]
+    fn replace_file<F>(path: &Path, write: F) -> Result<()>
+    where
+        F: FnOnce(&mut std::io::BufWriter<std::fs::File>) -> Result<()>,
+    {
+        use std::io::Write;
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&temp)?);
+        write(&mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(temp, path)?;
+        Ok(())
+    }
+
+    /// 从 `save_cache` 保存的文件恢复项目树，需要开启 `msgpack` 特性
+    /// 开启 `gzip` 特性时同样可以读取 `save_cache_compressed` 保存的文件，根据文件头自动识别是否经过压缩。
+    /// 恢复后的文件系统为磁盘，其余运行时状态为空；
+    /// 文件标识或格式版本不一致、内容无法解析时返回 `AruiError::InvalidCache`，此时应重新扫描
+    #[cfg(feature = "msgpack")]
+    pub fn load_cache<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read(path)?;
+        #[cfg(feature = "gzip")]
+        let contents = if contents.starts_with(&[0x1f, 0x8b]) {
+            use std::io::Read;
+            let mut decoded = Vec::new();
+            flate2::read::GzDecoder::new(contents.as_slice())
+                .read_to_end(&mut decoded)
+                .map_err(|error| AruiError::InvalidCache(error.to_string()))?;
+            decoded
+        } else {
+            contents
+        };
+        let header_len = TREE_CACHE_MAGIC.len() + 4;
+        if contents.len() < header_len || !contents.starts_with(TREE_CACHE_MAGIC) {
+            return Err(AruiError::InvalidCache("missing header".to_string()));
+        }
+        let version = u32::from_le_bytes(contents[4..header_len].try_into().unwrap());
+        if version != TREE_CACHE_VERSION {
+            return Err(AruiError::InvalidCache(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+        rmp_serde::from_slice(&contents[header_len..])
+            .map_err(|error| AruiError::InvalidCache(error.to_string()))
+    }
+
+    /// 将项目树保存为只包含元数据的 tar 归档（元数据索引），返回写入的节点数量，需要开启 `index` 特性
+    /// 归档中每个节点对应一个内容为空的条目，路径、类型与总结信息保存在 PAX 扩展头中，格式见 `index` 模块；
+    /// 归档的体积与文件大小无关，适合保存大型数据集的目录结构与统计信息，之后可通过 `load_index` 重新加载。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let path = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// project.save_index(&path).unwrap();
+    ///
+    /// let loaded = ProjectTree::load_index(&path).unwrap();
+    /// assert_eq!(loaded.root.unwrap().summary.size, project.root.unwrap().summary.size);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "index")]
+    pub fn save_index<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
+        let writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        index::write(self, writer)
+    }
+
+    /// 从 `save_index` 保存的元数据索引归档重新加载项目树，需要开启 `index` 特性
+    /// 加载后的文件系统为磁盘，其余运行时状态为空；归档格式不正确时返回 `AruiError::InvalidIndex`
+    #[cfg(feature = "index")]
+    pub fn load_index<P: AsRef<Path>>(path: P) -> Result<Self> {
+        index::read(std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// 将项目整体与各顶层目录的汇总信息导出为 TOML 清单，需要开启 `toml` 特性
+    ///
+    /// 清单包含 `[project]` 表与 `[directories.<目录名>]` 表，各表字段如下：
+    /// - `size`：文件大小之和
+    /// - `files`、`dirs`：文件数量与目录数量
+    /// - `lines`、`code`、`comments`、`blanks`：总行数以及代码、注释、空白行数
+    ///
+    /// `[project]` 表另外包含项目别名 `name`；直接位于根目录下的文件仅计入 `[project]`。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`，未总结时各项统计为 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let manifest = project.to_toml_manifest().unwrap();
+    /// assert!(manifest.contains("[directories.tree]"));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn to_toml_manifest(&self) -> Result<String> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let mut project = Self::manifest_table(&root.summary);
+        project.insert("name".to_string(), self.name.clone().into());
+        let directories = root
+            .children
+            .iter()
+            .flatten()
+            .filter(|child| child.is_dir)
+            .map(|child| {
+                let table = Self::manifest_table(&child.summary);
+                (child.name().to_string(), toml::Value::Table(table))
+            })
+            .collect::<toml::Table>();
+        let mut manifest = toml::Table::new();
+        manifest.insert("project".to_string(), toml::Value::Table(project));
+        manifest.insert("directories".to_string(), toml::Value::Table(directories));
+        Ok(manifest.to_string())
+    }
+
+    /// 将 TOML 清单写入 `writer`，内容与 `to_toml_manifest` 相同，需要开启 `toml` 特性
+    #[cfg(feature = "toml")]
+    pub fn write_toml_manifest<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(self.to_toml_manifest()?.as_bytes())?;
+        Ok(())
+    }
+
+    /// 根据总结信息生成 TOML 清单中的一个表，超出 TOML 整数范围的值取最大值
+    #[cfg(feature = "toml")]
+    fn manifest_table(summary: &NodeSummary) -> toml::Table {
+        let fields = [
+            ("size", summary.size),
+            ("files", summary.file_count),
+            ("dirs", summary.dir_count),
+            ("lines", summary.count),
+            ("code", summary.lines.code),
+            ("comments", summary.lines.comments),
+            ("blanks", summary.lines.blanks),
+        ];
+        fields
+            .into_iter()
+            .map(|(key, value)| {
+                let value = i64::try_from(value).unwrap_or(i64::MAX);
+                (key.to_string(), toml::Value::Integer(value))
+            })
+            .collect()
+    }
+
+    /// 将项目树中的所有文件以 CSV 格式写入 `writer`，每个文件一行，各列的含义见 `csv::HEADER`
+    /// 项目树未构建时返回 `AruiError::NotBuilt`；`writer` 无缓冲时建议包装为 `BufWriter`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let mut output = Vec::new();
+    /// project.to_csv(&mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().contains("./src/lib.rs,rs,Rust,"));
+    /// ```
+    pub fn to_csv<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        csv::write(root, writer)
+    }
+
+    /// 使用自定义导出器导出项目树，遍历顺序与各方法的调用时机见 `TreeExporter`
+    /// 项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::errors::AruiError;
+    /// use arui_core::tree::export::TreeExporter;
+    /// use arui_core::tree::node::TreeNode;
+    /// use arui_core::tree::root::ProjectTree;
+    ///
+    /// /// 统计最大深度
+    /// struct MaxDepth(usize);
+    ///
+    /// impl TreeExporter for MaxDepth {
+    ///     fn visit_node(&mut self, _node: &TreeNode, depth: usize) -> Result<(), AruiError> {
+    ///         self.0 = self.0.max(depth);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// let mut exporter = MaxDepth(0);
+    /// project.export(&mut exporter).unwrap();
+    /// assert!(exporter.0 >= 1);
+    /// ```
+    pub fn export<E: TreeExporter + ?Sized>(&self, exporter: &mut E) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        export::walk(root, exporter)
+    }
+
+    /// 将项目树中的所有文件以 Parquet 格式写入 `writer`，返回写入的文件数量，需要开启 `parquet` 特性
+    /// 各列的含义与 `to_csv` 一致，类型见 `parquet::SCHEMA`；项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let path = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// let rows = project.write_parquet(std::fs::File::create(&path).unwrap()).unwrap();
+    /// assert!(rows > 0);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: std::io::Write + Send>(&self, writer: W) -> Result<u64> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        Ok(parquet::write(root, writer)?)
+    }
+
+    /// 将项目树的节点与总结信息写入 SQLite 数据库，返回写入的节点数量，需要开启 `sqlite` 特性
+    /// - path：数据库文件路径，不存在时创建
+    ///
+    /// 表结构见 `sqlite::SCHEMA`；数据库中已有的其他扫描结果保持不变，同一项目树（ID 相同）之前写入的内容会被替换。
+    /// 项目树未构建时返回 `AruiError::NotBuilt`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let path = std::env::temp_dir().join(arui_core::utils::generate_id());
+    /// assert!(project.to_sqlite(&path).unwrap() > 0);
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    #[cfg(feature = "sqlite")]
+    pub fn to_sqlite<P: AsRef<Path>>(&self, path: P) -> Result<u64> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let mut connection = rusqlite::Connection::open(path)?;
+        connection.pragma_update(None, "foreign_keys", true)?;
+        Ok(sqlite::write(&mut connection, &self.id, &self.name, root)?)
+    }
+
+    /// 生成 cloc 或 tokei 风格的语言统计表，包含各语言的文件数量与代码、注释、空白行数以及合计
+    /// - style：表格风格，布局与对应工具的默认输出一致，见 `TableStyle`
+    ///
+    /// 项目树未构建时返回 `AruiError::NotBuilt`，未总结时各项统计为 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::cloc::TableStyle;
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let table = project.to_language_table(TableStyle::Cloc).unwrap();
+    /// assert!(table.lines().any(|line| line.starts_with("Rust ")));
+    /// println!("{}", table);
+    /// ```
+    pub fn to_language_table(&self, style: TableStyle) -> Result<String> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        let mut output = Vec::new();
+        cloc::write(root, style, &mut output)?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    /// 以 `du -b` 的格式将项目树写入 `writer`，每行为 `<大小>\t<路径>`，目录的大小为其下所有文件之和
+    /// - all：是否同时输出文件，对应 `du -ab`
+    ///
+    /// 节点按后序遍历的顺序输出，根节点位于最后一行，格式细节见 `du` 模块；
+    /// 项目树未构建时返回 `AruiError::NotBuilt`，未总结时大小均为 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use arui_core::tree::root::ProjectTree;
+    /// let mut project = ProjectTree::try_plant("test", "./src", None).unwrap();
+    /// project.summarize().unwrap();
+    /// let mut output = Vec::new();
+    /// project.to_du(false, &mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().ends_with("\t./src\n"));
+    /// ```
+    pub fn to_du<W: std::io::Write>(&self, all: bool, writer: W) -> Result<()> {
+        let root = self.root.as_ref().ok_or(AruiError::NotBuilt)?;
+        du::write(root, all, writer)?;
+        Ok(())
+    }
+
+    /// 对指定路径的节点执行更新操作，并在需要时重新汇总总结信息
+    /// - target：已解析的节点路径
+    fn update_subtree<F>(&mut self, target: PathBuf, mut update: F) -> Result<()>
+    where
+        F: FnMut(&mut TreeNode) -> Result<()>,
+    {
+        let summarizer = self.summarizer();
+        let root = self.root.as_mut().ok_or(AruiError::NotBuilt)?;
+        // 根节点存在总结时间，说明已经生成过总结信息，此时需要同步更新总结信息
+        let mut summarizer = root.summary.summarized_at.map(|_| summarizer);
+        let found = Self::update_tree_node(root, &target, &mut summarizer, &mut update);
+        if let Some(summarizer) = &mut summarizer {
+            self.warnings.extend(summarizer.take_warnings());
+        }
+        if !found? {
+            return Err(AruiError::NodeNotFound(
+                target.to_string_lossy().into_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 节点路径相对项目根路径的深度，根节点为 0，不在项目根路径之下时视为 0
+    fn depth_of(&self, path: &Path) -> usize {
+        path.strip_prefix(&self.path)
+            .map_or(0, |relative| relative.components().count())
+    }
+
+    /// 将路径解析为项目树中的节点路径
+    fn resolve_path<S>(&self, path: S) -> PathBuf
+    where
+        S: Into<String>,
+    {
+        let path = PathBuf::from(path.into());
+        if path.is_absolute() {
+            path
+        } else {
+            Path::new(&self.path).join(path)
+        }
+    }
+
+    /// 递归查找目标节点并执行更新，返回是否找到目标节点
+    /// 找到目标后，沿递归返回路径依次重新汇总各祖先节点的总结信息
+    fn update_tree_node<F>(
+        node: &mut TreeNode,
+        target: &Path,
+        summarizer: &mut Option<Summarizer>,
+        update: &mut F,
+    ) -> Result<bool>
+    where
+        F: FnMut(&mut TreeNode) -> Result<()>,
+    {
+        if Path::new(&node.path) == target {
+            update(node)?;
+            if let Some(summarizer) = summarizer {
+                node.summary = summarizer.summarize(node);
+            }
+            return Ok(true);
+        }
+        // 目标不在当前节点之下，无需继续查找
+        if !target.starts_with(&node.path) {
+            return Ok(false);
+        }
+        let mut found = false;
+        if let Some(children) = node.children.as_mut() {
+            for child in children {
+                if Self::update_tree_node(child, target, summarizer, update)? {
+                    found = true;
+                    break;
+                }
+            }
+        }
+        if found && summarizer.is_some() {
+            node.summary = NodeSummary::collect(node);
+            NodeSummary::mark_vendored(node);
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::encoding::Encoding;
+    use crate::tree::visible::ProjectTreeVisible;
+    use crate::tree::warning::WarningKind;
+    use std::fs;
+
+    /// 使用给定的文件系统与配置分析 `project` 目录
+    fn analyze<F: FileSystem + 'static>(fs: F, config: Option<ProjectConfig>) -> ProjectTree {
+        let mut tree = ProjectTree::new("test", "project", config).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        tree
+    }
+
+    /// 测试使用的临时目录，离开作用域时（包括断言失败时）自动删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(generate_id());
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl std::ops::Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl AsRef<Path> for TempDir {
+        fn as_ref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let name = "test";
+        let path = ".".to_string();
+        let tree = ProjectTree::new(name, path, None);
+        assert_eq!(tree.id.len(), 36);
+        assert_eq!(tree.name, name.to_string());
+        assert_eq!(tree.path, ".".to_string());
+    }
+
+    #[test]
+    fn test_get_valid() {
+        let valid_tree = ProjectTree::new("test".to_string(), "./src".to_string(), None);
+        assert!(valid_tree.is_valid());
+        let invalid_tree = ProjectTree::new("test".to_string(), "/not_exist".to_string(), None);
+        assert!(!invalid_tree.is_valid());
+    }
+
+    #[test]
+    fn test_build_project_tree() {
+        let name = "test";
+        let path = "./src";
+        let mut tree = ProjectTree::new(name, path, None);
+        tree.build().expect("panic");
+        assert!(tree.root.is_some());
+        assert_eq!(tree.name, "test".to_string());
+        assert_eq!(tree.path, "./src".to_string());
+        // 打印一下看看结构是否正确
+        tree.print_tree();
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_plant() {
+        let name = "test";
+        let path = "./src";
+        let tree = ProjectTree::plant(name, path, None);
+        assert!(tree.root.is_some());
+        assert_eq!(tree.name, "test".to_string());
+        assert_eq!(tree.path, "./src".to_string());
+        // 打印一下看看结构是否正确
+        tree.print_tree();
+        println!("{}", tree.root.unwrap());
+    }
+
+    #[test]
+    fn test_try_plant() {
+        let tree = ProjectTree::try_plant("test", "./src", None).expect("plant failed");
+        assert!(tree.root.as_ref().unwrap().summary.summarized_at.is_some());
+        assert!(matches!(
+            ProjectTree::try_plant("test", "./not_exist", None),
+            Err(AruiError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_rebuild_subtree() {
+        let mut tree =
+            ProjectTree::try_plant("test", "./tests/examples", None).expect("plant failed");
+        let before = tree.root.as_ref().unwrap().summary.clone();
+        tree.rebuild_subtree("tree/summary")
+            .expect("rebuild failed");
+        let after = &tree.root.as_ref().unwrap().summary;
+        // 文件未发生变化，重建后汇总结果应保持一致
+        assert_eq!(before.size, after.size);
+        assert_eq!(before.count, after.count);
+        assert!(after.summarized_at > before.summarized_at);
+        // 修改时间取自文件系统，与总结的时间无关
+        assert_eq!(after.updated_at, before.updated_at);
+    }
+
+    #[test]
+    fn test_shallow_build_and_expand() {
+        let config = ProjectConfig::new().with_max_depth(1);
+        let mut tree = ProjectTree::new("test", "./tests/examples", Some(config));
+        tree.build().expect("build failed");
+        let root = tree.root.as_ref().unwrap();
+        assert!(root.is_loaded());
+        // 第一层目录未加载子节点
+        let sub_dir = root
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|child| child.is_dir)
+            .unwrap();
+        assert!(!sub_dir.is_loaded());
+
+        tree.summarize().expect("summarize failed");
+        let shallow_size = tree.root.as_ref().unwrap().summary.size;
+        tree.expand("tree").expect("expand failed");
+        tree.expand("tree/summary").expect("expand failed");
+        // 展开后总结信息沿祖先链更新
+        assert!(tree.root.as_ref().unwrap().summary.size > shallow_size);
+    }
+
+    #[test]
+    fn test_rebuild_subtree_depth() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/a/b/c/d.rs", "fn d() {}\n")
+            .add_file("project/a/e.rs", "fn e() {}\n");
+        let config = ProjectConfig::new().with_max_depth(2);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.build().expect("build failed");
+        assert!(!tree.find("a/b").unwrap().is_loaded());
+        // 重建子树时最大深度从项目根节点起算，`a/b` 仍不展开
+        tree.rebuild_subtree("a").expect("rebuild failed");
+        assert!(!tree.find("a/b").unwrap().is_loaded());
+        tree.expand("a/b").expect("expand failed");
+        assert!(tree.find("a/b/c").is_some());
+    }
+
+    #[test]
+    fn test_expand_progress_and_cancel() {
+        use crate::fs::MemoryFs;
+        use crate::tree::progress::ProgressEvent;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let fs = MemoryFs::new().add_file("project/a/b/c.rs", "fn c() {}\n");
+        let config = ProjectConfig::new().with_max_depth(1);
+        let events = Arc::new(AtomicUsize::new(0));
+        let sink = events.clone();
+        let mut tree = ProjectTree::new("test", "project", Some(config))
+            .with_fs(fs)
+            .with_progress(move |_: ProgressEvent| {
+                sink.fetch_add(1, Ordering::Relaxed);
+            });
+        tree.build().expect("build failed");
+        // 展开时同样报告进度
+        let before = events.load(Ordering::Relaxed);
+        tree.expand("a").expect("expand failed");
+        assert!(events.load(Ordering::Relaxed) > before);
+        // 已取消时不再展开
+        let token = CancelToken::new();
+        token.cancel();
+        tree.cancel = Some(token);
+        assert!(matches!(tree.expand("a/b"), Err(AruiError::Cancelled)));
+        assert!(!tree.find("a/b").unwrap().is_loaded());
+    }
+
+    #[test]
+    fn test_progress() {
+        use std::sync::Mutex;
+        let files = Arc::new(Mutex::new(Vec::new()));
+        let sink = files.clone();
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree/summary", None)
+            .with_progress(move |event: crate::tree::progress::ProgressEvent| {
+                if let crate::tree::progress::ProgressEvent::ProcessedFiles(n) = event {
+                    sink.lock().unwrap().push(n);
+                }
+            });
+        tree.build().expect("build failed");
+        tree.summarize().expect("summarize failed");
+        // 构建和总结过程各处理 3 个文件
+        assert_eq!(*files.lock().unwrap(), vec![1, 2, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_cancel_build() {
+        let token = CancelToken::new();
+        let handle = token.clone();
+        // 处理完第一个文件后立即取消
+        let mut tree = ProjectTree::new("test", "./tests/examples", None)
+            .with_cancel_token(token)
+            .with_progress(move |event: crate::tree::progress::ProgressEvent| {
+                if let crate::tree::progress::ProgressEvent::ProcessedFiles(_) = event {
+                    handle.cancel();
+                }
+            });
+        assert!(matches!(tree.build(), Err(AruiError::Cancelled)));
+        // 保留已构建的部分项目树
+        let root = tree.root.as_ref().expect("partial tree");
+        assert!(root.children.as_ref().unwrap().len() <= 2);
+        assert!(matches!(tree.summarize(), Err(AruiError::Cancelled)));
+    }
+
+    #[test]
+    fn test_expand_excludes() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/a/kept.rs", "fn kept() {}\n")
+            .add_file("project/a/skip/inner.rs", "fn inner() {}\n");
+        let config = ProjectConfig::new().with_max_depth(1).add_exclude("a/skip");
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.build().expect("build failed");
+        // 展开时同样排除配置的路径
+        tree.expand("a").expect("expand failed");
+        assert!(tree.find("a/kept.rs").is_some());
+        assert!(tree.find("a/skip").is_none());
+    }
+
+    /// 获取路径对应目录的子节点名称
+    fn child_names(tree: &ProjectTree, path: &str) -> Vec<String> {
+        let node = tree.find(path).expect("node not found");
+        node.children
+            .iter()
+            .flatten()
+            .map(|child| child.name().to_string())
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_expand_with_config() {
+        use crate::tree::config::ChildOrder;
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("sub/b_dir")).unwrap();
+        fs::write(dir.join("sub/a.txt"), "a").unwrap();
+        fs::write(dir.join("sub/b_dir/c.txt"), "c").unwrap();
+        std::os::unix::fs::symlink(dir.join("sub/a.txt"), dir.join("sub/link")).unwrap();
+        let config = ProjectConfig::new()
+            .with_order(ChildOrder::Name)
+            .with_skip_symlinks(true);
+        let mut full = ProjectTree::new("test", dir.to_str().unwrap(), Some(config.clone()));
+        full.build().expect("build failed");
+        assert_eq!(child_names(&full, "sub"), vec!["a.txt", "b_dir"]);
+        // 展开时沿用配置的排序方式，并同样跳过符号链接
+        let config = config.with_max_depth(1);
+        let mut tree = ProjectTree::new("test", dir.to_str().unwrap(), Some(config));
+        tree.build().expect("build failed");
+        assert!(!tree.find("sub").unwrap().is_loaded());
+        tree.expand("sub").expect("expand failed");
+        assert_eq!(child_names(&tree, "sub"), child_names(&full, "sub"));
+        assert!(!tree.find("sub/b_dir").unwrap().is_loaded());
+    }
+
+    #[test]
+    fn test_expand_order() {
+        use crate::fs::MemoryFs;
+        use crate::tree::config::ChildOrder;
+        let fs = MemoryFs::new()
+            .add_file("project/a/z.rs", "fn z() {}\n")
+            .add_file("project/a/c.rs", "fn c() {}\n")
+            .add_file("project/a/m/y.rs", "fn y() {}\n")
+            .add_file("project/a/b/x.rs", "fn x() {}\n");
+        for (order, expected) in [
+            (ChildOrder::DirsFirst, ["b", "m", "c.rs", "z.rs"]),
+            (ChildOrder::Name, ["b", "c.rs", "m", "z.rs"]),
+        ] {
+            let config = ProjectConfig::new().with_order(order);
+            let mut full =
+                ProjectTree::new("test", "project", Some(config.clone())).with_fs(fs.clone());
+            full.build().expect("build failed");
+            assert_eq!(child_names(&full, "a"), expected);
+            // 浅构建后按需展开，子节点顺序与完整构建一致
+            let config = config.with_max_depth(1);
+            let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs.clone());
+            tree.build().expect("build failed");
+            tree.expand("a").expect("expand failed");
+            assert_eq!(child_names(&tree, "a"), expected);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tolerant_build() {
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/file.txt"), "hello").unwrap();
+        // 损坏的符号链接作为节点保留，仅记录警告
+        std::os::unix::fs::symlink(dir.join("missing"), dir.join("broken")).unwrap();
+        // 指向自身的符号链接无法获取元数据
+        std::os::unix::fs::symlink(dir.join("loop"), dir.join("loop")).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        let mut strict = ProjectTree::new("test", path.clone(), None);
+        assert!(strict.build().is_err());
+
+        let config = ProjectConfig::new().with_tolerant(true);
+        let mut tree = ProjectTree::new("test", path, Some(config));
+        tree.build().expect("build failed");
+        assert_eq!(tree.warnings.len(), 2);
+        let broken = tree.warnings.iter().find(|w| w.path.ends_with("broken"));
+        assert_eq!(broken.unwrap().kind, WarningKind::BrokenSymlink);
+        let looped = tree.warnings.iter().find(|w| w.path.ends_with("loop"));
+        assert_eq!(looped.unwrap().kind, WarningKind::Unreadable);
+        // 其余路径正常构建
+        assert_eq!(
+            tree.root.as_ref().unwrap().children.as_ref().unwrap().len(),
+            2
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_accounting() {
+        use std::os::unix::fs::symlink;
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/file.txt"), "hello").unwrap();
+        symlink("file.txt", dir.join("sub/link")).unwrap();
+        symlink("missing", dir.join("sub/broken")).unwrap();
+        symlink(dir.join("sub"), dir.join("dir_link")).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        // 损坏的符号链接不会导致构建失败
+        let tree = ProjectTree::try_plant("test", path, None).expect("plant failed");
+        let root = tree.root.as_ref().unwrap();
+        let link = root.find(dir.join("sub/link")).unwrap();
+        assert_eq!(link.symlink_target.as_deref(), Some("file.txt"));
+        assert!(!link.is_broken_symlink());
+        assert_eq!(link.summary.size, 5);
+        let broken = root.find(dir.join("sub/broken")).unwrap();
+        assert!(broken.is_symlink() && broken.is_broken_symlink());
+        assert_eq!(broken.summary.size, 0);
+        let sub = root.find(dir.join("sub")).unwrap();
+        assert_eq!(
+            (sub.summary.symlink_count, sub.summary.broken_symlink_count),
+            (2, 1)
+        );
+        // 目录链接自身及其下的链接均被计入
+        assert_eq!(
+            (
+                root.summary.symlink_count,
+                root.summary.broken_symlink_count
+            ),
+            (5, 2)
+        );
+        assert_eq!(tree.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_size_cap_warnings() {
+        let fs = MemoryFs::new()
+            .add_file("project/small.txt", "a\n")
+            .add_file("project/large.txt", "a\nb\nc\nd\n");
+        let config = ProjectConfig::new().with_max_file_size(4);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.build().expect("build failed");
+        assert!(tree.warnings.is_empty());
+        tree.summarize().expect("summarize failed");
+        assert_eq!(tree.warnings.len(), 1);
+        assert_eq!(tree.warnings[0].kind, WarningKind::SizeCapExceeded);
+        assert!(tree.warnings[0].path.ends_with("large.txt"));
+        // 超出上限的文件仅计入大小
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.summary.size, 10);
+        assert_eq!(root.summary.count, 1);
+        // analyze 时重置警告
+        tree.analyze().expect("analyze failed");
+        assert_eq!(tree.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_file_warning() {
+        let fs = MemoryFs::new().add_file("project/gone.txt", "a\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.build().expect("build failed");
+        // 构建后文件被删除，总结时仅记录一次警告与一次错误
+        tree.fs = Arc::new(MemoryFs::new().add_dir("project"));
+        let stats = tree.summarize().expect("summarize failed");
+        assert_eq!(stats.files_visited, 1);
+        assert_eq!(stats.errors_skipped, 1);
+        assert_eq!(tree.warnings.len(), 1);
+        assert_eq!(tree.root.as_ref().unwrap().summary.count, 0);
+    }
+
+    #[test]
+    fn test_word_counts() {
+        let fs = MemoryFs::new()
+            .add_file("project/docs/en.md", "Hello, world!\nSecond line\n")
+            .add_file("project/docs/zh.md", "你好世界\n")
+            .add_file("project/logo.png", b"\x89PNG\r\n\x1a\n\x00\x00".to_vec());
+        // 默认不统计
+        let tree = analyze(fs.clone(), None);
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!((root.summary.words, root.summary.chars), (0, 0));
+        let config = ProjectConfig::new().with_count_words(true);
+        let tree = analyze(fs, Some(config));
+        let root = tree.root.as_ref().unwrap();
+        let en = root.find("project/docs/en.md").unwrap();
+        assert_eq!((en.summary.words, en.summary.chars), (4, 26));
+        let zh = root.find("project/docs/zh.md").unwrap();
+        assert_eq!((zh.summary.words, zh.summary.chars), (4, 5));
+        // 二进制文件不计入
+        assert_eq!((root.summary.words, root.summary.chars), (8, 31));
+    }
+
+    #[test]
+    fn test_cache_options() {
+        let cache = Arc::new(SummaryCache::new());
+        let mut tree = ProjectTree::new("test", "./src", None).with_cache(cache.clone());
+        tree.analyze().expect("analyze failed");
+        assert_eq!(tree.root.as_ref().unwrap().summary.words, 0);
+        // 选项改变后不再复用按旧选项得到的总结信息
+        let config = ProjectConfig::new()
+            .with_count_words(true)
+            .with_count_tokens(true);
+        let mut tree =
+            ProjectTree::new("test", "./src", Some(config.clone())).with_cache(cache.clone());
+        tree.analyze().expect("analyze failed");
+        let summary = &tree.root.as_ref().unwrap().summary;
+        assert!(summary.words > 0 && summary.tokens > 0);
+        assert_eq!(cache.hits(), 0);
+        // 选项一致时复用缓存
+        let mut tree = ProjectTree::new("test", "./src", Some(config)).with_cache(cache.clone());
+        tree.analyze().expect("analyze failed");
+        assert!(cache.hits() > 0);
+        assert_eq!(tree.root.as_ref().unwrap().summary.words, summary.words);
+    }
+
+    #[test]
+    fn test_token_estimate() {
+        let fs = MemoryFs::new()
+            .add_file("project/a.rs", "fn main() {}\n")
+            .add_file("project/docs/b.md", "x".repeat(400))
+            .add_file("project/logo.png", b"\x89PNG\r\n\x1a\n\x00\x00".to_vec());
+        let tree = analyze(fs.clone(), None);
+        assert_eq!(tree.root.as_ref().unwrap().summary.tokens, 0);
+        let config = ProjectConfig::new().with_count_tokens(true);
+        let tree = analyze(fs, Some(config));
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.find("project/a.rs").unwrap().summary.tokens, 4);
+        assert_eq!(root.find("project/docs").unwrap().summary.tokens, 100);
+        assert_eq!(root.summary.tokens, 104);
+    }
+
+    #[test]
+    fn test_markers() {
+        let fs = MemoryFs::new()
+            .add_file(
+                "src/a.rs",
+                "// TODO: one\n// TODO two, FIXME\nlet todo = 1;\n",
+            )
+            .add_file("src/b.py", "# HACK\n# TODOS are not markers\n# XXX\n");
+        let mut tree = ProjectTree::new("test", "src", None).with_fs(fs.clone());
+        tree.analyze().expect("analyze failed");
+        assert!(tree.root.as_ref().unwrap().summary.markers.is_empty());
+        let config = ProjectConfig::new()
+            .with_default_markers()
+            .add_markers(["XXX"]);
+        let mut tree = ProjectTree::new("test", "src", Some(config)).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let a = &root.find("src/a.rs").unwrap().summary.markers;
+        assert_eq!((a["TODO"], a["FIXME"], a.get("HACK")), (2, 1, None));
+        let markers = &root.summary.markers;
+        assert_eq!(
+            (
+                markers["TODO"],
+                markers["FIXME"],
+                markers["HACK"],
+                markers["XXX"]
+            ),
+            (2, 1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_binary_files() {
+        let fs = MemoryFs::new()
+            .add_file("project/main.rs", "fn main() {}\n")
+            .add_file("project/logo.png", b"\x89PNG\r\n\x1a\n\x00\x00".to_vec())
+            .add_file("project/data.bin", vec![0xff, 0xfe, 0x41]);
+        let tree = analyze(fs, None);
+        // 二进制文件不统计行数，也不产生警告
+        assert!(tree.warnings.is_empty());
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.summary.is_binary, None);
+        assert_eq!(
+            root.find("project/logo.png").unwrap().summary.is_binary,
+            Some(true)
+        );
+        assert_eq!(
+            root.find("project/main.rs").unwrap().summary.is_binary,
+            Some(false)
+        );
+        assert_eq!((root.summary.text.files, root.summary.text.size), (1, 13));
+        assert_eq!(
+            (root.summary.binary.files, root.summary.binary.size),
+            (2, 13)
+        );
+        assert_eq!(root.summary.binary.lines, 0);
+    }
+
+    #[test]
+    fn test_encodings() {
+        let fs = MemoryFs::new()
+            .add_file("project/utf8.txt", "你好\n")
+            .add_file("project/bom.txt", b"\xef\xbb\xbfhi\n".to_vec())
+            .add_file("project/gbk.txt", b"\xc4\xe3\xba\xc3\n".to_vec())
+            .add_file("project/gbk2.txt", b"\xd6\xd0\xce\xc4\n".to_vec());
+        let tree = analyze(fs, None);
+        let root = tree.root.as_ref().unwrap();
+        let bom = root.find("project/bom.txt").unwrap();
+        assert_eq!(bom.summary.encoding, Some(Encoding::Utf8Bom));
+        assert_eq!(root.summary.encoding, None);
+        let by_encoding = &root.summary.by_encoding;
+        assert_eq!(by_encoding.len(), 3);
+        assert_eq!(by_encoding[&Encoding::Gbk], 2);
+        assert_eq!(by_encoding[&Encoding::Utf8], 1);
+    }
+
+    #[test]
+    fn test_lossy_text() {
+        let mut invalid_tail = "line\n".repeat(2000).into_bytes();
+        invalid_tail.extend_from_slice(b"caf\xe9\n");
+        let fs = MemoryFs::new()
+            .add_file("project/latin1.txt", b"caf\xe9\nna\xefve\n".to_vec())
+            .add_file("project/tail.txt", invalid_tail)
+            .add_file("project/utf8.txt", "你好\n");
+        let tree = analyze(fs, None);
+        let root = tree.root.as_ref().unwrap();
+        let latin1 = root.find("project/latin1.txt").unwrap();
+        assert_eq!(latin1.summary.is_binary, Some(false));
+        assert_eq!((latin1.summary.count, latin1.summary.lossy), (2, true));
+        // 无效字节位于判断二进制时检查的范围之外
+        let tail = root.find("project/tail.txt").unwrap();
+        assert_eq!((tail.summary.count, tail.summary.lossy), (2001, true));
+        assert!(!root.find("project/utf8.txt").unwrap().summary.lossy);
+        assert!(root.summary.lossy);
+        assert_eq!(root.summary.count, 2004);
+        assert!(tree.warnings.is_empty());
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn test_digest() {
+        let fs = MemoryFs::new()
+            .add_file("a/src/lib.rs", "fn a() {}\n")
+            .add_file("a/README", "")
+            .add_file("b/README", "")
+            .add_file("b/src/lib.rs", "fn a() {}\n");
+        let plant = |root: &str| {
+            let mut tree = ProjectTree::new("test", root, None).with_fs(fs.clone());
+            tree.analyze().expect("analyze failed");
+            tree
+        };
+        let (a, b) = (plant("a"), plant("b"));
+        let (a, b) = (a.root.as_ref().unwrap(), b.root.as_ref().unwrap());
+        // 内容相同的目录摘要相同，与子节点的顺序无关
+        assert!(a.summary.digest.is_some());
+        assert_eq!(a.summary.digest, b.summary.digest);
+        let readme = a.find("a/README").unwrap();
+        assert_eq!(
+            readme.summary.digest_hex().unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let changed = MemoryFs::new()
+            .add_file("a/src/lib.rs", "fn b() {}\n")
+            .add_file("a/README", "");
+        let mut tree = ProjectTree::new("test", "a", None).with_fs(changed);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_ne!(root.summary.digest, a.summary.digest);
+        assert_eq!(
+            root.find("a/README").unwrap().summary.digest,
+            readme.summary.digest
+        );
+
+        // 任一文件没有摘要时，其所在的各级目录均没有摘要
+        let config = ProjectConfig::new().with_max_file_size(5);
+        let mut tree = ProjectTree::new("test", "a", Some(config)).with_fs(fs.clone());
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert!(root.find("a/src/lib.rs").unwrap().summary.digest.is_none());
+        assert!(root.find("a/src").unwrap().summary.digest.is_none());
+        assert!(root.summary.digest.is_none());
+        assert_eq!(
+            root.find("a/README").unwrap().summary.digest,
+            readme.summary.digest
+        );
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_compressed_size() {
+        let fs = MemoryFs::new()
+            .add_file("project/repeated.txt", "a".repeat(1_000))
+            .add_file("project/large.txt", "b".repeat(10_000))
+            .add_file("project/empty", "");
+        let config = ProjectConfig::new().with_max_file_size(5_000);
+        let tree = analyze(fs, Some(config));
+        let root = tree.root.as_ref().unwrap();
+        let repeated = root.find("project/repeated.txt").unwrap();
+        assert!(repeated.summary.compressed_size < 100);
+        // 空内容同样包含 gzip 头尾
+        let empty = root.find("project/empty").unwrap();
+        assert_eq!(empty.summary.compressed_size, 20);
+        // 超出大小上限的文件按原始大小计入
+        let large = root.find("project/large.txt").unwrap();
+        assert_eq!(large.summary.compressed_size, 10_000);
+        assert_eq!(
+            root.summary.compressed_size,
+            repeated.summary.compressed_size + 20 + 10_000
+        );
+    }
+
+    #[test]
+    fn test_from_paths() {
+        let root = "./tests/examples/tree";
+        let paths = vec![
+            PathBuf::from("summary/test.rs"),
+            PathBuf::from("./summary/test.js"),
+            PathBuf::from("node/file/test_get_file_size.txt"),
+        ];
+        let mut tree = ProjectTree::from_paths("test", root, paths).expect("assemble failed");
+        tree.summarize().expect("summarize failed");
+        tree.print_tree();
+        let root = tree.root.as_ref().unwrap();
+        let children = root.children.as_ref().unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].path, "./tests/examples/tree/summary");
+        assert_eq!(children[0].children.as_ref().unwrap().len(), 2);
+        // 仅统计列表中的文件
+        let expected: u64 = [
+            "summary/test.rs",
+            "summary/test.js",
+            "node/file/test_get_file_size.txt",
+        ]
+        .iter()
+        .map(|path| {
+            fs::metadata(Path::new("./tests/examples/tree").join(path))
+                .unwrap()
+                .len()
+        })
+        .sum();
+        assert_eq!(root.summary.size, expected);
+
+        // 根路径之外的路径无法组装
+        let outside = vec![PathBuf::from("../root.rs")];
+        assert!(ProjectTree::from_paths("test", "./src", outside).is_err());
+    }
+
+    #[test]
+    fn test_from_path_reader() {
+        let input = std::io::Cursor::new(
+            "summary/test.rs\r\n\n./summary/test.js\nnode/file/test_get_file_size.txt\n",
+        );
+        let tree = ProjectTree::from_path_reader("test", "./tests/examples/tree", input)
+            .expect("read paths failed");
+        let root = tree.root.as_ref().unwrap();
+        assert!(root.find("./tests/examples/tree/summary/test.rs").is_some());
+        assert_eq!(root.children.as_ref().unwrap().len(), 2);
+        // 已获取总结信息
+        assert!(root.summary.summarized_at.is_some());
+        assert!(root.summary.size > 0);
+
+        let outside = std::io::Cursor::new("../root.rs\n");
+        assert!(ProjectTree::from_path_reader("test", "./src", outside).is_err());
+    }
+
+    #[test]
+    fn test_memory_fs() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/lib.rs", "pub mod a;\npub mod b;\n")
+            .add_file("project/src/a.rs", "")
+            .add_file("project/README.md", "# test\n")
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.is_valid());
+        tree.build().expect("build failed");
+        tree.summarize().expect("summarize failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.children.as_ref().unwrap().len(), 3);
+        assert_eq!(root.summary.size, 29);
+        assert_eq!(root.summary.count, 3);
+
+        let invalid =
+            ProjectTree::new("test", "other", None).with_fs(MemoryFs::new().add_dir("project"));
+        assert!(!invalid.is_valid());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/lib.rs", "pub mod a;\n// TODO: b\n")
+            .add_file("project/logo.bin", [0u8, 159, 146, 150])
+            .add_dir("project/empty");
+        let config = ProjectConfig::default().with_default_markers();
+        let tree = analyze(fs, Some(config));
+        let json = serde_json::to_string(&tree).expect("serialize failed");
+        let loaded: ProjectTree = serde_json::from_str(&json).expect("deserialize failed");
+        assert_eq!(loaded.id, tree.id);
+        assert_eq!(loaded.config, tree.config);
+        let root = loaded.root.as_ref().unwrap();
+        assert_eq!(root.children.as_ref().unwrap().len(), 3);
+        assert_eq!(root.summary.count, 2);
+        assert_eq!(root.summary.binary.files, 1);
+        assert_eq!(
+            root.summary.markers,
+            tree.root.as_ref().unwrap().summary.markers
+        );
+        // 运行时状态不参与序列化
+        assert!(loaded.cache.is_none() && loaded.progress.is_none());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/README.md", "# test\n");
+        let tree = analyze(fs, None);
+        let pretty = tree.to_json(true).expect("export failed");
+        let compact = tree.to_json(false).expect("export failed");
+        assert!(pretty.contains('\n') && !compact.contains('\n'));
+        let mut written = Vec::new();
+        tree.write_json(&mut written).expect("export failed");
+        let value: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(value["name"], "test");
+        let root = &value["root"];
+        assert_eq!(root["summary"]["count"], 2);
+        assert_eq!(root["children"].as_array().unwrap().len(), 2);
+        assert!(root["children"][1]["children"].is_null());
+        assert!(value.get("fs").is_none() && value.get("cache").is_none());
+    }
+
+    /// 包含各类节点、配置与警告的项目树，用于验证导出格式
+    #[cfg(feature = "serde")]
+    fn schema_fixture() -> ProjectTree {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n// TODO: args\n")
+            .add_file("project/src/lib.py", "def f():\n    return 1.5\n")
+            .add_file("project/data.bin", [0u8, 159, 146, 150])
+            .add_file("project/big.txt", "x".repeat(64))
+            .add_dir("project/empty");
+        let config = ProjectConfig::default()
+            .with_default_markers()
+            .with_count_words(true)
+            .with_max_file_size(32);
+        let mut tree = analyze(fs, Some(config));
+        assert!(!tree.warnings.is_empty());
+        // 以整数为键的统计表，JSON 中以字符串表示
+        #[cfg(feature = "unix-metadata")]
+        tree.root
+            .as_mut()
+            .unwrap()
+            .summary
+            .by_owner
+            .insert(1000, ExtStats::default());
+        tree
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_options() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/README.md", "# test\n");
+        let mut tree = analyze(fs, None);
+        // 不筛选字段时与 `to_json` 完全一致
+        assert_eq!(
+            tree.to_json_with(&JsonOptions::new().with_pretty(true))
+                .unwrap(),
+            tree.to_json(true).unwrap()
+        );
+
+        let snapshot = JsonOptions::new().without_timestamps();
+        let first = tree.to_json_with(&snapshot).expect("export failed");
+        tree.summarize().expect("summarize failed");
+        assert_eq!(tree.to_json_with(&snapshot).unwrap(), first);
+        let value: serde_json::Value = serde_json::from_str(&first).unwrap();
+        let summary = value["root"]["summary"].as_object().unwrap();
+        assert!(summary.contains_key("size"));
+        assert!(!summary.contains_key("summarized_at") && !summary.contains_key("newest_mtime"));
+
+        let options = JsonOptions::new()
+            .with_pretty(true)
+            .with_fields(["size", "lines"]);
+        let mut written = Vec::new();
+        tree.write_json_with(&mut written, &options)
+            .expect("export failed");
+        assert!(written.contains(&b'\n'));
+        let value: serde_json::Value = serde_json::from_slice(&written).unwrap();
+        let file = &value["root"]["children"][0]["children"][0];
+        assert_eq!(file["path"], "project/src/main.rs");
+        assert_eq!(
+            file["summary"],
+            serde_json::json!({ "size": 13, "lines": {
+                "code": 1, "comments": 0, "blanks": 0
+            }})
+        );
+        // 筛选后的结果仍可重新加载，缺少的字段取默认值
+        let loaded = ProjectTree::from_json(std::str::from_utf8(&written).unwrap()).unwrap();
+        assert_eq!(loaded.root.unwrap().summary.size, 20);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_schema_round_trip() {
+        let tree = schema_fixture();
+        for pretty in [false, true] {
+            let json = tree.to_json(pretty).unwrap();
+            let loaded = ProjectTree::from_json(&json).expect("import failed");
+            assert_eq!(loaded.to_json(pretty).unwrap(), json);
+        }
+
+        // 忽略无法识别的字段
+        let json = tree.to_json(false).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], crate::tree::schema::SCHEMA_VERSION);
+        value["added_later"] = serde_json::json!({ "nested": [1, 2] });
+        value["root"]["summary"]["added_later"] = serde_json::json!(true);
+        let loaded = ProjectTree::from_json(&value.to_string()).expect("import failed");
+        assert_eq!(loaded.to_json(false).unwrap(), json);
+
+        // 拒绝更高的格式版本
+        value["schema_version"] = serde_json::json!(crate::tree::schema::SCHEMA_VERSION + 1);
+        assert!(matches!(
+            ProjectTree::from_json(&value.to_string()),
+            Err(AruiError::UnsupportedSchema(_))
+        ));
+        value.as_object_mut().unwrap().remove("schema_version");
+        assert!(ProjectTree::from_json(&value.to_string()).is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_schema_round_trip() {
+        let tree = schema_fixture();
+        let yaml = tree.to_yaml().unwrap();
+        assert!(yaml.starts_with("schema_version: 1\n"));
+        let loaded = ProjectTree::from_yaml(&yaml).expect("import failed");
+        assert_eq!(loaded.to_yaml().unwrap(), yaml);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/a.rs", "fn a() {}\n")
+            .add_file("project/b.py", "def b():\n    pass\n")
+            .add_file("project/c.md", "# c\n");
+        let tree = analyze(fs, None);
+        let yaml = tree.to_yaml().expect("export failed");
+        assert!(yaml.contains("name: test"));
+        let value: serde_yaml_ng::Value = serde_yaml_ng::from_str(&yaml).unwrap();
+        let by_extension = value["root"]["summary"]["by_extension"]
+            .as_mapping()
+            .unwrap();
+        let keys = by_extension
+            .keys()
+            .filter_map(|key| key.as_str())
+            .collect::<Vec<_>>();
+        // 统计表按键排序，多次导出的结果一致
+        assert_eq!(keys, ["md", "py", "rs"]);
+        assert_eq!(tree.to_yaml().unwrap(), yaml);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_flare_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/README.md", "# test\n\nhello\n")
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.to_flare(FlareMetric::Size).is_err());
+        tree.analyze().expect("analyze failed");
+        let flare: serde_json::Value =
+            serde_json::from_str(&tree.to_flare(FlareMetric::Size).unwrap()).unwrap();
+        assert_eq!(
+            flare,
+            serde_json::json!({
+                "name": "test",
+                "children": [
+                    { "name": "empty", "children": [] },
+                    { "name": "src", "children": [{ "name": "main.rs", "value": 13 }] },
+                    { "name": "README.md", "value": 14 }
+                ]
+            })
+        );
+        let flare: serde_json::Value =
+            serde_json::from_str(&tree.to_flare(FlareMetric::Lines).unwrap()).unwrap();
+        assert_eq!(flare["children"][2]["value"], 3);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_change_log() {
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("project")).unwrap();
+        let project = dir.join("project");
+        fs::write(project.join("kept.txt"), "hello\n").unwrap();
+        fs::write(project.join("removed.txt"), "bye\n").unwrap();
+        let log = dir.join("changes.ndjson");
+        let mut tree = ProjectTree::new("test", project.to_string_lossy(), None);
+        tree.analyze().expect("analyze failed");
+
+        // 没有变更时不写入日志
+        assert!(tree.refresh_to_log(&log).unwrap().is_empty());
+        assert!(!log.exists());
+
+        fs::write(project.join("kept.txt"), "hello\nworld\n").unwrap();
+        fs::remove_file(project.join("removed.txt")).unwrap();
+        tree.refresh_to_log(&log).unwrap();
+        fs::write(project.join("added.txt"), "new\n").unwrap();
+        tree.refresh_to_log(&log).unwrap();
+
+        // 每次刷新的事件依次追加到日志末尾
+        let contents = fs::read_to_string(&log).unwrap();
+        let events: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, String)> = events
+            .iter()
+            .map(|event| {
+                let path = event["path"].as_str().unwrap();
+                let name = Path::new(path).file_name().unwrap().to_string_lossy();
+                (event["event"].as_str().unwrap(), name.into_owned())
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("removed", "removed.txt".to_string()),
+                ("modified", "kept.txt".to_string()),
+                ("added", "added.txt".to_string()),
+            ]
+        );
+        assert_eq!(events[0]["before"]["size"], 4);
+        assert!(events[0]["after"].is_null());
+        assert_eq!(events[1]["before"]["lines"], 1);
+        assert_eq!(events[1]["after"]["lines"], 2);
+        assert!(events[2]["before"].is_null());
+        assert!(events[2]["time"].as_u64().unwrap() >= events[0]["time"].as_u64().unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_ndjson_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/README.md", "# test\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.write_ndjson(Vec::new()).is_err());
+        tree.analyze().expect("analyze failed");
+        let mut output = Vec::new();
+        tree.write_ndjson(&mut output).expect("export failed");
+        let records = output
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        // 先序遍历：根节点、src、main.rs、README.md
+        let paths = records
+            .iter()
+            .map(|record| record["path"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "project",
+                "project/src",
+                "project/src/main.rs",
+                "project/README.md"
+            ]
+        );
+        assert_eq!(records[0]["children"], 2);
+        assert_eq!(records[2]["depth"], 2);
+        assert!(records[2]["children"].is_null());
+        assert_eq!(records[0]["summary"]["count"], 2);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_export() {
+        use crate::fs::MemoryFs;
+        use ::parquet::file::reader::{FileReader, SerializedFileReader};
+        use ::parquet::record::RowAccessor;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/data.bin", [0u8, 159, 146, 150]);
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(matches!(
+            tree.write_parquet(Vec::new()),
+            Err(AruiError::NotBuilt)
+        ));
+        tree.analyze().expect("analyze failed");
+        let dir = TempDir::new();
+        let path = dir.join("tree.parquet");
+        let file = std::fs::File::create(&path).unwrap();
+        assert_eq!(tree.write_parquet(file).unwrap(), 2);
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        let main = rows
+            .iter()
+            .find(|row| row.get_string(0).unwrap() == "project/src/main.rs")
+            .unwrap();
+        assert_eq!(main.get_string(2).unwrap(), "Rust");
+        assert_eq!(main.get_ulong(3).unwrap(), 13);
+        assert_eq!(main.get_ulong(6).unwrap(), 1);
+        assert!(!main.get_bool(9).unwrap());
+        let data = rows
+            .iter()
+            .find(|row| row.get_string(0).unwrap() == "project/data.bin")
+            .unwrap();
+        assert!(data.get_bool(9).unwrap());
+        assert!(data.get_string(2).is_err());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/src/lib.rs", "pub mod a;\n\n")
+            .add_file("project/README.md", "# test\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        let dir = TempDir::new();
+        let path = dir.join("tree.db");
+        assert!(matches!(tree.to_sqlite(&path), Err(AruiError::NotBuilt)));
+        tree.analyze().expect("analyze failed");
+        assert_eq!(tree.to_sqlite(&path).unwrap(), 5);
+        // 再次导出同一项目树时替换之前的内容，其他项目树的结果追加保存
+        assert_eq!(tree.to_sqlite(&path).unwrap(), 5);
+        let mut other = ProjectTree::new("other", "project", None);
+        other.fs = tree.fs.clone();
+        other.analyze().expect("analyze failed");
+        other.to_sqlite(&path).unwrap();
+
+        let connection = rusqlite::Connection::open(&path).unwrap();
+        let query = |sql: &str| -> i64 {
+            connection
+                .query_row(sql, [&tree.id], |row| row.get(0))
+                .unwrap()
+        };
+        assert_eq!(query("SELECT COUNT(*) FROM scans WHERE id <> ?1"), 1);
+        assert_eq!(query("SELECT COUNT(*) FROM nodes WHERE scan = ?1"), 5);
+        assert_eq!(query("SELECT COUNT(*) FROM nodes WHERE scan <> ?1"), 5);
+        assert_eq!(
+            query(
+                "SELECT s.code FROM nodes n JOIN summaries s ON s.node = n.id
+                 WHERE n.scan = ?1 AND n.path = 'project/src/main.rs'"
+            ),
+            1
+        );
+        assert_eq!(
+            query(
+                "SELECT e.files FROM nodes n JOIN extensions e ON e.node = n.id
+                 WHERE n.scan = ?1 AND n.parent IS NULL AND e.extension = 'rs'"
+            ),
+            2
+        );
+        let parent: String = connection
+            .query_row(
+                "SELECT p.name FROM nodes n JOIN nodes p ON p.id = n.parent
+                 WHERE n.scan = ?1 AND n.name = 'lib.rs'",
+                [&tree.id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(parent, "src");
+    }
+
+    #[cfg(feature = "index")]
+    #[test]
+    fn test_metadata_index() {
+        use crate::fs::MemoryFs;
+        let deep = format!("project/{}/data.csv", "nested-directory/".repeat(8));
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file(&deep, vec![b'x'; 1 << 20])
+            .add_dir("project/empty");
+        let tree = analyze(fs, None);
+        let mut archive = Vec::new();
+        assert_eq!(index::write(&tree, &mut archive).unwrap(), 13);
+        // 文件内容不会写入归档
+        assert!(archive.len() < 1 << 16);
+
+        let loaded = index::read(archive.as_slice()).expect("read failed");
+        assert_eq!((&loaded.id, &loaded.name), (&tree.id, &tree.name));
+        let (root, expected) = (loaded.root.as_ref().unwrap(), tree.root.as_ref().unwrap());
+        assert_eq!(
+            serde_json::to_value(root).unwrap(),
+            serde_json::to_value(expected).unwrap()
+        );
+        assert_eq!(root.find(&deep).unwrap().summary.size, 1 << 20);
+
+        // 标准的 tar 工具同样可以读取归档中的目录结构
+        let mut entries = tar::Archive::new(archive.as_slice());
+        let paths: Vec<String> = entries
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                entry
+                    .unwrap()
+                    .path()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(paths[0], index::INDEX_ENTRY);
+        assert!(paths.contains(&"project/src/main.rs".to_string()));
+
+        assert!(matches!(
+            index::read(&b"not an archive"[..]),
+            Err(AruiError::InvalidIndex(_)) | Err(AruiError::IO(_))
+        ));
+    }
+
+    #[cfg(all(feature = "json", feature = "msgpack", feature = "gzip"))]
+    #[test]
+    fn test_compressed_exports() {
+        use crate::fs::MemoryFs;
+        use std::io::Read;
+        let mut memory = MemoryFs::new();
+        for index in 0..200 {
+            memory = memory.add_file(format!("project/src/module_{}.rs", index), "fn main() {}\n");
+        }
+        let tree = analyze(memory, None);
+
+        let mut compressed = Vec::new();
+        tree.write_json_gz(&mut compressed).unwrap();
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, tree.to_json(false).unwrap());
+        assert!(compressed.len() * 5 < json.len());
+
+        let dir = TempDir::new();
+        let (plain, gzipped) = (dir.join("plain"), dir.join("gzipped"));
+        tree.save_cache(&plain).unwrap();
+        tree.save_cache_compressed(&gzipped).unwrap();
+        assert!(fs::metadata(&gzipped).unwrap().len() < fs::metadata(&plain).unwrap().len());
+        // 读取时自动识别是否经过压缩
+        let restored = ProjectTree::load_cache(&gzipped).expect("load failed");
+        assert_eq!(
+            restored.to_json(false).unwrap(),
+            tree.to_json(false).unwrap()
+        );
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_binary_cache() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/data.bin", [0u8, 159, 146, 150]);
+        let tree = analyze(fs, None);
+        let dir = TempDir::new();
+        let path = dir.join("tree.cache");
+        tree.save_cache(&path).expect("save failed");
+        let restored = ProjectTree::load_cache(&path).expect("load failed");
+        assert_eq!(restored.id, tree.id);
+        let (root, expected) = (restored.root.unwrap(), tree.root.as_ref().unwrap());
+        assert_eq!(root.summary.count, expected.summary.count);
+        assert_eq!(root.summary.by_extension, expected.summary.by_extension);
+        assert_eq!(
+            root.find("project/src/main.rs").unwrap().summary.lines.code,
+            1
+        );
+
+        // 以字段名为键写入，不依赖字段的顺序
+        let mut contents = std::fs::read(&path).unwrap();
+        assert!(contents.windows(7).any(|window| window == b"summary"));
+        // 格式版本不一致或文件损坏时返回错误
+        contents[4] += 1;
+        std::fs::write(&path, &contents).unwrap();
+        assert!(matches!(
+            ProjectTree::load_cache(&path),
+            Err(AruiError::InvalidCache(_))
+        ));
+        std::fs::write(&path, b"ARUI\x01\x00\x00\x00garbage").unwrap();
+        assert!(ProjectTree::load_cache(&path).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_manifest() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n\n// entry\n")
+            .add_file("project/docs/guide/intro.md", "# intro\n")
+            .add_file("project/README.md", "# test\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.to_toml_manifest().is_err());
+        tree.analyze().expect("analyze failed");
+        let manifest = tree.to_toml_manifest().expect("export failed");
+        let table = manifest.parse::<toml::Table>().unwrap();
+        assert_eq!(table["project"]["name"].as_str(), Some("test"));
+        assert_eq!(table["project"]["files"].as_integer(), Some(3));
+        assert_eq!(table["project"]["lines"].as_integer(), Some(5));
+        let directories = table["directories"].as_table().unwrap();
+        assert_eq!(directories.keys().collect::<Vec<_>>(), ["docs", "src"]);
+        assert_eq!(directories["docs"]["dirs"].as_integer(), Some(1));
+        assert_eq!(directories["src"]["code"].as_integer(), Some(1));
+        assert_eq!(directories["src"]["blanks"].as_integer(), Some(1));
+        assert_eq!(directories["src"]["comments"].as_integer(), Some(1));
+    }
+
+    #[test]
+    fn test_validate_against_fs() {
+        use crate::fs::MemoryFs;
+        let files = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/src/lib.rs", "")
+            .add_file("project/docs/guide.md", "# guide\n")
+            .add_file("project/target/out.bin", "");
+        let config = ProjectConfig {
+            exclude: vec!["target".to_string()],
+            ..Default::default()
+        };
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(files.clone());
+        assert!(tree.validate_against_fs().is_err());
+        tree.analyze().expect("analyze failed");
+        let freshness = tree.validate_against_fs().unwrap();
+        assert!(freshness.is_fresh());
+        assert_eq!(freshness.checked, 6);
+
+        // 修改文件、在目录中新增文件，排除的路径不受影响
+        tree.fs = Arc::new(
+            files
+                .add_file("project/src/main.rs", "fn main() { run() }\n")
+                .add_file("project/docs/api.md", "")
+                .add_file("project/target/new.bin", ""),
+        );
+        let freshness = tree.validate_against_fs().unwrap();
+        assert_eq!(freshness.stale, ["project/docs", "project/src/main.rs"]);
+        assert_eq!(tree.refresh_stale().unwrap(), freshness);
+        assert!(tree.validate_against_fs().unwrap().is_fresh());
+        assert_eq!(tree.root.as_ref().unwrap().summary.file_count, 4);
+    }
+
+    #[test]
+    fn test_validate_after_expand() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/src/util/mod.rs", "pub fn f() {}\n");
+        let config = ProjectConfig::new().with_max_depth(1);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        // 展开后的节点与文件系统一致，不被视为过期
+        tree.expand("src").expect("expand failed");
+        tree.expand("src/util").expect("expand failed");
+        let freshness = tree.validate_against_fs().unwrap();
+        assert!(freshness.is_fresh());
+        assert_eq!(freshness.checked, 5);
+    }
+
+    #[test]
+    fn test_subtree_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/src/util/mod.rs", "pub fn f() {}\n")
+            .add_file("project/docs/guide.md", "# guide\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.subtree("src").is_err());
+        tree.analyze().expect("analyze failed");
+        assert!(tree.subtree("not_exist").is_err());
+
+        let src = tree.subtree("src").unwrap();
+        assert_eq!(src.path, "project/src");
+        assert_eq!(src.root.as_ref().unwrap().summary.file_count, 2);
+        assert_eq!(
+            src.to_flat_list().unwrap(),
+            "project/src\t27\t2\nproject/src/main.rs\t13\t1\n\
+             project/src/util\t14\t1\nproject/src/util/mod.rs\t14\t1\n"
+        );
+        // 子树同样可以继续选取子树
+        let util = src.subtree("util").unwrap();
+        let mut output = Vec::new();
+        util.to_csv(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn test_flat_list() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/b.txt", "b\n")
+            .add_file("project/a/tab\tname.txt", "x\ny\n")
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.to_flat_list().is_err());
+        tree.analyze().expect("analyze failed");
+        let list = tree.to_flat_list().unwrap();
+        assert_eq!(
+            list.lines().collect::<Vec<_>>(),
+            [
+                "project\t6\t3",
+                "project/a\t4\t2",
+                "project/a/tab\\tname.txt\t4\t2",
+                "project/b.txt\t2\t1",
+                "project/empty\t0\t0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_xml_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/notes.txt", "a\nb\n")
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("a<b>", "project", None).with_fs(fs);
+        assert!(tree.to_xml(Vec::new()).is_err());
+        tree.analyze().expect("analyze failed");
+        let mut output = Vec::new();
+        tree.to_xml(&mut output).expect("export failed");
+        let output = String::from_utf8(output).unwrap();
+        let expected = [
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            "<project name=\"a&lt;b&gt;\">",
+            "  <directory name=\"project\" path=\"project\" type=\"directory\" size=\"17\" lines=\"3\" files=\"2\">",
+            "    <directory name=\"empty\" path=\"project/empty\" type=\"directory\" size=\"0\" lines=\"0\" files=\"0\"/>",
+            "    <directory name=\"src\" path=\"project/src\" type=\"directory\" size=\"13\" lines=\"1\" files=\"1\">",
+            "      <file name=\"main.rs\" path=\"project/src/main.rs\" type=\"file\" size=\"13\" lines=\"1\" extension=\"rs\" language=\"Rust\"/>",
+            "    </directory>",
+            "    <file name=\"notes.txt\" path=\"project/notes.txt\" type=\"file\" size=\"4\" lines=\"2\" extension=\"txt\" language=\"Text\"/>",
+            "  </directory>",
+            "</project>",
+        ];
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n\n// entry\n")
+            .add_file("project/src/a.rs", "")
+            .add_file("project/data.bin", [0u8, 159, 146, 150])
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        let mut output = Vec::new();
+        assert!(tree.to_csv(&mut output).is_err());
+        tree.analyze().expect("analyze failed");
+        tree.to_csv(&mut output).expect("export failed");
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        // 表头与每个文件一行，目录不输出
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], csv::HEADER.join(","));
+        assert!(lines[1].starts_with("project/src/a.rs,rs,Rust,0,"));
+        assert!(lines[2].starts_with("project/src/main.rs,rs,Rust,23,"));
+        assert!(lines[2].contains(",3,1,1,1,false,"));
+        assert!(lines[3].starts_with("project/data.bin,bin,,4,"));
+        assert!(lines[3].contains(",true,"));
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn test_from_archive() {
+        let dir = TempDir::new();
+        let archive_path = dir.join("summary.tar");
+        let mut builder = tar::Builder::new(fs::File::create(&archive_path).unwrap());
+        builder
+            .append_dir_all("summary", "./tests/examples/tree/summary")
+            .unwrap();
+        builder.finish().unwrap();
+
+        let archived = ProjectTree::from_archive(&archive_path).expect("archive failed");
+        let planted = ProjectTree::try_plant("test", "./tests/examples/tree/summary", None)
+            .expect("plant failed");
+        let archived_root = archived.root.as_ref().unwrap();
+        let planted_root = planted.root.as_ref().unwrap();
+        assert_eq!(archived.path, "summary.tar");
+        // 与直接从磁盘构建的结果一致
+        let archived_dir = &archived_root.children.as_ref().unwrap()[0];
+        assert_eq!(
+            archived_dir.children.as_ref().unwrap().len(),
+            planted_root.children.as_ref().unwrap().len()
+        );
+        assert_eq!(archived_root.summary.size, planted_root.summary.size);
+        assert_eq!(archived_root.summary.count, planted_root.summary.count);
+    }
+
+    #[test]
+    fn test_du_output() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/src/util/mod.rs", "pub fn a() {}\n")
+            .add_file("project/README.md", "# test\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.to_du(false, Vec::new()).is_err());
+        tree.analyze().expect("analyze failed");
+
+        let mut output = Vec::new();
+        tree.to_du(false, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "14\tproject/src/util\n27\tproject/src\n34\tproject\n"
+        );
+        let mut output = Vec::new();
+        tree.to_du(true, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "14\tproject/src/util/mod.rs");
+        assert_eq!(lines[5], "34\tproject");
+    }
+
+    #[test]
+    fn test_language_table() {
+        use crate::fs::MemoryFs;
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "// entry\nfn main() {}\n\n")
+            .add_file("project/src/lib.rs", "pub mod a;\n")
+            .add_file("project/run.py", "print(1)\n")
+            .add_file("project/data.bin", [0u8, 159, 146, 150]);
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.to_language_table(TableStyle::Cloc).is_err());
+        tree.analyze().expect("analyze failed");
+
+        let table = tree.to_language_table(TableStyle::Cloc).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 8);
+        // 按代码行数降序排列，无法识别语言的文件不计入
+        assert!(lines[3].starts_with("Rust "));
+        assert!(lines[3].ends_with("2              1              1              2"));
+        assert!(lines[4].starts_with("Python "));
+        assert!(lines[6].starts_with("SUM: "));
+        assert!(lines[6].ends_with("3              1              1              3"));
+
+        let table = tree.to_language_table(TableStyle::Tokei).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[3].starts_with(" Python "));
+        assert!(lines[6].starts_with(" Total "));
+        assert!(lines[6].ends_with("5            3            1            1"));
+    }
+
+    #[test]
+    fn test_deterministic_order() {
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree/node", None);
+        tree.build().expect("build failed");
+        let names: Vec<&str> = tree
+            .root
+            .as_ref()
+            .unwrap()
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(TreeNode::name)
+            .collect();
+        // 目录在前，再按名称排序
+        assert_eq!(names, vec!["count", "file", "test_dir"]);
+        let test_dir = &tree.root.as_ref().unwrap().children.as_ref().unwrap()[2];
+        let names: Vec<&str> = test_dir
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(TreeNode::name)
+            .collect();
+        assert_eq!(names, vec!["file1.txt", "file2.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_cycle() {
+        use crate::tree::node::NodeMarker;
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::write(dir.join("a/file.txt"), "hello").unwrap();
+        // a/loop -> ..，指向根目录形成循环
+        std::os::unix::fs::symlink("..", dir.join("a/loop")).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        let mut tree = ProjectTree::new("test", path.clone(), None);
+        tree.build().expect("build failed");
+        tree.summarize().expect("summarize failed");
+        let a = &tree.root.as_ref().unwrap().children.as_ref().unwrap()[0];
+        let looped = a
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|child| child.name() == "loop")
+            .unwrap();
+        assert!(matches!(
+            looped.marker,
+            Some(NodeMarker::CycleDetected { .. })
+        ));
+        assert!(looped.children.as_ref().unwrap().is_empty());
+        assert_eq!(tree.root.as_ref().unwrap().summary.size, 5);
+
+        // 跳过符号链接时不包含链接节点
+        let config = ProjectConfig::new().with_skip_symlinks(true);
+        let mut tree = ProjectTree::new("test", path, Some(config));
+        tree.build().expect("build failed");
+        let a = &tree.root.as_ref().unwrap().children.as_ref().unwrap()[0];
+        assert_eq!(a.children.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_build_stats() {
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree/summary", None);
+        let stats = tree.build().expect("build failed");
+        assert_eq!(stats.dirs_visited, 1);
+        assert_eq!(stats.files_visited, 3);
+        assert_eq!(stats.bytes_read, 0);
+        let stats = tree.summarize().expect("summarize failed");
+        assert_eq!(stats.files_visited, 3);
+        assert_eq!(stats.bytes_read, tree.root.as_ref().unwrap().summary.size);
+        assert_eq!(stats.errors_skipped, 0);
+    }
+
+    #[test]
+    fn test_breadth_first_build() {
+        use crate::tree::config::WalkStrategy;
+        use std::sync::Mutex;
+        let dirs = Arc::new(Mutex::new(Vec::new()));
+        let sink = dirs.clone();
+        let config = ProjectConfig::new().with_strategy(WalkStrategy::BreadthFirst);
+        let mut bfs = ProjectTree::new("test", "./tests/examples/tree", Some(config))
+            .with_progress(move |event: crate::tree::progress::ProgressEvent| {
+                if let crate::tree::progress::ProgressEvent::EnteredDir(path) = event {
+                    sink.lock().unwrap().push(path.to_string());
+                }
+            });
+        bfs.build().expect("build failed");
+        bfs.summarize().expect("summarize failed");
+        // 按层级顺序进入目录
+        let depths: Vec<usize> = dirs
+            .lock()
+            .unwrap()
+            .iter()
+            .take(7)
+            .map(|path| Path::new(path).components().count())
+            .collect();
+        assert!(depths.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // 与深度优先的构建结果一致
+        let dfs =
+            ProjectTree::try_plant("test", "./tests/examples/tree", None).expect("plant failed");
+        let bfs_root = bfs.root.as_ref().unwrap();
+        let dfs_root = dfs.root.as_ref().unwrap();
+        assert_eq!(bfs_root.summary.size, dfs_root.summary.size);
+        assert_eq!(bfs_root.summary.count, dfs_root.summary.count);
+        assert_eq!(paths(bfs_root), paths(dfs_root));
+    }
+
+    /// 按先序收集节点路径
+    fn paths(node: &TreeNode) -> Vec<String> {
+        let mut result = vec![node.path.clone()];
+        for child in node.children.iter().flatten() {
+            result.extend(paths(child));
+        }
+        result
+    }
+
+    #[test]
+    fn test_analyze() {
+        let mut separate = ProjectTree::new("test", "./tests/examples/tree", None);
+        let build_stats = separate.build().expect("build failed");
+        let summary_stats = separate.summarize().expect("summarize failed");
+
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", None);
+        let stats = tree.analyze().expect("analyze failed");
+        // 一次遍历即可访问到所有文件，并读取相同的字节数
+        assert_eq!(stats.files_visited, build_stats.files_visited);
+        assert_eq!(stats.dirs_visited, build_stats.dirs_visited);
+        assert_eq!(stats.bytes_read, summary_stats.bytes_read);
+
+        let root = tree.root.as_ref().unwrap();
+        let expected = separate.root.as_ref().unwrap();
+        assert_eq!(root.summary.size, expected.summary.size);
+        assert_eq!(root.summary.count, expected.summary.count);
+        assert!(root.summary.summarized_at.is_some());
+        // 子目录同样完成了汇总
+        for (child, expected) in root
+            .children
+            .iter()
+            .flatten()
+            .zip(expected.children.iter().flatten())
+        {
+            assert_eq!(child.summary.size, expected.summary.size);
+            assert_eq!(child.summary.count, expected.summary.count);
+        }
+    }
+
+    #[test]
+    fn test_language_stats() {
+        let fs = MemoryFs::new()
+            .add_file("project/src/lib.rs", "mod a;\nmod b;\n")
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file(
+                "project/bin/run",
+                "#!/usr/bin/env python3\nprint(1)\nprint(2)\n",
+            )
+            .add_file("project/LICENSE", "MIT\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        assert!(tree.language_stats().is_empty());
+        tree.analyze().expect("analyze failed");
+        let stats = tree.language_stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].0, Language::Python);
+        assert_eq!(stats[0].1.lines, 3);
+        assert_eq!(stats[1].0, Language::Rust);
+        assert_eq!(stats[1].1.files, 2);
+        let run = &tree.find("bin/run").unwrap().summary;
+        assert_eq!(run.language, Some(Language::Python));
+        // 文件节点不保存按语言划分的统计信息
+        assert!(run.by_language.is_empty());
+        // 无法识别语言的文件不计入
+        assert!(tree.find("LICENSE").unwrap().summary.language.is_none());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_summarize() {
+        use crate::tree::session::AnalysisSession;
+        // 构建时逐个读取的结果作为对照
+        let mut expected = ProjectTree::new("test", "./src", None);
+        let expected_stats = expected.analyze().expect("analyze failed");
+
+        let session = AnalysisSession::new(4).expect("create session failed");
+        let mut tree = ProjectTree::new("test", "./src", None);
+        tree.build().expect("build failed");
+        let stats = session
+            .install(|| tree.summarize())
+            .expect("summarize failed");
+        assert_eq!(stats.files_visited, expected_stats.files_visited);
+        assert_eq!(stats.dirs_visited, expected_stats.dirs_visited);
+        assert_eq!(stats.bytes_read, expected_stats.bytes_read);
+
+        let root = tree.root.as_ref().unwrap();
+        let expected = expected.root.as_ref().unwrap();
+        assert_eq!(root.summary.size, expected.summary.size);
+        assert_eq!(root.summary.count, expected.summary.count);
+        let file = "./src/tree/summarizer.rs";
+        assert_eq!(
+            root.find(file).unwrap().summary.count,
+            expected.find(file).unwrap().summary.count
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sandboxed_build() {
+        use crate::tree::node::NodeMarker;
+        let outside = TempDir::new();
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/file.txt"), "hello").unwrap();
+        // 指向根路径之外的目录与文件
+        std::os::unix::fs::symlink(&outside, dir.join("escape")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.txt"), dir.join("secret.txt")).unwrap();
+        // 指向根路径之内的链接不受影响
+        std::os::unix::fs::symlink(dir.join("sub"), dir.join("inside")).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        let config = ProjectConfig::new().with_sandbox(true);
+        let mut tree = ProjectTree::new("test", path.clone(), Some(config));
+        tree.build().expect("build failed");
+        tree.summarize().expect("summarize failed");
+        let root = tree.root.as_ref().unwrap();
+        let find = |name: &str| {
+            root.children
+                .iter()
+                .flatten()
+                .find(|child| child.name() == name)
+                .unwrap()
+        };
+        let escape = find("escape");
+        assert!(matches!(
+            escape.marker,
+            Some(NodeMarker::OutsideRoot { .. })
+        ));
+        assert!(escape.children.as_ref().unwrap().is_empty());
+        assert!(matches!(
+            find("secret.txt").marker,
+            Some(NodeMarker::OutsideRoot { .. })
+        ));
+        assert!(find("inside").marker.is_none());
+        assert_eq!(tree.warnings.len(), 2);
+        // 根路径之外的内容不计入总结信息
+        assert_eq!(root.summary.size, 10);
+
+        // 未开启沙箱模式时跟随链接
+        let mut tree = ProjectTree::new("test", path, None);
+        tree.build().expect("build failed");
+        tree.summarize().expect("summarize failed");
+        assert_eq!(tree.root.as_ref().unwrap().summary.size, 22);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_special_files() {
+        use crate::fs::FileKind;
+        use crate::tree::node::NodeMarker;
+        let dir = TempDir::new();
+        fs::write(dir.join("file.txt"), "hello").unwrap();
+        // 没有写入端的管道，读取时会无限阻塞
+        let status = std::process::Command::new("mkfifo")
+            .arg(dir.join("pipe"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let _socket = std::os::unix::net::UnixListener::bind(dir.join("socket")).unwrap();
+        let path = dir.to_string_lossy().to_string();
+
+        let mut tree = ProjectTree::new("test", path.clone(), None);
+        tree.analyze().expect("analyze failed");
+        tree.summarize().expect("summarize failed");
+        let root = tree.root.as_ref().unwrap();
+        let marker = |name: &str| {
+            root.children
+                .iter()
+                .flatten()
+                .find(|child| child.name() == name)
+                .unwrap()
+                .marker
+                .clone()
+        };
+        assert_eq!(
+            marker("pipe"),
+            Some(NodeMarker::SpecialFile {
+                kind: FileKind::Fifo
+            })
+        );
+        assert_eq!(
+            marker("socket"),
+            Some(NodeMarker::SpecialFile {
+                kind: FileKind::Socket
+            })
+        );
+        assert!(marker("file.txt").is_none());
+        assert_eq!(root.summary.size, 5);
+
+        // 开启后特殊文件不会出现在项目树中
+        let config = ProjectConfig::new().with_skip_special(true);
+        let mut tree = ProjectTree::new("test", path, Some(config));
+        tree.build().expect("build failed");
+        assert_eq!(
+            tree.root.as_ref().unwrap().children.as_ref().unwrap().len(),
+            1
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_size() {
+        let dir = TempDir::new();
+        fs::write(dir.join("small.txt"), "small").unwrap();
+        // 稀疏文件仅占用极少的磁盘块
+        let sparse = fs::File::create(dir.join("sparse.bin")).unwrap();
+        sparse.set_len(8 << 20).unwrap();
+
+        let tree = ProjectTree::try_plant("test", dir.to_string_lossy(), None).unwrap();
+        let root = tree.root.as_ref().unwrap();
+        let sparse = root.find(dir.join("sparse.bin")).unwrap();
+        assert_eq!(sparse.summary.size, 8 << 20);
+        assert!(sparse.summary.disk_size < sparse.summary.size);
+        let small = root.find(dir.join("small.txt")).unwrap();
+        assert_eq!(
+            root.summary.disk_size,
+            sparse.summary.disk_size + small.summary.disk_size
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_flags() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("bin")).unwrap();
+        fs::write(dir.join("plain.txt"), "plain").unwrap();
+        fs::write(dir.join("bin/tool"), "tool").unwrap();
+        fs::write(dir.join("bin/run"), "run").unwrap();
+        fs::set_permissions(dir.join("plain.txt"), fs::Permissions::from_mode(0o644)).unwrap();
+        fs::set_permissions(dir.join("bin/tool"), fs::Permissions::from_mode(0o4777)).unwrap();
+        fs::set_permissions(dir.join("bin/run"), fs::Permissions::from_mode(0o744)).unwrap();
+
+        let tree = ProjectTree::try_plant("test", dir.to_string_lossy(), None).unwrap();
+        let root = tree.root.as_ref().unwrap();
+        let plain = root.find(dir.join("plain.txt")).unwrap();
+        assert_eq!(plain.summary.mode, Some(0o644));
+        assert!(!plain.summary.world_writable && !plain.summary.setuid);
+        assert_eq!(plain.summary.executable_count, 0);
+        let bin = root.find(dir.join("bin")).unwrap();
+        assert!(bin.summary.world_writable && bin.summary.setuid);
+        assert_eq!(bin.summary.mode, None);
+        assert_eq!(bin.summary.executable_count, 2);
+        assert!(root.summary.world_writable && root.summary.setuid);
+        assert_eq!(root.summary.executable_count, 2);
+    }
+
+    #[cfg(all(unix, feature = "unix-metadata"))]
+    #[test]
+    fn test_owner_stats() {
+        use std::os::unix::fs::MetadataExt;
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let root = tree.root.as_ref().unwrap();
+        let path = "./tests/examples/tree/summary/test.rs";
+        let metadata = fs::metadata(path).unwrap();
+        let owner = root.find(path).unwrap().summary.owner.clone().unwrap();
+        assert_eq!((owner.uid, owner.gid), (metadata.uid(), metadata.gid()));
+        assert_eq!(owner.user, crate::tree::owner::user_name(metadata.uid()));
+        // 目录按属主汇总，各属主之和与目录总计一致
+        assert!(root.summary.owner.is_none());
+        let size: u64 = root.summary.by_owner.values().map(|stats| stats.size).sum();
+        assert_eq!(size, root.summary.size);
+        assert!(root.summary.by_owner.contains_key(&owner.uid));
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        use crate::tree::checkpoint::Checkpoint;
+        let dir = TempDir::new();
+        let path = dir.join("checkpoint");
+        let checkpoint = Checkpoint::new(path.to_string_lossy()).with_interval(1);
+        // 进入第三个目录时取消构建
+        let token = CancelToken::new();
+        let trigger = token.clone();
+        let entered = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut tree = ProjectTree::new("test", "./tests/examples", None)
+            .with_checkpoint(checkpoint.clone())
+            .with_cancel_token(token)
+            .with_progress(move |event: crate::tree::progress::ProgressEvent| {
+                if let crate::tree::progress::ProgressEvent::EnteredDir(_) = event
+                    && entered.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 2
+                {
+                    trigger.cancel();
+                }
+            });
+        assert!(matches!(tree.build(), Err(AruiError::Cancelled)));
+        assert!(checkpoint.exists());
+
+        // 从检查点继续构建，结果与完整构建一致
+        let mut resumed =
+            ProjectTree::new("test", "./tests/examples", None).with_checkpoint(checkpoint.clone());
+        let stats = resumed.resume().expect("resume failed");
+        assert!(!checkpoint.exists());
+        let mut full = ProjectTree::new("test", "./tests/examples", None);
+        let full_stats = full.build().expect("build failed");
+        assert!(stats.dirs_visited < full_stats.dirs_visited);
+        assert_eq!(
+            paths(resumed.root.as_ref().unwrap()),
+            paths(full.root.as_ref().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resume_tampered_checkpoint() {
+        use crate::fs::MemoryFs;
+        use crate::tree::checkpoint::Checkpoint;
+        let dir = TempDir::new();
+        let path = dir.join("checkpoint");
+        let checkpoint = Checkpoint::new(path.to_string_lossy());
+        let fs = MemoryFs::new()
+            .add_file("project/a.rs", "")
+            .add_file("project/dir/b.rs", "");
+        // 待加载的路径为文件，或同一目录出现两次
+        for pending in [
+            "Q\t1\tproject/a.rs\n",
+            "Q\t1\tproject/dir\nQ\t1\tproject/dir\n",
+        ] {
+            fs::write(
+                &path,
+                format!(
+                    "# arui checkpoint v2\nD\tproject\nF\tproject/a.rs\nU\tproject/dir\n{}",
+                    pending
+                ),
+            )
+            .unwrap();
+            let mut tree = ProjectTree::new("test", "project", None)
+                .with_fs(fs.clone())
+                .with_checkpoint(checkpoint.clone());
+            assert!(matches!(
+                tree.resume(),
+                Err(AruiError::InvalidCheckpoint(_))
+            ));
+        }
+        checkpoint.remove().unwrap();
+    }
+
+    #[test]
+    fn test_from_paths_wide() {
+        // 同一目录下的大量文件，重复路径只插入一次，文件出现子路径时转换为目录
+        let paths = (0..20_000)
+            .map(|i| PathBuf::from(format!("wide/{}.rs", i % 10_000)))
+            .chain([PathBuf::from("wide/0.rs/inner.rs")]);
+        let tree = ProjectTree::from_paths("test", "project", paths).expect("assemble failed");
+        let wide = tree.find("wide").unwrap();
+        assert_eq!(wide.children.as_ref().unwrap().len(), 10_000);
+        assert!(tree.find("wide/0.rs").unwrap().is_dir);
+        assert!(tree.find("wide/0.rs/inner.rs").is_some());
+
+        // 重叠的子路径不会重复插入
+        let config = ProjectConfig::new().add_includes(["node", "node/test_dir"]);
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", Some(config));
+        tree.build().expect("build failed");
+        let node = tree.find("node").unwrap();
+        let names: Vec<&str> = node.children.iter().flatten().map(TreeNode::name).collect();
+        let mut unique = names.clone();
+        unique.dedup();
+        assert_eq!(names, unique);
+    }
+
+    #[test]
+    fn test_include_as_scan_roots() {
+        let config = ProjectConfig::new().add_includes(["node/test_dir", "summary/test.rs"]);
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", Some(config));
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(
+            paths(root),
+            vec![
+                "./tests/examples/tree",
+                "./tests/examples/tree/node",
+                "./tests/examples/tree/node/test_dir",
+                "./tests/examples/tree/node/test_dir/file1.txt",
+                "./tests/examples/tree/node/test_dir/file2.rs",
+                "./tests/examples/tree/summary",
+                "./tests/examples/tree/summary/test.rs",
+            ]
+        );
+        let summary = &root.children.as_ref().unwrap()[1];
+        assert_eq!(
+            summary.summary.size,
+            summary.children.as_ref().unwrap()[0].summary.size
+        );
+
+        // 不存在的子路径
+        let config = ProjectConfig::new().add_include("not_exist");
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", Some(config.clone()));
+        assert!(matches!(tree.build(), Err(AruiError::IO(_))));
+        let mut tree = ProjectTree::new(
+            "test",
+            "./tests/examples/tree",
+            Some(config.with_tolerant(true)),
+        );
+        tree.build().expect("build failed");
+        assert_eq!(tree.warnings.len(), 1);
+        // 根路径之外的子路径
+        let config = ProjectConfig::new().add_include("../tree");
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", Some(config));
+        assert!(matches!(tree.build(), Err(AruiError::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_include_depth() {
+        let fs = MemoryFs::new()
+            .add_file("project/a/b/c/d.rs", "fn d() {}\n")
+            .add_file("project/a/b/e.rs", "fn e() {}\n");
+        let config = ProjectConfig::new().with_max_depth(3).add_include("a/b");
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.build().expect("build failed");
+        // 子路径的深度从项目根节点起算，`a/b/c` 位于第 3 层，不再展开
+        assert!(tree.find("a/b/e.rs").is_some());
+        assert!(!tree.find("a/b/c").unwrap().is_loaded());
+        // 与重建子树时的深度一致
+        tree.rebuild_subtree("a/b").expect("rebuild failed");
+        assert!(!tree.find("a/b/c").unwrap().is_loaded());
+    }
+
+    #[test]
+    fn test_refresh() {
+        let dir = TempDir::new();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/kept.txt"), "hello").unwrap();
+        fs::write(dir.join("changed.txt"), "a").unwrap();
+        fs::write(dir.join("removed.txt"), "bye").unwrap();
+        let path = dir.to_string_lossy().to_string();
+        let mut tree = ProjectTree::new("test", path.clone(), None);
+        assert!(matches!(tree.refresh(), Err(AruiError::NotBuilt)));
+        tree.analyze().expect("analyze failed");
+
+        fs::write(dir.join("changed.txt"), "a\nb\n").unwrap();
+        fs::remove_file(dir.join("removed.txt")).unwrap();
+        fs::write(dir.join("sub/added.txt"), "new").unwrap();
+        let changes = tree.refresh().expect("refresh failed");
+        let join = |name: &str| dir.join(name).to_string_lossy().to_string();
+        assert_eq!(changes.added, vec![join("sub/added.txt")]);
+        assert_eq!(changes.removed, vec![join("removed.txt")]);
+        assert_eq!(changes.modified, vec![join("changed.txt")]);
+        assert_eq!(tree.root.as_ref().unwrap().summary.size, 12);
+        assert!(tree.refresh().expect("refresh failed").is_empty());
+
+        // 根路径失效时保留原有的项目树
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(tree.refresh().is_err());
+        assert!(tree.root.is_some());
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut records = Vec::new();
+        let mut tree = ProjectTree::new("test", "./tests/examples/tree", None);
+        let stats = tree
+            .stream(|record| records.push(record))
+            .expect("stream failed");
+        assert!(tree.root.is_none());
+
+        let expected =
+            ProjectTree::try_plant("test", "./tests/examples/tree", None).expect("plant failed");
+        let expected_root = expected.root.as_ref().unwrap();
+        // 根目录的记录最后生成，且与完整分析的结果一致
+        let root = records.last().unwrap();
+        assert_eq!(root.depth, 0);
+        assert_eq!(root.path, expected_root.path);
+        assert_eq!(root.summary.size, expected_root.summary.size);
+        assert_eq!(root.summary.count, expected_root.summary.count);
+        // 每个目录各生成一条记录，子目录先于父目录
+        assert_eq!(records.len() as u64, stats.dirs_visited);
+        let summary = records
+            .iter()
+            .position(|r| r.path.ends_with("summary"))
+            .unwrap();
+        let tree_dir = records.iter().position(|r| r.depth == 0).unwrap();
+        assert!(summary < tree_dir);
+    }
+
+    #[test]
+    fn test_unicode_filters() {
+        // 以 NFD 形式存储的文件名
+        let fs = MemoryFs::new()
+            .add_file("photos/Cafe\u{301}/menu.txt", "coffee\n")
+            .add_file("photos/Re\u{301}sume\u{301}/cv.txt", "cv\n")
+            .add_file("photos/other.txt", "other\n");
+        // 以 NFC 形式书写的排除与包含路径
+        let config = ProjectConfig::new().add_exclude("Caf\u{e9}");
+        let mut tree = ProjectTree::new("test", "photos", Some(config)).with_fs(fs.clone());
+        tree.analyze().expect("analyze failed");
+        assert!(tree.find("Caf\u{e9}").is_none());
+        assert!(tree.find("R\u{e9}sum\u{e9}/cv.txt").is_some());
+        assert_eq!(tree.root.as_ref().unwrap().summary.count, 2);
+
+        let config = ProjectConfig::new().add_include("Caf\u{e9}");
+        let mut tree = ProjectTree::new("test", "photos", Some(config)).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let menu = tree.find("Caf\u{e9}/menu.txt").unwrap();
+        assert_eq!(menu.summary.count, 1);
+    }
+
+    #[test]
+    fn test_rebuild_subtree_not_found() {
+        let mut tree = ProjectTree::new("test", "./tests/examples", None);
+        // 未构建时无法重建
+        assert!(tree.rebuild_subtree("tree").is_err());
+        tree.build().expect("build failed");
+        assert!(tree.rebuild_subtree("tree").is_ok());
+        assert!(tree.rebuild_subtree("tree/not_exist").is_err());
     }
 }
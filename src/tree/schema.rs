@@ -0,0 +1,143 @@
+//! # 导出格式版本
+//! 需要开启 `serde` 特性。
+//! JSON 与 YAML 导出使用同一套带版本号的格式：顶层对象在项目树的字段之外包含 `schema_version` 字段，
+//! 外部工具可以据此判断能否读取导出结果。格式的兼容规则如下：
+//! - 同一版本内只会新增字段，不会删除字段或改变已有字段的含义，字段不兼容的变化会递增版本号
+//! - 导入时忽略无法识别的字段，缺少的字段取默认值，因此同一版本内新旧导出结果可以互相读取
+//! - 导入时拒绝版本号高于当前支持版本的内容，避免静默丢失无法理解的信息
+//!
+//! 在同一版本下，导出后导入再导出的结果与首次导出的结果完全一致。
+//!
+//! 开启 `json-schema` 特性后，可以通过 `json_schema` 生成描述 JSON 导出格式的 JSON Schema，
+//! 生成结果同时随仓库发布于 `schema/export.schema.json`，第三方工具可以据此校验导出结果或生成代码。
+use crate::errors::AruiError;
+use crate::tree::root::ProjectTree;
+use serde::{Deserialize, Serialize};
+
+/// 当前的导出格式版本
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 项目树的导出结果，包含导出格式版本与项目树的各个字段
+#[derive(Serialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "json-schema", schemars(rename = "ProjectTreeExport"))]
+pub(crate) struct ExportRef<'a> {
+    /// 导出格式版本
+    schema_version: u32,
+    /// 项目树的各个字段
+    #[serde(flatten)]
+    tree: &'a ProjectTree,
+}
+
+impl<'a> ExportRef<'a> {
+    /// 使用当前的格式版本导出项目树
+    pub(crate) fn new(tree: &'a ProjectTree) -> Self {
+        ExportRef {
+            schema_version: SCHEMA_VERSION,
+            tree,
+        }
+    }
+}
+
+/// 生成描述 JSON 导出格式（`ProjectTree::to_json`）的 JSON Schema，需要开启 `json-schema` 特性
+/// Schema 遵循 JSON Schema 2020-12 规范，各字段的说明取自对应结构体的文档注释
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::schema;
+/// let schema = schema::json_schema();
+/// assert_eq!(schema["properties"]["schema_version"]["type"], "integer");
+/// ```
+#[cfg(feature = "json-schema")]
+pub fn json_schema() -> serde_json::Value {
+    schemars::schema_for!(ExportRef<'static>).to_value()
+}
+
+/// 导入时首先读取的格式版本，其余字段被忽略
+/// 项目树随后从同一内容中单独读取：展开（flatten）读取会丢失部分格式信息，如 JSON 中以字符串表示的整数键
+#[derive(Deserialize)]
+pub(crate) struct SchemaHeader {
+    /// 导出格式版本
+    schema_version: u32,
+}
+
+impl SchemaHeader {
+    /// 检查格式版本，高于当前支持的版本时返回 `AruiError::UnsupportedSchema`
+    pub(crate) fn check(&self) -> Result<(), AruiError> {
+        if self.schema_version > SCHEMA_VERSION {
+            return Err(AruiError::UnsupportedSchema(self.schema_version));
+        }
+        Ok(())
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(all(test, feature = "json-schema"))]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+
+    /// 随仓库发布的 Schema 文件，包含所有特性下的字段
+    const PUBLISHED_SCHEMA: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/schema/export.schema.json");
+
+    #[cfg(all(
+        feature = "hash",
+        feature = "unix-metadata",
+        feature = "git",
+        feature = "compress",
+        feature = "manifest"
+    ))]
+    #[test]
+    fn test_published_schema() {
+        let generated = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+        // 设置环境变量 ARUI_UPDATE_SCHEMA 后运行测试即可重新生成
+        if std::env::var_os("ARUI_UPDATE_SCHEMA").is_some() {
+            std::fs::write(PUBLISHED_SCHEMA, &generated).unwrap();
+        }
+        let published = std::fs::read_to_string(PUBLISHED_SCHEMA).unwrap();
+        assert!(
+            published == generated,
+            "schema/export.schema.json is outdated, rerun the tests with ARUI_UPDATE_SCHEMA=1 and all features"
+        );
+    }
+
+    #[test]
+    fn test_export_matches_schema() {
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.rs", "fn main() {}\n")
+            .add_file("project/Cargo.toml", "[package]\nname = \"demo\"\n")
+            .add_file("project/data.bin", [0u8, 159, 146, 150])
+            .add_dir("project/empty");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        let schema = json_schema();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let published: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(PUBLISHED_SCHEMA).unwrap()).unwrap();
+        let published = jsonschema::validator_for(&published).unwrap();
+
+        // 未构建、仅构建与完成总结的项目树均符合 Schema
+        let mut exports = vec![tree.to_json(false).unwrap()];
+        tree.build().unwrap();
+        exports.push(tree.to_json(false).unwrap());
+        tree.analyze().unwrap();
+        exports.push(tree.to_json(false).unwrap());
+        for export in exports {
+            let instance: serde_json::Value = serde_json::from_str(&export).unwrap();
+            let errors: Vec<String> = validator
+                .iter_errors(&instance)
+                .map(|error| format!("{} at {}", error, error.instance_path))
+                .collect();
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert!(published.is_valid(&instance));
+        }
+
+        // 缺少必需字段时无法通过校验
+        let mut instance: serde_json::Value =
+            serde_json::from_str(&tree.to_json(false).unwrap()).unwrap();
+        instance["root"]["is_dir"] = serde_json::Value::Null;
+        assert!(!validator.is_valid(&instance));
+    }
+}
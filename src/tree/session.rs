@@ -0,0 +1,132 @@
+//! # 分析会话
+//! 需要开启 `parallel` 特性。
+//! 需要扫描大量项目的应用（如同时分析多个仓库的服务）若为每个项目单独创建工作线程，线程数量会随项目数量膨胀。
+//! `AnalysisSession` 持有一个共享的线程池与总结缓存，会话中的所有项目均在同一线程池上分析，
+//! 并共享同一份缓存，重复分析时未发生变化的文件不再重新读取。
+use crate::errors::AruiError;
+use crate::tree::cache::SummaryCache;
+use crate::tree::root::ProjectTree;
+use crate::tree::stats::BuildStats;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::Arc;
+
+type Result<T> = std::result::Result<T, AruiError>;
+
+/// 分析会话
+/// 会话可以被多个线程共享引用，各项目的分析互不影响
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::root::ProjectTree;
+/// use arui_core::tree::session::AnalysisSession;
+///
+/// let session = AnalysisSession::new(2).unwrap();
+/// let mut trees = vec![
+///     ProjectTree::new("src", "./src", None),
+///     ProjectTree::new("tests", "./tests", None),
+/// ];
+/// for result in session.analyze_all(&mut trees) {
+///     assert!(result.unwrap().files_visited > 0);
+/// }
+/// assert!(!session.cache().is_empty());
+/// ```
+pub struct AnalysisSession {
+    /// 共享的线程池
+    pool: ThreadPool,
+    /// 共享的总结缓存
+    cache: Arc<SummaryCache>,
+}
+
+impl AnalysisSession {
+    /// 创建会话
+    /// - threads：线程池的线程数量，为 0 时由 rayon 根据 CPU 核心数决定
+    pub fn new(threads: usize) -> Result<Self> {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+        Ok(AnalysisSession {
+            pool,
+            cache: Arc::new(SummaryCache::new()),
+        })
+    }
+
+    /// 使用已有的总结缓存，例如与会话之外的项目树共享
+    pub fn with_cache(mut self, cache: Arc<SummaryCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// 线程池的线程数量
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// 会话共享的总结缓存
+    pub fn cache(&self) -> &Arc<SummaryCache> {
+        &self.cache
+    }
+
+    /// 在会话的线程池中执行操作，操作内部的并行任务同样使用该线程池
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        self.pool.install(op)
+    }
+
+    /// 将项目树加入会话，使其共享会话的总结缓存
+    pub fn attach(&self, tree: &mut ProjectTree) {
+        tree.cache = Some(self.cache.clone());
+    }
+
+    /// 在会话的线程池中构建项目树
+    pub fn build(&self, tree: &mut ProjectTree) -> Result<BuildStats> {
+        self.attach(tree);
+        self.install(|| tree.build())
+    }
+
+    /// 在会话的线程池中构建项目树并同时生成总结信息
+    pub fn analyze(&self, tree: &mut ProjectTree) -> Result<BuildStats> {
+        self.attach(tree);
+        self.install(|| tree.analyze())
+    }
+
+    /// 在会话的线程池中并行分析多个项目树，结果与项目树一一对应
+    /// 任一项目树分析失败不影响其余项目树
+    pub fn analyze_all(&self, trees: &mut [ProjectTree]) -> Vec<Result<BuildStats>> {
+        trees.iter_mut().for_each(|tree| self.attach(tree));
+        self.install(|| trees.par_iter_mut().map(ProjectTree::analyze).collect())
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session() {
+        let session = AnalysisSession::new(2).expect("create session failed");
+        assert_eq!(session.threads(), 2);
+        let mut trees = vec![
+            ProjectTree::new("summary", "./tests/examples/tree/summary", None),
+            ProjectTree::new("missing", "./not_exist", None),
+            ProjectTree::new("node", "./tests/examples/tree/node", None),
+        ];
+        let results = session.analyze_all(&mut trees);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(AruiError::InvalidPath(_))));
+        assert!(results[2].is_ok());
+        let cached = session.cache().len();
+        assert!(cached > 0);
+
+        // 再次分析时复用缓存，不再读取文件内容
+        let stats = session.analyze(&mut trees[0]).expect("analyze failed");
+        assert_eq!(stats.bytes_read, 0);
+        assert!(session.cache().hits() > 0);
+        assert_eq!(session.cache().len(), cached);
+        assert!(trees[0].root.as_ref().unwrap().summary.count > 0);
+    }
+}
@@ -0,0 +1,192 @@
+//! # SQLite 导出
+//! 需要开启 `sqlite` 特性。
+//! 将项目树的节点与总结信息写入 SQLite 数据库，表结构见 `SCHEMA`。
+//! 每次导出对应 `scans` 表中的一行，以项目树的 ID 区分，多次扫描的结果可以保存在同一个数据库中，
+//! 便于保留扫描历史并通过 SQL 比较不同时间的结果；再次导出同一项目树时会替换之前写入的内容。
+use crate::tree::node::TreeNode;
+use crate::tree::summary::NodeSummary;
+use rusqlite::{Connection, Result, Transaction, params};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 表结构，依次为：
+/// - scans：每次导出一行，包含项目树 ID、别名、根路径与导出时间（Unix 时间戳，单位为秒）
+/// - nodes：所有节点，`parent` 为父节点的行 ID，根节点为 null；`depth` 为相对根节点的深度
+/// - summaries：每个节点一行，包含大小、文件与目录数量、行数以及文件的语言、编码等信息，mtime 单位为秒
+/// - extensions：各节点按后缀划分的文件数量、大小与行数
+///
+/// 删除 `scans` 中的一行时（需要开启 `foreign_keys`），其下的节点与统计信息一并删除
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS scans (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    path TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS nodes (
+    id INTEGER PRIMARY KEY,
+    scan TEXT NOT NULL REFERENCES scans(id) ON DELETE CASCADE,
+    parent INTEGER REFERENCES nodes(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    name TEXT NOT NULL,
+    depth INTEGER NOT NULL,
+    is_dir INTEGER NOT NULL,
+    kind TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS nodes_scan_path ON nodes(scan, path);
+CREATE INDEX IF NOT EXISTS nodes_parent ON nodes(parent);
+CREATE TABLE IF NOT EXISTS summaries (
+    node INTEGER PRIMARY KEY REFERENCES nodes(id) ON DELETE CASCADE,
+    size INTEGER NOT NULL,
+    disk_size INTEGER NOT NULL,
+    files INTEGER NOT NULL,
+    dirs INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    code INTEGER NOT NULL,
+    comments INTEGER NOT NULL,
+    blanks INTEGER NOT NULL,
+    binary INTEGER,
+    language TEXT,
+    encoding TEXT,
+    mime TEXT,
+    origin TEXT,
+    mtime INTEGER
+);
+CREATE TABLE IF NOT EXISTS extensions (
+    node INTEGER NOT NULL REFERENCES nodes(id) ON DELETE CASCADE,
+    extension TEXT NOT NULL,
+    files INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    lines INTEGER NOT NULL,
+    PRIMARY KEY (node, extension)
+);
+";
+
+/// 将项目树写入数据库，必要时创建表结构，返回写入的节点数量
+/// - connection：数据库连接
+/// - id、name：项目树的 ID 与别名，写入 `scans` 表
+/// - root：根节点
+///
+/// 所有内容在同一个事务中写入，失败时数据库保持不变
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::TreeNode;
+/// use arui_core::tree::sqlite;
+/// use rusqlite::Connection;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// root.children = Some(vec![TreeNode::new("./project/main.rs", false)]);
+/// let mut connection = Connection::open_in_memory().unwrap();
+/// assert_eq!(sqlite::write(&mut connection, "id", "test", &root).unwrap(), 2);
+/// let count: i64 = connection
+///     .query_row("SELECT COUNT(*) FROM nodes WHERE parent IS NOT NULL", [], |row| row.get(0))
+///     .unwrap();
+/// assert_eq!(count, 1);
+/// ```
+pub fn write(connection: &mut Connection, id: &str, name: &str, root: &TreeNode) -> Result<u64> {
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+    remove_scan(&transaction, id)?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| int(duration.as_secs()));
+    transaction.execute(
+        "INSERT INTO scans (id, name, path, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![id, name, root.path, created_at],
+    )?;
+    let mut count = 0;
+    {
+        let mut insert_node = transaction.prepare(
+            "INSERT INTO nodes (scan, parent, path, name, depth, is_dir, kind)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        let mut insert_summary = transaction.prepare(
+            "INSERT INTO summaries (node, size, disk_size, files, dirs, lines, code, comments, blanks,
+                                    binary, language, encoding, mime, origin, mtime)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        )?;
+        let mut insert_extension = transaction.prepare(
+            "INSERT INTO extensions (node, extension, files, size, lines) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        let mut stack: Vec<(&TreeNode, Option<i64>, u64)> = vec![(root, None, 0)];
+        while let Some((node, parent, depth)) = stack.pop() {
+            insert_node.execute(params![
+                id,
+                parent,
+                node.path,
+                node.name(),
+                int(depth),
+                node.is_dir,
+                node.kind.to_string()
+            ])?;
+            let row = transaction.last_insert_rowid();
+            let summary = &node.summary;
+            insert_summary.execute(params![
+                row,
+                int(summary.size),
+                int(summary.disk_size),
+                int(summary.file_count),
+                int(summary.dir_count),
+                int(summary.count),
+                int(summary.lines.code),
+                int(summary.lines.comments),
+                int(summary.lines.blanks),
+                summary.is_binary,
+                summary.language.map(|language| language.name()),
+                summary.encoding.map(|encoding| encoding.name()),
+                summary.mime,
+                summary.origin.map(|origin| origin.name()),
+                mtime(summary),
+            ])?;
+            for (extension, stats) in &summary.by_extension {
+                insert_extension.execute(params![
+                    row,
+                    extension,
+                    int(stats.files),
+                    int(stats.size),
+                    int(stats.lines)
+                ])?;
+            }
+            count += 1;
+            if let Some(children) = &node.children {
+                stack.extend(
+                    children
+                        .iter()
+                        .rev()
+                        .map(|child| (child, Some(row), depth + 1)),
+                );
+            }
+        }
+    }
+    transaction.commit()?;
+    Ok(count)
+}
+
+/// 删除之前写入的同一次扫描
+fn remove_scan(transaction: &Transaction, id: &str) -> Result<()> {
+    transaction.execute(
+        "DELETE FROM extensions WHERE node IN (SELECT id FROM nodes WHERE scan = ?1)",
+        [id],
+    )?;
+    transaction.execute(
+        "DELETE FROM summaries WHERE node IN (SELECT id FROM nodes WHERE scan = ?1)",
+        [id],
+    )?;
+    transaction.execute("DELETE FROM nodes WHERE scan = ?1", [id])?;
+    transaction.execute("DELETE FROM scans WHERE id = ?1", [id])?;
+    Ok(())
+}
+
+/// 最后修改时间的 Unix 时间戳，单位为秒
+fn mtime(summary: &NodeSummary) -> Option<i64> {
+    summary
+        .updated_at
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| int(duration.as_secs()))
+}
+
+/// SQLite 的整数为有符号 64 位整数，超出范围时取最大值
+fn int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,59 @@
+//! # 扫描统计
+//! 记录构建、总结等操作本身的开销，例如耗时、访问的节点数量、读取的字节数，便于调用方记录日志与监控扫描成本。
+use std::fmt::Display;
+use std::time::Duration;
+
+/// 扫描统计信息
+/// - elapsed：耗时
+/// - dirs_visited：访问的目录数量
+/// - files_visited：访问的文件数量
+/// - bytes_read：读取的文件内容字节数
+/// - errors_skipped：被跳过的错误数量
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildStats {
+    /// 操作耗时
+    pub elapsed: Duration,
+    /// 访问的目录数量
+    pub dirs_visited: u64,
+    /// 访问的文件数量
+    pub files_visited: u64,
+    /// 读取的文件内容字节数，构建阶段仅读取元数据，因此为 0
+    pub bytes_read: u64,
+    /// 被跳过的错误数量，如容错模式下跳过的路径、总结时无法读取的文件
+    pub errors_skipped: u64,
+}
+
+impl Display for BuildStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "elapsed: {:?}, dirs: {}, files: {}, bytes read: {}, errors skipped: {}",
+            self.elapsed,
+            self.dirs_visited,
+            self.files_visited,
+            self.bytes_read,
+            self.errors_skipped
+        )
+    }
+}
+
+impl BuildStats {
+    /// 累加另一份统计信息
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::stats::BuildStats;
+    ///
+    /// let mut stats = BuildStats { files_visited: 1, ..Default::default() };
+    /// stats.merge(&BuildStats { files_visited: 2, ..Default::default() });
+    /// assert_eq!(stats.files_visited, 3);
+    /// ```
+    pub fn merge(&mut self, other: &BuildStats) {
+        self.elapsed += other.elapsed;
+        self.dirs_visited += other.dirs_visited;
+        self.files_visited += other.files_visited;
+        self.bytes_read += other.bytes_read;
+        self.errors_skipped += other.errors_skipped;
+    }
+}
@@ -0,0 +1,24 @@
+//! # 流式分析
+//! 分析超大目录（上千万个文件）时，完整的项目树会占用大量内存。
+//! 流式分析在遍历过程中逐个目录汇总总结信息并交给调用方处理，目录完成汇总后立即释放其子节点，
+//! 内存占用仅与目录深度及单个目录的宽度相关，而与文件总数无关。
+use crate::tree::node::NodeMarker;
+use crate::tree::summary::NodeSummary;
+
+/// 目录记录
+/// 流式分析中每个目录完成汇总后生成一条记录，子目录的记录总是先于父目录生成
+/// - path：目录路径
+/// - depth：相对于项目根路径的深度
+/// - summary：目录下所有文件的总结信息的加合
+/// - marker：目录的节点标记
+#[derive(Debug, Clone)]
+pub struct DirRecord {
+    /// 目录路径
+    pub path: String,
+    /// 相对于项目根路径的深度，根目录为 0
+    pub depth: usize,
+    /// 目录下所有文件的总结信息的加合
+    pub summary: NodeSummary,
+    /// 节点标记，如出现循环的目录
+    pub marker: Option<NodeMarker>,
+}
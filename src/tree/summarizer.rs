@@ -0,0 +1,545 @@
+//! # 项目树总结
+//! 负责遍历已构建的树节点，自底向上获取并写入各节点的总结信息。
+//! `NodeSummary::update` 与 `ProjectTree::summarize` 均通过该模块完成，
+//! `ProjectTree::analyze` 也会在构建过程中借助该模块直接获取文件的总结信息。
+use crate::fs::{DiskFs, FileSystem, FsMetadata};
+use crate::tree::cache::{SummaryCache, SummaryOptions};
+use crate::tree::cancel::CancelToken;
+use crate::tree::encoding::Encoding;
+use crate::tree::image::ImageInfo;
+use crate::tree::language::{Language, shebang_interpreter};
+use crate::tree::license;
+#[cfg(feature = "manifest")]
+use crate::tree::manifest::ManifestInfo;
+use crate::tree::mime;
+use crate::tree::node::count::{READ_BUFFER_LEN, TextCounter, is_binary};
+use crate::tree::node::file::get_file_suffix;
+use crate::tree::node::{NodeMarker, TreeNode};
+use crate::tree::origin::{CodeOrigin, is_generated};
+use crate::tree::progress::{ProgressReporter, ProgressTracker};
+use crate::tree::stats::BuildStats;
+use crate::tree::summary::NodeSummary;
+use crate::tree::warning::{Warning, WarningKind};
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 读取文件时保留的开头内容的字节数，用于识别语言、编码、图片与许可证等，其余内容逐块读取后即丢弃
+/// 清单文件与许可证文件同样仅解析这一段内容，超出的部分不参与解析
+const HEAD_LEN: usize = 256 * 1024;
+
+/// 节点总结器
+/// 递归遍历节点，获取文件的总结信息并汇总到目录
+pub(crate) struct Summarizer {
+    /// 文件系统，默认为磁盘
+    fs: Arc<dyn FileSystem>,
+    /// 扫描统计
+    stats: BuildStats,
+    /// 进度追踪
+    progress: ProgressTracker,
+    /// 取消令牌
+    cancel: Option<CancelToken>,
+    /// 总结是否已被取消，取消后已完成的部分总结信息仍会保留
+    cancelled: bool,
+    /// 总结缓存，设置后复用未发生变化的文件的总结信息
+    cache: Option<Arc<SummaryCache>>,
+    /// 读取的文件大小上限，为空时不限制
+    max_file_size: Option<u64>,
+    /// 是否统计文本文件的词数与字符数
+    count_words: bool,
+    /// 需要统计的代码标记，为空时不统计
+    markers: Vec<String>,
+    /// 是否估算文本文件的 token 数量
+    count_tokens: bool,
+    /// 记录的最大文件数量，为 0 时不记录
+    top_n: usize,
+    /// 总结过程中记录的警告
+    warnings: Vec<Warning>,
+}
+
+impl Default for Summarizer {
+    fn default() -> Self {
+        Summarizer {
+            fs: Arc::new(DiskFs),
+            stats: BuildStats::default(),
+            progress: ProgressTracker::default(),
+            cancel: None,
+            cancelled: false,
+            cache: None,
+            max_file_size: None,
+            count_words: false,
+            markers: Vec::new(),
+            count_tokens: false,
+            top_n: 0,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Summarizer {
+    /// 设置文件系统
+    pub(crate) fn with_fs(mut self, fs: Arc<dyn FileSystem>) -> Self {
+        self.fs = fs;
+        self
+    }
+
+    /// 设置进度报告
+    pub(crate) fn with_progress(mut self, progress: Option<Arc<dyn ProgressReporter>>) -> Self {
+        self.progress = ProgressTracker::new(progress);
+        self
+    }
+
+    /// 设置取消令牌
+    pub(crate) fn with_cancel(mut self, cancel: Option<CancelToken>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// 设置读取的文件大小上限
+    pub(crate) fn with_max_file_size(mut self, max_file_size: Option<u64>) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// 设置是否统计词数与字符数
+    pub(crate) fn with_count_words(mut self, count_words: bool) -> Self {
+        self.count_words = count_words;
+        self
+    }
+
+    /// 设置需要统计的代码标记
+    pub(crate) fn with_markers(mut self, markers: Vec<String>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// 设置是否估算 token 数量
+    pub(crate) fn with_count_tokens(mut self, count_tokens: bool) -> Self {
+        self.count_tokens = count_tokens;
+        self
+    }
+
+    /// 设置记录的最大文件数量
+    pub(crate) fn with_top_n(mut self, top_n: Option<usize>) -> Self {
+        self.top_n = top_n.unwrap_or_default();
+        self
+    }
+
+    /// 设置总结缓存
+    pub(crate) fn with_cache(mut self, cache: Option<Arc<SummaryCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// 以当前的总结选项使用缓存，缓存由不同的选项写入时会被清空，需在设置完各项选项之后调用
+    pub(crate) fn bind_cache(self) -> Self {
+        if let Some(cache) = &self.cache {
+            cache.bind(SummaryOptions {
+                max_file_size: self.max_file_size,
+                count_words: self.count_words,
+                markers: self.markers.clone(),
+                count_tokens: self.count_tokens,
+                features: SummaryOptions::enabled_features(),
+            });
+        }
+        self
+    }
+
+    /// 总结是否已被取消
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// 取出总结过程中记录的警告
+    pub(crate) fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// 取出扫描统计，耗时由调用方计算
+    pub(crate) fn take_stats(&mut self) -> BuildStats {
+        std::mem::take(&mut self.stats)
+    }
+
+    /// 检查取消令牌，并记录取消状态
+    fn check_cancelled(&mut self) -> bool {
+        if self.cancel.as_ref().is_some_and(CancelToken::is_cancelled) {
+            self.cancelled = true;
+        }
+        self.cancelled
+    }
+
+    /// 获取节点的总结信息，子节点的总结信息会自动写入，当前节点则由调用方决定是否写入
+    pub(crate) fn summarize(&mut self, node: &mut TreeNode) -> NodeSummary {
+        // 沙箱模式下逃逸出根路径的节点与特殊文件不读取任何内容
+        if is_skipped(node) {
+            return NodeSummary::new();
+        }
+        // 若非目录，直接计算当前文件，并终止递归
+        if !node.is_dir {
+            return self.summarize_file(&node.path);
+        }
+        // 开启并行特性时，目录下的文件交由线程池并行读取
+        #[cfg(feature = "parallel")]
+        return self.summarize_parallel(node);
+        #[cfg(not(feature = "parallel"))]
+        self.summarize_dir(node)
+    }
+
+    /// 依次获取目录下各个节点的总结信息
+    #[cfg(not(feature = "parallel"))]
+    fn summarize_dir(&mut self, node: &mut TreeNode) -> NodeSummary {
+        self.stats.dirs_visited += 1;
+        self.progress.enter_dir(&node.path);
+        // 若为目录，递归遍历所有子节点，从底向上获取总结信息，直到根节点
+        if let Some(children) = &mut node.children {
+            for child in children {
+                // 被取消时停止总结剩余子节点
+                if self.check_cancelled() {
+                    break;
+                }
+                // 递归调用子节点，并写入子节点
+                child.summary = self.summarize(child);
+            }
+        }
+
+        // 子节点均已更新，累加到父节点
+        let summary = NodeSummary::collect(node);
+        NodeSummary::mark_vendored(node);
+        summary
+    }
+
+    /// 并行获取目录的总结信息
+    /// 先遍历目录收集所有需要读取的文件节点，再在当前的 rayon 线程池中并行读取，最后自底向上汇总到各级目录。
+    /// 在 `AnalysisSession` 中调用时使用会话的线程池，否则使用 rayon 的全局线程池
+    #[cfg(feature = "parallel")]
+    fn summarize_parallel(&mut self, node: &mut TreeNode) -> NodeSummary {
+        use rayon::prelude::*;
+        let mut files = Vec::new();
+        self.collect_files(node, &mut files);
+        // 每个线程持有各自的总结器，完成后合并统计与警告
+        let workers: Vec<Summarizer> = files
+            .par_iter_mut()
+            .fold(
+                || self.fork(),
+                |mut worker, file| {
+                    // 被取消时跳过剩余文件
+                    if !worker.check_cancelled() {
+                        file.summary = worker.summarize_file(&file.path);
+                    }
+                    worker
+                },
+            )
+            .collect();
+        for mut worker in workers {
+            self.stats.merge(&worker.take_stats());
+            self.warnings.append(&mut worker.warnings);
+            self.cancelled |= worker.cancelled;
+        }
+        Summarizer::roll_up(node)
+    }
+
+    /// 递归收集需要读取的文件节点，同时记录访问的目录
+    /// 被标记的节点不读取任何内容，直接重置其总结信息
+    #[cfg(feature = "parallel")]
+    fn collect_files<'a>(&mut self, node: &'a mut TreeNode, files: &mut Vec<&'a mut TreeNode>) {
+        if is_skipped(node) {
+            node.summary = NodeSummary::new();
+            return;
+        }
+        if !node.is_dir {
+            files.push(node);
+            return;
+        }
+        self.stats.dirs_visited += 1;
+        self.progress.enter_dir(&node.path);
+        for child in node.children.iter_mut().flatten() {
+            self.collect_files(child, files);
+        }
+    }
+
+    /// 创建共享文件系统、进度、取消令牌与缓存的总结器，用于在其他线程中读取文件
+    #[cfg(feature = "parallel")]
+    fn fork(&self) -> Summarizer {
+        Summarizer {
+            fs: self.fs.clone(),
+            progress: self.progress.clone(),
+            cancel: self.cancel.clone(),
+            cache: self.cache.clone(),
+            max_file_size: self.max_file_size,
+            count_words: self.count_words,
+            markers: self.markers.clone(),
+            count_tokens: self.count_tokens,
+            top_n: self.top_n,
+            ..Default::default()
+        }
+    }
+
+    /// 根据子节点现有的总结信息，自底向上汇总各级目录的总结信息，不读取任何文件
+    /// 文件节点直接返回其现有的总结信息
+    pub(crate) fn roll_up(node: &mut TreeNode) -> NodeSummary {
+        if !node.is_dir {
+            return node.summary.clone();
+        }
+        if let Some(children) = &mut node.children {
+            for child in children.iter_mut().filter(|child| child.is_dir) {
+                child.summary = Summarizer::roll_up(child);
+            }
+        }
+        let summary = NodeSummary::collect(node);
+        NodeSummary::mark_vendored(node);
+        summary
+    }
+
+    /// 获取单个文件的总结信息
+    fn summarize_file(&mut self, path: &str) -> NodeSummary {
+        let path = Path::new(path);
+        let metadata = match self.fs.metadata(path) {
+            // 未经构建标记的特殊文件同样不读取，避免在管道上无限阻塞
+            Ok(metadata) if metadata.kind.is_special() => {
+                self.stats.files_visited += 1;
+                self.progress.file_done();
+                return NodeSummary::new();
+            }
+            Ok(metadata) => metadata,
+            Err(error) => {
+                self.stats.errors_skipped += 1;
+                self.warnings
+                    .push(Warning::from_io(path.to_string_lossy(), &error));
+                // 无法获取元数据时同样无法读取内容，不再重复记录警告
+                self.stats.files_visited += 1;
+                self.progress.file_done();
+                return NodeSummary::new();
+            }
+        };
+        let summary = self.summarize_contents(path, &metadata);
+        self.stats.files_visited += 1;
+        self.progress.file_done();
+        summary
+    }
+
+    /// 根据已知的文件元数据，读取文件内容并获取总结信息
+    /// 构建过程中已获取过元数据时使用，避免重复访问文件系统；不计入访问的文件数量
+    /// 设置了缓存且文件未发生变化时，直接复用缓存的总结信息
+    pub(crate) fn summarize_contents(&mut self, path: &Path, metadata: &FsMetadata) -> NodeSummary {
+        if let Some(mut summary) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(path, metadata))
+        {
+            summary.summarized_at = Some(std::time::SystemTime::now());
+            // 修改权限或属主不会改变修改时间，因此以最新的元数据为准
+            summary.record_metadata(metadata);
+            summary.top_n = self.top_n;
+            summary.record_file(path);
+            self.progress.bytes_done(summary.size);
+            return summary;
+        }
+        let mut summary = NodeSummary::new();
+        summary.summarized_at = Some(std::time::SystemTime::now());
+        summary.top_n = self.top_n;
+        summary.record_metadata(metadata);
+        summary.suffixes = get_file_suffix(path).into_iter().collect();
+        // 超出大小上限的文件仅记录大小
+        if let Some(max_file_size) = self.max_file_size.filter(|max| metadata.len > *max) {
+            self.warnings.push(
+                Warning::new(
+                    path.to_string_lossy(),
+                    format!(
+                        "file size {} exceeds the cap of {}",
+                        metadata.len, max_file_size
+                    ),
+                )
+                .with_kind(WarningKind::SizeCapExceeded),
+            );
+            summary.language = Language::detect(path, &[]);
+            summary.mime = mime::detect(path, &[]).map(String::from);
+            if is_generated(path, &[], metadata.len, 0) {
+                summary.origin = Some(CodeOrigin::Generated);
+            }
+            #[cfg(feature = "compress")]
+            {
+                summary.compressed_size = metadata.len;
+            }
+            summary.record_file(path);
+            self.progress.bytes_done(summary.size);
+            return summary;
+        }
+        // 仅保留开头的一段内容用于识别语言、编码等，其余内容逐块读取统计
+        let fs = self.fs.clone();
+        let mut head = Vec::new();
+        let opened = fs.open(path).and_then(|mut reader| {
+            reader
+                .by_ref()
+                .take(HEAD_LEN as u64)
+                .read_to_end(&mut head)?;
+            Ok(reader)
+        });
+        self.stats.bytes_read += head.len() as u64;
+        summary.language = Language::detect(path, &head);
+        summary.interpreter = shebang_interpreter(&head).map(String::from);
+        summary.license = license::detect(path, &head);
+        summary.mime = mime::detect(path, &head).map(String::from);
+        #[cfg(feature = "manifest")]
+        {
+            summary.manifest = ManifestInfo::parse(path, &head);
+        }
+        let counted = opened.and_then(|reader| self.count_contents(&mut summary, &head, reader));
+        if is_generated(path, &head, summary.size, summary.count) {
+            summary.origin = Some(CodeOrigin::Generated);
+        }
+        summary.record_file(path);
+        match counted {
+            Ok(_) => {
+                if let Some(cache) = &self.cache {
+                    cache.insert(path, metadata, &summary);
+                }
+            }
+            Err(error) => {
+                self.stats.errors_skipped += 1;
+                self.warnings
+                    .push(Warning::from_io(path.to_string_lossy(), &error));
+            }
+        }
+        self.progress.bytes_done(summary.size);
+        summary
+    }
+
+    /// 识别开头内容的编码与图片信息，再逐块读取其余内容，计算摘要并统计行数，二进制内容不统计行数
+    /// 根据配置同时统计词数、字符数、代码标记与 token 数量
+    /// - head：已读取的开头内容
+    /// - reader：其余内容的读取源
+    fn count_contents(
+        &mut self,
+        summary: &mut NodeSummary,
+        head: &[u8],
+        mut reader: impl Read,
+    ) -> std::io::Result<()> {
+        // 开头内容可能恰好截断了多字节字符，识别编码时去除末尾不完整的字符
+        let complete = match std::str::from_utf8(head) {
+            Err(error) if head.len() == HEAD_LEN && error.error_len().is_none() => {
+                &head[..error.valid_up_to()]
+            }
+            _ => head,
+        };
+        summary.encoding = Encoding::detect(complete);
+        summary.image = ImageInfo::parse(head);
+        // 识别为 Latin-1、GBK 等传统编码的内容虽然不是合法的 UTF-8，但仍作为文本统计
+        let legacy = matches!(summary.encoding, Some(Encoding::Latin1 | Encoding::Gbk));
+        let binary = is_binary(head) && !legacy;
+        summary.is_binary = Some(binary);
+        let text = (!binary).then(|| TextCounter::new(summary.language, &self.markers));
+        let mut sink = ContentSink::new(text);
+        sink.write(head)?;
+        let mut buffer = vec![0; READ_BUFFER_LEN];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            self.stats.bytes_read += read as u64;
+            sink.write(&buffer[..read])?;
+        }
+        #[cfg(feature = "hash")]
+        {
+            use sha2::Digest;
+            summary.digest = Some(sink.hasher.finalize().into());
+        }
+        #[cfg(feature = "compress")]
+        {
+            summary.compressed_size = sink.encoder.finish()?.0;
+        }
+        let Some(mut text) = sink.text else {
+            return Ok(());
+        };
+        text.finish();
+        summary.count = text.lines;
+        summary.line_ending = text.line_ending();
+        summary.lossy = text.lossy;
+        summary.lines = text.line_stats();
+        summary.max_line_length = text.max_line_length;
+        summary.functions = text.functions;
+        if self.count_words {
+            summary.words = text.words;
+            summary.chars = text.chars;
+        }
+        if self.count_tokens {
+            summary.tokens = text.tokens();
+        }
+        summary.markers = text.markers;
+        Ok(())
+    }
+}
+
+/// 逐块处理文件内容时的累加状态
+/// - hasher：内容摘要，需要开启 `hash` 特性
+/// - encoder：压缩后的大小，需要开启 `compress` 特性
+/// - text：文本统计，二进制内容为空
+struct ContentSink<'a> {
+    /// 内容摘要
+    #[cfg(feature = "hash")]
+    hasher: sha2::Sha256,
+    /// 以默认压缩级别进行 gzip 压缩，仅统计压缩后的字节数
+    #[cfg(feature = "compress")]
+    encoder: flate2::write::GzEncoder<ByteCount>,
+    /// 文本统计
+    text: Option<TextCounter<'a>>,
+}
+
+impl<'a> ContentSink<'a> {
+    /// 创建累加状态，`text` 为空时不统计文本
+    fn new(text: Option<TextCounter<'a>>) -> Self {
+        ContentSink {
+            #[cfg(feature = "hash")]
+            hasher: sha2::Digest::new(),
+            #[cfg(feature = "compress")]
+            encoder: flate2::write::GzEncoder::new(
+                ByteCount::default(),
+                flate2::Compression::default(),
+            ),
+            text,
+        }
+    }
+
+    /// 处理一块内容
+    fn write(&mut self, chunk: &[u8]) -> std::io::Result<()> {
+        #[cfg(feature = "hash")]
+        sha2::Digest::update(&mut self.hasher, chunk);
+        #[cfg(feature = "compress")]
+        std::io::Write::write_all(&mut self.encoder, chunk)?;
+        if let Some(text) = &mut self.text {
+            text.write(chunk);
+        }
+        Ok(())
+    }
+}
+
+/// 仅统计写入字节数的输出目标
+#[cfg(feature = "compress")]
+#[derive(Default)]
+struct ByteCount(u64);
+
+#[cfg(feature = "compress")]
+impl std::io::Write for ByteCount {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 是否跳过读取节点的内容，如沙箱模式下逃逸出根路径的节点、特殊文件与损坏的符号链接
+fn is_skipped(node: &TreeNode) -> bool {
+    matches!(
+        node.marker,
+        Some(
+            NodeMarker::OutsideRoot { .. }
+                | NodeMarker::SpecialFile { .. }
+                | NodeMarker::BrokenSymlink { .. }
+        )
+    )
+}
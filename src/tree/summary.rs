@@ -4,28 +4,448 @@
 //! 对于不同的节点，计算规则如下：
 //! - 文件：直接计算
 //! - 目录：统计目录下所有文件的累加
+use crate::fs::FsMetadata;
+use crate::tree::encoding::Encoding;
+#[cfg(feature = "git")]
+use crate::tree::git::GitCommit;
+use crate::tree::image::{ImageFormat, ImageInfo};
+use crate::tree::language::Language;
+#[cfg(feature = "manifest")]
+use crate::tree::manifest::{ManifestInfo, ManifestKind};
 use crate::tree::node::TreeNode;
-use crate::tree::node::count::get_file_count;
-use crate::tree::node::file::get_file_size;
+use crate::tree::origin::{CodeOrigin, is_vendor_dir};
+#[cfg(feature = "unix-metadata")]
+use crate::tree::owner::Owner;
+use crate::tree::summarizer::Summarizer;
+use std::cmp::Reverse;
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-#[derive(Debug, Clone)]
+/// 单个后缀或语言的统计信息
+/// - files：文件数量
+/// - size：磁盘占用大小
+/// - lines：文本行数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ExtStats {
+    /// 文件数量
+    pub files: u64,
+    /// 磁盘占用大小
+    pub size: u64,
+    /// 文本行数
+    pub lines: u64,
+}
+
+impl ExtStats {
+    /// 累加另一份统计信息
+    pub fn merge(&mut self, other: &ExtStats) {
+        self.files += other.files;
+        self.size += other.size;
+        self.lines += other.lines;
+    }
+}
+
+/// 按类型划分的行数
+/// - code：代码行
+/// - comments：注释行
+/// - blanks：空白行
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct LineStats {
+    /// 代码行，即既不是空白行也不是注释行的行
+    pub code: u64,
+    /// 注释行，包括单行注释与块注释中的非空白行
+    pub comments: u64,
+    /// 空白行，仅包含空白字符的行
+    pub blanks: u64,
+}
+
+impl LineStats {
+    /// 总行数
+    pub fn total(&self) -> u64 {
+        self.code + self.comments + self.blanks
+    }
+
+    /// 累加另一份统计信息
+    pub fn merge(&mut self, other: &LineStats) {
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blanks += other.blanks;
+    }
+}
+
+/// 文件的换行符风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum LineEnding {
+    /// 仅使用 `\n`
+    Lf,
+    /// 仅使用 `\r\n`
+    Crlf,
+    /// 同时使用 `\n` 与 `\r\n`
+    Mixed,
+}
+
+/// 按换行符风格划分的文件数量
+/// - lf：仅使用 `\n` 的文件数量
+/// - crlf：仅使用 `\r\n` 的文件数量
+/// - mixed：混用两者的文件数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct LineEndingStats {
+    /// 仅使用 `\n` 的文件数量
+    pub lf: u64,
+    /// 仅使用 `\r\n` 的文件数量
+    pub crlf: u64,
+    /// 混用两者的文件数量，统一换行符时需要优先处理
+    pub mixed: u64,
+}
+
+impl LineEndingStats {
+    /// 累加另一份统计信息
+    pub fn merge(&mut self, other: &LineEndingStats) {
+        self.lf += other.lf;
+        self.crlf += other.crlf;
+        self.mixed += other.mixed;
+    }
+}
+
+/// 文件大小分布的各区间上限（不含），依次为 1 KiB、10 KiB、100 KiB、1 MiB、10 MiB、100 MiB，最后一个区间没有上限
+pub const SIZE_BUCKETS: [u64; 6] = [1 << 10, 10 << 10, 100 << 10, 1 << 20, 10 << 20, 100 << 20];
+
+/// 文件大小分布，按 `SIZE_BUCKETS` 划分的各区间中的文件数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct SizeHistogram {
+    /// 各区间的文件数量，最后一项为不小于 100 MiB 的文件
+    pub counts: [u64; SIZE_BUCKETS.len() + 1],
+}
+
+impl SizeHistogram {
+    /// 记录一个文件的大小
+    pub fn record(&mut self, size: u64) {
+        let index = SIZE_BUCKETS.partition_point(|bound| *bound <= size);
+        self.counts[index] += 1;
+    }
+
+    /// 累加另一份统计信息
+    pub fn merge(&mut self, other: &SizeHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+
+    /// 依次获取各区间的下限、上限（不含，最后一个区间为空）与文件数量
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::summary::SizeHistogram;
+    ///
+    /// let mut histogram = SizeHistogram::default();
+    /// histogram.record(100);
+    /// histogram.record(4096);
+    /// let buckets: Vec<_> = histogram.buckets().collect();
+    /// assert_eq!(buckets[0], (0, Some(1024), 1));
+    /// assert_eq!(buckets[1], (1024, Some(10240), 1));
+    /// assert_eq!(buckets[6], (100 << 20, None, 0));
+    /// ```
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, Option<u64>, u64)> + '_ {
+        self.counts.iter().enumerate().map(|(index, count)| {
+            let lower = index.checked_sub(1).map_or(0, |index| SIZE_BUCKETS[index]);
+            (lower, SIZE_BUCKETS.get(index).copied(), *count)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 /// 节点总结信息
 /// - size: 磁盘占用大小
+/// - disk_size: 实际分配的磁盘大小
 /// - count: 包含文本行数
-/// - updated_at: 最后更新时间
-/// - suffixes: 后缀
-///   - 文件：当前文件的后缀
-///   - 目录：当前目录下所有文件的后缀
+/// - file_count: 包含的文件数量
+/// - dir_count: 包含的目录数量
+/// - direct: 直接位于目录下的文件的统计信息
+/// - direct_dir_count: 直接位于目录下的子目录数量
+/// - avg_file_size: 文件的平均大小
+/// - median_file_size: 文件大小的中位数
+/// - size_histogram: 文件大小的分布
+/// - symlink_count: 符号链接的数量
+/// - broken_symlink_count: 损坏的符号链接的数量
+/// - is_empty: 是否为空目录
+/// - empty_dir_count: 空目录的数量
+/// - hidden_count: 隐藏文件与目录的数量
+/// - hidden_size: 隐藏文件与目录的总大小
+/// - max_depth: 文件的最大深度
+/// - avg_depth: 文件的平均深度
+/// - deepest_path: 最深的文件路径
+/// - lines: 按代码、注释、空白划分的行数
+/// - max_line_length: 最长一行的字符数
+/// - functions: 声明的函数数量
+/// - words: 文本的词数
+/// - chars: 文本的字符数
+/// - markers: 各代码标记出现的次数
+/// - tokens: 估算的 token 数量
+/// - line_ending: 文件的换行符风格
+/// - line_endings: 按换行符风格划分的文件数量
+/// - updated_at: 最后修改时间
+/// - summarized_at: 获取总结信息的时间
+/// - suffixes: 后缀，不包含 `.` 且统一为小写
+///   - 文件：当前文件的后缀，没有后缀时为空
+///   - 目录：当前目录下所有文件的后缀，去重并按字典序排列
+/// - by_extension: 按后缀划分的统计信息，键为后缀，没有后缀的文件记录在空字符串下
+/// - language: 文件的语言，目录为空
+/// - by_language: 按语言划分的统计信息，无法识别语言的文件不计入
+/// - interpreter: 脚本 shebang 指定的解释器，目录为空
+/// - by_interpreter: 按解释器划分的脚本数量
+/// - license: 许可证文件的 SPDX 标识符
+/// - licenses: 发现的所有许可证文件及其 SPDX 标识符
+/// - is_binary: 文件是否为二进制
+/// - lossy: 是否包含以有损方式解码统计的文本文件
+/// - text: 文本文件的统计信息
+/// - binary: 二进制文件的统计信息
+/// - origin: 生成代码或第三方代码的标记
+/// - generated: 生成文件的统计信息
+/// - vendored: 第三方代码的统计信息
+/// - encoding: 文件的文本编码，目录为空
+/// - by_encoding: 按文本编码划分的文件数量
+/// - mime: 文件的 MIME 类型，目录为空
+/// - by_mime: 按 MIME 类型划分的文件数量
+/// - image: 图片的格式与尺寸，目录为空
+/// - pixels: 图片的像素总数
+/// - by_image_format: 按图片格式划分的图片数量
+/// - largest_file: 占用最大的文件路径及其大小
+/// - largest_files: 占用最大的若干个文件路径及其大小
+/// - newest_mtime: 最近的修改时间
+/// - oldest_mtime: 最早的修改时间
+/// - created_at: 创建时间
+/// - mode: 文件的权限位
+/// - world_writable: 是否包含所有用户可写的文件
+/// - setuid: 是否包含设置了 setuid 的文件
+/// - executable_count: 可执行文件的数量
+/// - digest: 内容摘要，需要开启 `hash` 特性
+/// - owner: 文件的属主信息，需要开启 `unix-metadata` 特性
+/// - by_owner: 按属主划分的统计信息，需要开启 `unix-metadata` 特性
+/// - last_commit: 最近一次提交，需要开启 `git` 特性
+/// - compressed_size: 估算的 gzip 压缩后大小，需要开启 `compress` 特性
+/// - manifest: 清单文件的解析结果，需要开启 `manifest` 特性
+/// - by_manifest: 按清单类型划分的清单数量，需要开启 `manifest` 特性
+/// - dependencies: 清单声明的运行时依赖数量，需要开启 `manifest` 特性
+/// - dev_dependencies: 清单声明的开发依赖数量，需要开启 `manifest` 特性
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NodeSummary {
     /// u64 磁盘占用大小，默认为 0
     pub size: u64,
+    /// 实际分配的磁盘大小（按块计算），目录节点为其下所有文件之和
+    /// 稀疏文件小于 `size`，大量小文件则因块对齐而大于 `size`；无法获取时（如非 Unix 平台、内存文件系统）与 `size` 相同
+    /// 与 `du` 的结果不完全一致：硬链接的文件在每个路径下各计一次，目录自身占用的块也不计入
+    pub disk_size: u64,
     /// u64 包含文本行数，默认为 0
     pub count: u64,
-    /// 最后更新时间，若没有启动 `project_tree.summarize` 则为空
+    /// 包含的文件数量，文件节点为 1，目录节点为其下所有文件的数量
+    pub file_count: u64,
+    /// 包含的目录数量，即当前节点之下所有层级的目录之和（不包括节点自身），文件节点为 0
+    /// 与 `file_count` 一同使用，无需遍历子节点即可得知目录的规模
+    pub dir_count: u64,
+    /// 直接位于目录下（不包括子目录中）的文件数量、大小与行数，文件节点为空
+    /// 其余统计均为递归汇总的结果，界面中仅展示当前目录内容时可直接使用
+    pub direct: ExtStats,
+    /// 直接位于目录下的子目录数量，文件节点为 0
+    pub direct_dir_count: u64,
+    /// 文件的平均大小（向下取整），没有文件时为 0
+    pub avg_file_size: u64,
+    /// 文件大小的中位数，文件数量为偶数时取中间两者的平均值（向下取整），没有文件时为 0
+    /// 与平均大小一同可以区分“大量小文件”与“少数大文件”
+    pub median_file_size: u64,
+    /// 文件大小的分布，目录节点为其下所有文件，根节点即为整个项目的分布，可用于存储分析
+    pub size_histogram: SizeHistogram,
+    /// 当前节点之下符号链接的数量（不包括节点自身），包括损坏的符号链接
+    pub symlink_count: u64,
+    /// 当前节点之下指向不存在目标的符号链接的数量（不包括节点自身）
+    pub broken_symlink_count: u64,
+    /// 是否为空目录，即子节点已加载且没有任何子节点，文件与尚未展开的目录为 false
+    pub is_empty: bool,
+    /// 空目录的数量，包括当前目录自身
+    pub empty_dir_count: u64,
+    /// 当前节点之下隐藏文件与目录的数量（不包括节点自身），隐藏目录之下的隐藏节点同样计入
+    pub hidden_count: u64,
+    /// 当前节点之下隐藏文件与隐藏目录的总大小，隐藏目录中的所有文件均计入，且不会重复计算
+    /// 可用于了解 `.git`、`.cache` 等隐藏内容在目录中的占比
+    pub hidden_size: u64,
+    /// 文件相对当前节点的最大深度，直接位于目录下的文件深度为 1，文件节点自身为 0
+    /// 根节点的深度统计可用于发现生成代码中的异常嵌套
+    pub max_depth: u64,
+    /// 文件相对当前节点的平均深度，没有文件时为 0
+    pub avg_depth: f64,
+    /// 深度最大的文件路径，深度相同时取路径字典序较小者，没有文件时为空
+    pub deepest_path: Option<PathBuf>,
+    /// 各文件相对当前节点的深度之和，用于汇总时计算平均深度，属于内部状态，不参与序列化
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub(crate) depth_sum: u64,
+    /// 按代码、注释、空白划分的行数，三者之和与 `count` 一致
+    /// 无法识别语言的文件中，除空白行外均视为代码行
+    pub lines: LineStats,
+    /// 最长一行的字符数，文件节点为其自身，目录节点为其下所有文件中的最大值，二进制文件为 0
+    /// 可用于发现伪装成源码的压缩或生成文件，或检查代码风格中的行宽限制
+    pub max_line_length: u64,
+    /// 根据语言的函数声明关键字粗略统计的函数数量，目录节点为其下所有文件之和
+    /// 无法识别语言或语言没有简单的声明关键字（如 C、Java）时为 0，可作为项目复杂度的粗略参考
+    pub functions: u64,
+    /// 文本的词数，需要在配置中开启 `count_words`，未开启时与二进制文件一样为 0
+    /// 以空白分隔词语，每个汉字、假名单独计为一个词
+    pub words: u64,
+    /// 文本的字符数（Unicode 标量值的数量，包括换行符），需要在配置中开启 `count_words`
+    pub chars: u64,
+    /// 各代码标记（如 `TODO`、`FIXME`）出现的次数，需要在配置中设置 `markers`，默认为空
+    /// 目录节点为其下所有文件之和，可直接用于技术债务的统计
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub markers: HashMap<String, u64>,
+    /// 估算的 token 数量，需要在配置中开启 `count_tokens`，未开启时与二进制文件一样为 0
+    /// 目录节点为其下所有文件之和，可直接用于预估将其输入大语言模型时的上下文占用
+    pub tokens: u64,
+    /// 文件的换行符风格，目录、二进制文件以及没有换行符的文件为空
+    pub line_ending: Option<LineEnding>,
+    /// 按换行符风格划分的文件数量
+    pub line_endings: LineEndingStats,
+    /// 最后修改时间，文件节点为其自身的修改时间，目录节点为其下所有节点中最晚的修改时间
+    /// 文件系统不支持修改时间或目录下没有文件时为空
     pub updated_at: Option<std::time::SystemTime>,
+    /// 获取总结信息的时间，即扫描的时间，若没有启动 `project_tree.summarize` 则为空
+    pub summarized_at: Option<std::time::SystemTime>,
     /// 包含的文件后缀，默认为空
     pub suffixes: Vec<String>,
+    /// 按后缀划分的文件数量、大小与行数，目录节点为其下所有文件，文件节点为空（其后缀见 `suffixes`）
+    /// 目录中各后缀的统计信息之和与 `size`、`count` 一致，可用于回答“该目录中 .rs 与 .json 各占多少”
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_extension: HashMap<String, ExtStats>,
+    /// 文件的语言，根据后缀或 shebang 识别，无法识别的文件与目录为空
+    pub language: Option<Language>,
+    /// 按语言划分的文件数量、大小与行数，目录节点为其下所有文件，文件节点为空（其语言见 `language`）
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_language: HashMap<Language, ExtStats>,
+    /// 脚本首行 shebang 指定的解释器名称（如 `bash`、`python3`），没有 shebang 的文件与目录为空
+    /// 与 `language` 不同，可以区分同一语言的不同解释器，如 `sh` 与 `bash`
+    pub interpreter: Option<String>,
+    /// 按解释器划分的脚本数量，默认为空
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_interpreter: HashMap<String, u64>,
+    /// 许可证文件（如 `LICENSE`、`COPYING`）的 SPDX 标识符，无法识别内容时为 `NOASSERTION`，其他文件与目录为空
+    pub license: Option<String>,
+    /// 发现的许可证文件路径及其 SPDX 标识符，按路径的字典序排列，文件节点为其自身，目录节点为其下所有许可证文件
+    pub licenses: Vec<(PathBuf, String)>,
+    /// 占用最大的文件路径及其大小，文件节点为其自身，目录节点为其下所有文件中最大者，没有文件时为空
+    /// 大小相同时取路径字典序较小者，结果与子节点的顺序无关
+    pub largest_file: Option<(PathBuf, u64)>,
+    /// 占用最大的若干个文件路径及其大小，按大小从大到小排列，大小相同时按路径的字典序排列
+    /// 需要在配置中设置 `track_top_n`，数量不超过该值，未设置时为空
+    pub largest_files: Vec<(PathBuf, u64)>,
+    /// 记录的最大文件数量，汇总时取子节点中的最大值，属于内部状态，不参与序列化
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub(crate) top_n: usize,
+    /// 文件是否为二进制，根据开头一段内容是否包含空字节或不是合法的 UTF-8 判断
+    /// 目录与未读取内容的文件（如超出大小上限、读取失败）为空，二进制文件不统计行数
+    pub is_binary: Option<bool>,
+    /// 文本内容不是合法的 UTF-8（如 Latin-1、GBK 文本，或夹杂无效字节的文本），行数按字节统计，
+    /// 其余文本统计以替换字符代替无法解码的字节，结果可能不准确；目录节点表示其下是否存在这样的文件
+    pub lossy: bool,
+    /// 文本文件的数量、大小与行数，未读取内容的文件不计入
+    pub text: ExtStats,
+    /// 二进制文件的数量与大小，未读取内容的文件不计入
+    pub binary: ExtStats,
+    /// 文件或目录看起来由工具生成或来自第三方依赖时的来源，见 `origin` 模块
+    /// 第三方依赖目录及其下的所有节点为第三方代码；其余文件看起来由工具生成时为生成的文件，
+    /// 其余目录在其下所有文件均为生成的文件时为生成的代码，其余为空
+    pub origin: Option<CodeOrigin>,
+    /// 生成文件的数量、大小与行数，第三方目录下的文件仅计入 `vendored`
+    pub generated: ExtStats,
+    /// 第三方依赖目录下文件的数量、大小与行数
+    pub vendored: ExtStats,
+    /// 文件的文本编码，根据开头的内容推断，目录、未读取内容的文件与不像文本的内容为空
+    /// 识别为 GBK、Latin-1 编码的文件视为文本，按字节统计行数（见 `lossy`）
+    pub encoding: Option<Encoding>,
+    /// 按文本编码划分的文件数量，默认为空，可用于了解目录中的编码分布
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_encoding: HashMap<Encoding, u64>,
+    /// 文件的 MIME 类型，根据后缀或魔数识别，空文件与目录为空
+    pub mime: Option<String>,
+    /// 按 MIME 类型划分的文件数量，默认为空，无法识别类型的文件不计入
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_mime: HashMap<String, u64>,
+    /// 图片的格式与尺寸，根据文件头识别，目录、非图片文件与未读取内容的文件为空
+    pub image: Option<ImageInfo>,
+    /// 图片的像素总数，文件节点为其自身，目录节点为其下所有图片之和
+    pub pixels: u64,
+    /// 按图片格式划分的图片数量，默认为空
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_image_format: HashMap<ImageFormat, u64>,
+    /// 最近的修改时间，文件节点为其自身的修改时间，目录节点为其下所有文件中最晚者
+    /// 文件系统不支持修改时间或目录下没有文件时为空
+    pub newest_mtime: Option<SystemTime>,
+    /// 最早的修改时间，文件节点为其自身的修改时间，目录节点为其下所有文件中最早者
+    /// 可用于找出长期未改动的模块
+    pub oldest_mtime: Option<SystemTime>,
+    /// 创建时间，文件节点为其自身的创建时间，目录节点为其下所有文件中最早者
+    /// 平台或文件系统不支持创建时间时为空，可用于区分遗留代码与新增代码
+    pub created_at: Option<SystemTime>,
+    /// 文件的权限位（如 `0o644`，包含 setuid 等特殊位），目录与非 Unix 平台为空
+    pub mode: Option<u32>,
+    /// 是否包含所有用户可写的文件，文件节点表示其自身，目录节点表示其下任一文件
+    pub world_writable: bool,
+    /// 是否包含设置了 setuid 的文件，文件节点表示其自身，目录节点表示其下任一文件
+    pub setuid: bool,
+    /// 可执行文件的数量，即任一用户拥有执行权限的文件，文件节点为 0 或 1，目录节点为其下所有文件之和
+    /// 非 Unix 平台始终为 0，可用于检查脚本意外丢失或获得执行权限的情况
+    pub executable_count: u64,
+    /// 内容的 SHA-256 摘要，未读取内容的文件（如超出大小上限、读取失败）为空
+    /// 目录的摘要由子节点的名称与摘要计算得到（Merkle 摘要），与子节点的顺序无关，
+    /// 两个目录的摘要相同即可认为其内容完全一致；其下任一节点没有摘要（如未读取内容的文件、尚未展开的目录）时为空
+    #[cfg(feature = "hash")]
+    pub digest: Option<[u8; 32]>,
+    /// 文件的属主与属组，目录与非 Unix 平台为空
+    #[cfg(feature = "unix-metadata")]
+    pub owner: Option<Owner>,
+    /// 按属主的用户 ID 划分的文件数量、大小与行数，默认为空，用户名可通过 `owner::user_name` 解析
+    #[cfg(feature = "unix-metadata")]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_owner: HashMap<u32, ExtStats>,
+    /// 最近一次修改的提交，文件节点为最近修改该文件的提交，目录节点为其下所有文件中最近的提交（即“最近由谁修改”）
+    /// 需要调用 `ProjectTree::annotate_git` 写入，未被跟踪的文件与目录下没有已跟踪文件时为空
+    #[cfg(feature = "git")]
+    pub last_commit: Option<GitCommit>,
+    /// 估算的 gzip 压缩后大小（默认压缩级别，包括 gzip 头尾），目录节点为其下所有文件之和
+    /// 未读取内容的文件（如超出大小上限）按原始大小计入，可用于预测打包或部署产物的大小
+    #[cfg(feature = "compress")]
+    pub compressed_size: u64,
+    /// 清单文件（如 `Cargo.toml`、`package.json`）的包名、依赖数量与工作区信息，其他文件与目录为空
+    #[cfg(feature = "manifest")]
+    pub manifest: Option<ManifestInfo>,
+    /// 按清单类型划分的清单数量，默认为空
+    #[cfg(feature = "manifest")]
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub by_manifest: HashMap<ManifestKind, u64>,
+    /// 清单声明的运行时依赖数量，目录节点为其下所有清单之和
+    #[cfg(feature = "manifest")]
+    pub dependencies: u64,
+    /// 清单声明的开发依赖数量，目录节点为其下所有清单之和
+    #[cfg(feature = "manifest")]
+    pub dev_dependencies: u64,
 }
 
 impl Display for NodeSummary {
@@ -42,15 +462,158 @@ impl NodeSummary {
     /// 创建节点总结实例
     /// 以默认值填充：
     /// - size: 0
+    /// - disk_size: 0
     /// - count: 0
+    /// - file_count: 0
+    /// - dir_count: 0
+    /// - direct: ExtStats::default
+    /// - direct_dir_count: 0
+    /// - avg_file_size: 0
+    /// - median_file_size: 0
+    /// - size_histogram: SizeHistogram::default
+    /// - symlink_count: 0
+    /// - broken_symlink_count: 0
+    /// - is_empty: false
+    /// - empty_dir_count: 0
+    /// - hidden_count: 0
+    /// - hidden_size: 0
+    /// - max_depth: 0
+    /// - avg_depth: 0.0
+    /// - deepest_path: None
+    /// - lines: LineStats::default
+    /// - max_line_length: 0
+    /// - functions: 0
+    /// - words: 0
+    /// - chars: 0
+    /// - markers: HashMap::new
+    /// - tokens: 0
+    /// - line_ending: None
+    /// - line_endings: LineEndingStats::default
     /// - updated_at: None
+    /// - summarized_at: None
     /// - suffixes: Vec::new
+    /// - by_extension: HashMap::new
+    /// - language: None
+    /// - by_language: HashMap::new
+    /// - interpreter: None
+    /// - by_interpreter: HashMap::new
+    /// - license: None
+    /// - licenses: Vec::new
+    /// - largest_file: None
+    /// - largest_files: Vec::new
+    /// - is_binary: None
+    /// - lossy: false
+    /// - text: ExtStats::default
+    /// - binary: ExtStats::default
+    /// - origin: None
+    /// - generated: ExtStats::default
+    /// - vendored: ExtStats::default
+    /// - encoding: None
+    /// - by_encoding: HashMap::new
+    /// - mime: None
+    /// - by_mime: HashMap::new
+    /// - image: None
+    /// - pixels: 0
+    /// - by_image_format: HashMap::new
+    /// - newest_mtime: None
+    /// - oldest_mtime: None
+    /// - created_at: None
+    /// - mode: None
+    /// - world_writable: false
+    /// - setuid: false
+    /// - executable_count: 0
+    /// - digest: None
+    /// - owner: None
+    /// - by_owner: HashMap::new
+    /// - last_commit: None
+    /// - compressed_size: 0
+    /// - manifest: None
+    /// - by_manifest: HashMap::new
+    /// - dependencies: 0
+    /// - dev_dependencies: 0
     pub fn new() -> Self {
         NodeSummary {
             size: 0,
+            disk_size: 0,
             count: 0,
+            file_count: 0,
+            dir_count: 0,
+            direct: ExtStats::default(),
+            direct_dir_count: 0,
+            avg_file_size: 0,
+            median_file_size: 0,
+            size_histogram: SizeHistogram::default(),
+            symlink_count: 0,
+            broken_symlink_count: 0,
+            is_empty: false,
+            empty_dir_count: 0,
+            hidden_count: 0,
+            hidden_size: 0,
+            max_depth: 0,
+            avg_depth: 0.0,
+            deepest_path: None,
+            depth_sum: 0,
+            lines: LineStats::default(),
+            max_line_length: 0,
+            functions: 0,
+            words: 0,
+            chars: 0,
+            markers: HashMap::new(),
+            tokens: 0,
+            line_ending: None,
+            line_endings: LineEndingStats::default(),
             updated_at: None,
+            summarized_at: None,
             suffixes: Vec::new(),
+            by_extension: HashMap::new(),
+            language: None,
+            by_language: HashMap::new(),
+            interpreter: None,
+            by_interpreter: HashMap::new(),
+            license: None,
+            licenses: Vec::new(),
+            largest_file: None,
+            largest_files: Vec::new(),
+            top_n: 0,
+            is_binary: None,
+            lossy: false,
+            text: ExtStats::default(),
+            binary: ExtStats::default(),
+            origin: None,
+            generated: ExtStats::default(),
+            vendored: ExtStats::default(),
+            encoding: None,
+            by_encoding: HashMap::new(),
+            mime: None,
+            by_mime: HashMap::new(),
+            image: None,
+            pixels: 0,
+            by_image_format: HashMap::new(),
+            newest_mtime: None,
+            oldest_mtime: None,
+            created_at: None,
+            mode: None,
+            world_writable: false,
+            setuid: false,
+            executable_count: 0,
+            #[cfg(feature = "hash")]
+            digest: None,
+            #[cfg(feature = "unix-metadata")]
+            owner: None,
+            #[cfg(feature = "unix-metadata")]
+            by_owner: HashMap::new(),
+            #[cfg(feature = "git")]
+            last_commit: None,
+            #[cfg(feature = "compress")]
+            compressed_size: 0,
+            #[cfg(feature = "manifest")]
+            manifest: None,
+            #[cfg(feature = "manifest")]
+            by_manifest: HashMap::new(),
+            #[cfg(feature = "manifest")]
+            dependencies: 0,
+            #[cfg(feature = "manifest")]
+            dev_dependencies: 0,
         }
     }
 
@@ -69,6 +632,7 @@ impl NodeSummary {
     /// 更新节点的总结信息
     /// - node：{&mut TreeNode} 可变节点实例
     /// - return：{NodeSummary}
+    ///
     /// 该函数属于 `NodeSummary` 模块，不直接绑定于 `TreeNode`，即不直接修改 `TreeNode.summary`
     /// 若有手动更新某节点信息的需求，则需要在调用该函数后手动赋值 `node.summary = summary;`
     /// 但在某节点的 `update` 过程中，子节点的 `summary` 会自动赋值，无需手动处理，最终返回的总结信息为当前启动节点的总结信息
@@ -92,42 +656,502 @@ impl NodeSummary {
     /// assert_eq!(node.summary.count > 0, true);
     /// ```
     pub fn update(node: &mut TreeNode) -> NodeSummary {
-        // 实例化节点总结对象
+        Summarizer::default().summarize(node)
+    }
+
+    /// 根据子节点现有的总结信息汇总目录节点的总结信息
+    /// - node：{&TreeNode} 目录节点实例
+    /// - return：{NodeSummary}
+    ///
+    /// 与 `update` 不同，该函数不会递归更新子节点，也不会读取任何文件，仅对子节点的 `summary` 进行累加，
+    /// 适用于局部子树更新后重新汇总其祖先节点
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::node::TreeNode;
+    /// use arui_core::tree::summary::NodeSummary;
+    ///
+    /// let mut node = TreeNode::new("./tests/examples/tree/summary", true);
+    /// let mut sub_node = TreeNode::new("./tests/examples/tree/summary/test.rs", false);
+    /// sub_node.upsert_summary();
+    /// node.children = Some(vec![sub_node]);
+    ///
+    /// let summary = NodeSummary::collect(&node);
+    /// assert_eq!(summary.size > 0, true);
+    /// ```
+    pub fn collect(node: &TreeNode) -> NodeSummary {
         let mut summary = NodeSummary::new();
-        summary.updated_at = Some(std::time::SystemTime::now());
+        summary.summarized_at = Some(std::time::SystemTime::now());
+        if let Some(children) = &node.children {
+            for child in children {
+                summary.merge(&child.summary);
+                summary.dir_count += child.is_dir as u64;
+                if child.is_dir {
+                    summary.direct_dir_count += 1;
+                } else {
+                    summary.direct.merge(&child.summary.file_stats());
+                    summary.merge_file(&child.summary);
+                }
+                summary.symlink_count += child.is_symlink() as u64;
+                summary.broken_symlink_count += child.is_broken_symlink() as u64;
+                // 隐藏节点的全部大小均计入，扣除其下已计入的部分以免重复
+                if child.is_hidden() {
+                    summary.hidden_count += 1;
+                    summary.hidden_size +=
+                        child.summary.size.saturating_sub(child.summary.hidden_size);
+                }
+            }
+            if node.is_dir && children.is_empty() {
+                summary.is_empty = true;
+                summary.empty_dir_count += 1;
+            }
+            summary.median_file_size = median_file_size(children);
+            summary.largest_files =
+                largest_files(children.iter().map(|child| &child.summary), summary.top_n);
+            summary.record_origin(node);
+            #[cfg(feature = "hash")]
+            {
+                summary.digest = digest_children(children);
+            }
+        }
+        summary
+    }
 
-        // 若非目录，直接计算当前文件，并终止递归
-        if !node.is_dir {
-            summary.size = get_file_size(&node.path).unwrap_or(0);
-            summary.count = get_file_count(&node.path).unwrap_or(0);
-            return summary;
+    /// 根据目录名与其下文件的来源，确定目录的来源
+    /// 第三方目录下的全部文件计入 `vendored`，并从 `generated` 中移除，避免重复计算
+    fn record_origin(&mut self, node: &TreeNode) {
+        let all = |stats: &ExtStats| self.file_count > 0 && stats.files == self.file_count;
+        if node.is_dir && is_vendor_dir(node.name()) {
+            self.set_vendored();
+        } else if all(&self.vendored) {
+            self.origin = Some(CodeOrigin::Vendored);
+        } else if all(&self.generated) {
+            self.origin = Some(CodeOrigin::Generated);
         }
+    }
 
-        // 若为目录，递归遍历所有子节点，从底向上获取总结信息，直到根节点
-        if let Some(children) = &mut node.children {
-            for child in children {
-                // 递归调用子节点
-                let child_summary = NodeSummary::update(child);
-                // 写入子节点
-                child.summary = child_summary.clone();
-
-                // 累加到父节点
-                summary.size += child_summary.size;
-                summary.count += child_summary.count;
-                // summary.suffixes.extend(child_summary.suffixes.clone());
+    /// 包含的不同后缀的数量，没有后缀的文件不计入
+    pub fn distinct_extensions(&self) -> usize {
+        self.by_extension
+            .iter()
+            .filter(|(suffix, stats)| !suffix.is_empty() && stats.files > 0)
+            .count()
+    }
+
+    /// 后缀的多样性，即按文件数量计算的各后缀占比的香农熵（以 2 为底），没有后缀的文件不计入
+    /// 仅有一种后缀时为 0，各后缀文件数量相同时等于后缀数量的对数；数值较大的目录通常混杂了多种职责的文件
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::summary::{ExtStats, NodeSummary};
+    ///
+    /// let mut summary = NodeSummary::new();
+    /// for suffix in ["rs", "md", "toml", "json"] {
+    ///     let stats = ExtStats { files: 2, ..Default::default() };
+    ///     summary.by_extension.insert(suffix.to_string(), stats);
+    /// }
+    /// assert_eq!(summary.distinct_extensions(), 4);
+    /// assert_eq!(summary.extension_diversity(), 2.0);
+    /// ```
+    pub fn extension_diversity(&self) -> f64 {
+        let counts: Vec<f64> = self
+            .by_extension
+            .iter()
+            .filter(|(suffix, stats)| !suffix.is_empty() && stats.files > 0)
+            .map(|(_, stats)| stats.files as f64)
+            .collect();
+        let total: f64 = counts.iter().sum();
+        counts
+            .iter()
+            .map(|count| {
+                let share = count / total;
+                -share * share.log2()
+            })
+            .sum::<f64>()
+            .max(0.0)
+    }
+
+    /// 将第三方目录下的所有节点标记为第三方代码，并将其统计信息计入 `vendored`
+    /// 目录的总结信息自底向上汇总，汇总子节点时尚不知道祖先目录是否为第三方目录，
+    /// 因此在第三方目录汇总完成后再标记其下的节点；`node` 不是第三方目录时不做任何处理
+    pub(crate) fn mark_vendored(node: &mut TreeNode) {
+        if !node.is_dir || !is_vendor_dir(node.name()) {
+            return;
+        }
+        let mut stack: Vec<&mut TreeNode> = node.children.iter_mut().flatten().collect();
+        while let Some(child) = stack.pop() {
+            child.summary.set_vendored();
+            stack.extend(child.children.iter_mut().flatten());
+        }
+    }
+
+    /// 将节点标记为第三方代码，全部文件计入 `vendored`，并从 `generated` 中移除
+    pub(crate) fn set_vendored(&mut self) {
+        self.vendored = self.file_stats();
+        self.generated = ExtStats::default();
+        self.origin = Some(CodeOrigin::Vendored);
+    }
+
+    /// 内容摘要的十六进制形式
+    #[cfg(feature = "hash")]
+    pub fn digest_hex(&self) -> Option<String> {
+        self.digest
+            .map(|digest| digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    /// 将子节点的总结信息累加到当前总结信息中
+    /// 后缀去重后按字典序排列，结果与子节点的顺序无关；子节点中文件的深度均加 1
+    pub(crate) fn merge(&mut self, child: &NodeSummary) {
+        self.size += child.size;
+        self.disk_size += child.disk_size;
+        self.count += child.count;
+        self.file_count += child.file_count;
+        self.avg_file_size = self.size.checked_div(self.file_count).unwrap_or_default();
+        self.size_histogram.merge(&child.size_histogram);
+        self.merge_depth(child);
+        self.dir_count += child.dir_count;
+        self.empty_dir_count += child.empty_dir_count;
+        self.hidden_count += child.hidden_count;
+        self.hidden_size += child.hidden_size;
+        self.symlink_count += child.symlink_count;
+        self.broken_symlink_count += child.broken_symlink_count;
+        self.lines.merge(&child.lines);
+        self.max_line_length = self.max_line_length.max(child.max_line_length);
+        self.functions += child.functions;
+        self.words += child.words;
+        #[cfg(feature = "compress")]
+        {
+            self.compressed_size += child.compressed_size;
+        }
+        self.chars += child.chars;
+        self.tokens += child.tokens;
+        for (marker, count) in &child.markers {
+            *self.markers.entry(marker.clone()).or_default() += count;
+        }
+        self.line_endings.merge(&child.line_endings);
+        for suffix in &child.suffixes {
+            if let Err(index) = self.suffixes.binary_search(suffix) {
+                self.suffixes.insert(index, suffix.clone());
+            }
+        }
+        for (suffix, stats) in &child.by_extension {
+            self.by_extension
+                .entry(suffix.clone())
+                .or_default()
+                .merge(stats);
+        }
+        for (language, stats) in &child.by_language {
+            self.by_language.entry(*language).or_default().merge(stats);
+        }
+        self.text.merge(&child.text);
+        self.binary.merge(&child.binary);
+        self.generated.merge(&child.generated);
+        self.vendored.merge(&child.vendored);
+        for (encoding, files) in &child.by_encoding {
+            *self.by_encoding.entry(*encoding).or_default() += files;
+        }
+        for (interpreter, files) in &child.by_interpreter {
+            *self.by_interpreter.entry(interpreter.clone()).or_default() += files;
+        }
+        for (mime, files) in &child.by_mime {
+            *self.by_mime.entry(mime.clone()).or_default() += files;
+        }
+        self.pixels += child.pixels;
+        for (format, files) in &child.by_image_format {
+            *self.by_image_format.entry(*format).or_default() += files;
+        }
+        if !child.licenses.is_empty() {
+            self.licenses.extend(child.licenses.iter().cloned());
+            self.licenses.sort();
+        }
+        self.top_n = self.top_n.max(child.top_n);
+        if let Some((path, size)) = &child.largest_file {
+            let larger = match &self.largest_file {
+                Some((largest_path, largest_size)) => {
+                    size > largest_size || size == largest_size && path < largest_path
+                }
+                None => true,
+            };
+            if larger {
+                self.largest_file = Some((path.clone(), *size));
             }
         }
+        self.newest_mtime = self.newest_mtime.max(child.newest_mtime);
+        self.updated_at = self.updated_at.max(child.updated_at);
+        self.oldest_mtime = earliest(self.oldest_mtime, child.oldest_mtime);
+        self.created_at = earliest(self.created_at, child.created_at);
+        self.world_writable |= child.world_writable;
+        self.lossy |= child.lossy;
+        self.setuid |= child.setuid;
+        self.executable_count += child.executable_count;
+        #[cfg(feature = "unix-metadata")]
+        for (uid, stats) in &child.by_owner {
+            self.by_owner.entry(*uid).or_default().merge(stats);
+        }
+        #[cfg(feature = "git")]
+        if let Some(commit) = &child.last_commit
+            && self
+                .last_commit
+                .as_ref()
+                .is_none_or(|last| commit.is_newer_than(last))
+        {
+            self.last_commit = Some(commit.clone());
+        }
+        #[cfg(feature = "manifest")]
+        {
+            for (kind, manifests) in &child.by_manifest {
+                *self.by_manifest.entry(*kind).or_default() += manifests;
+            }
+            self.dependencies += child.dependencies;
+            self.dev_dependencies += child.dev_dependencies;
+        }
+    }
 
-        summary
+    /// 文件自身的数量、大小与行数
+    fn file_stats(&self) -> ExtStats {
+        ExtStats {
+            files: self.file_count,
+            size: self.size,
+            lines: self.count,
+        }
+    }
+
+    /// 将文件子节点的后缀与语言计入按后缀、按语言划分的统计信息
+    /// 文件节点自身不保存这些映射，仅在汇总到目录时根据其后缀与语言写入
+    pub(crate) fn merge_file(&mut self, file: &NodeSummary) {
+        let stats = file.file_stats();
+        let suffix = file.suffixes.first().cloned().unwrap_or_default();
+        self.by_extension.entry(suffix).or_default().merge(&stats);
+        if let Some(language) = file.language {
+            self.by_language.entry(language).or_default().merge(&stats);
+        }
+    }
+
+    /// 根据文件自身的路径、大小与行数，记录其各项统计信息以及最大文件，仅用于文件节点
+    /// 按后缀与语言划分的统计信息在汇总到目录时写入，见 `merge_file`
+    pub(crate) fn record_file(&mut self, path: &Path) {
+        let stats = ExtStats {
+            files: 1,
+            size: self.size,
+            lines: self.count,
+        };
+        self.by_extension.clear();
+        self.by_language.clear();
+        self.line_endings = LineEndingStats::default();
+        match self.line_ending {
+            Some(LineEnding::Lf) => self.line_endings.lf = 1,
+            Some(LineEnding::Crlf) => self.line_endings.crlf = 1,
+            Some(LineEnding::Mixed) => self.line_endings.mixed = 1,
+            None => {}
+        }
+        self.text = ExtStats::default();
+        self.binary = ExtStats::default();
+        match self.is_binary {
+            Some(true) => self.binary = stats,
+            Some(false) => self.text = stats,
+            None => {}
+        }
+        self.generated = ExtStats::default();
+        self.vendored = ExtStats::default();
+        match self.origin {
+            Some(CodeOrigin::Generated) => self.generated = stats,
+            Some(CodeOrigin::Vendored) => self.vendored = stats,
+            None => {}
+        }
+        self.by_encoding = self
+            .encoding
+            .iter()
+            .map(|encoding| (*encoding, 1))
+            .collect();
+        self.by_mime = self.mime.iter().map(|mime| (mime.clone(), 1)).collect();
+        self.by_interpreter = self
+            .interpreter
+            .iter()
+            .map(|interpreter| (interpreter.clone(), 1))
+            .collect();
+        self.licenses = self
+            .license
+            .iter()
+            .map(|license| (path.to_path_buf(), license.clone()))
+            .collect();
+        self.pixels = self.image.map(|image| image.pixels()).unwrap_or_default();
+        self.by_image_format = self.image.iter().map(|image| (image.format, 1)).collect();
+        self.largest_file = Some((path.to_path_buf(), self.size));
+        self.largest_files.clear();
+        if self.top_n > 0 {
+            self.largest_files.push((path.to_path_buf(), self.size));
+        }
+        self.file_count = 1;
+        self.avg_file_size = self.size;
+        self.median_file_size = self.size;
+        self.size_histogram = SizeHistogram::default();
+        self.size_histogram.record(self.size);
+        self.max_depth = 0;
+        self.avg_depth = 0.0;
+        self.depth_sum = 0;
+        self.deepest_path = Some(path.to_path_buf());
+        #[cfg(feature = "unix-metadata")]
+        {
+            self.by_owner = self
+                .owner
+                .as_ref()
+                .map(|owner| HashMap::from([(owner.uid, stats)]))
+                .unwrap_or_default();
+        }
+        #[cfg(feature = "manifest")]
+        {
+            self.by_manifest = self.manifest.iter().map(|info| (info.kind, 1)).collect();
+            self.dependencies = self.manifest.as_ref().map_or(0, |info| info.dependencies);
+            self.dev_dependencies = self
+                .manifest
+                .as_ref()
+                .map_or(0, |info| info.dev_dependencies);
+        }
+    }
+
+    /// 合并子节点的深度统计，子节点中文件的深度均加 1
+    fn merge_depth(&mut self, child: &NodeSummary) {
+        let Some(path) = &child.deepest_path else {
+            return;
+        };
+        let depth = child.max_depth + 1;
+        let deeper = match &self.deepest_path {
+            Some(deepest) => depth > self.max_depth || depth == self.max_depth && path < deepest,
+            None => true,
+        };
+        if deeper {
+            self.max_depth = depth;
+            self.deepest_path = Some(path.clone());
+        }
+        // 深度之和不参与序列化，由平均深度还原，反序列化得到的总结信息同样可以汇总
+        let child_sum = (child.avg_depth * child.file_count as f64).round() as u64;
+        self.depth_sum += child_sum + child.file_count;
+        self.avg_depth = if self.file_count == 0 {
+            0.0
+        } else {
+            self.depth_sum as f64 / self.file_count as f64
+        };
+    }
+
+    /// 根据文件的元数据记录大小、时间、权限位与属主，仅用于文件节点
+    /// 权限位同时用于标记所有用户可写与 setuid
+    pub(crate) fn record_metadata(&mut self, metadata: &FsMetadata) {
+        self.size = metadata.len;
+        self.updated_at = metadata.modified;
+        self.disk_size = metadata.allocated.unwrap_or(metadata.len);
+        self.newest_mtime = metadata.modified;
+        self.oldest_mtime = metadata.modified;
+        self.created_at = metadata.created;
+        self.mode = metadata.mode;
+        self.world_writable = metadata.mode.is_some_and(|mode| mode & 0o002 != 0);
+        self.setuid = metadata.mode.is_some_and(|mode| mode & 0o4000 != 0);
+        self.executable_count = metadata.mode.is_some_and(|mode| mode & 0o111 != 0) as u64;
+        #[cfg(feature = "unix-metadata")]
+        {
+            self.owner = metadata
+                .uid
+                .zip(metadata.gid)
+                .map(|(uid, gid)| Owner::resolve(uid, gid));
+        }
+    }
+}
+
+/// 根据子节点的名称与摘要计算目录的摘要，子节点按名称排序后依次参与计算
+/// 任一子节点没有摘要时无法确定目录的内容，返回空
+#[cfg(feature = "hash")]
+fn digest_children(children: &[TreeNode]) -> Option<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+    let mut sorted: Vec<&TreeNode> = children.iter().collect();
+    sorted.sort_by(|a, b| a.name().cmp(b.name()));
+    let mut hasher = Sha256::new();
+    for child in sorted {
+        hasher.update(child.name().as_bytes());
+        hasher.update([0]);
+        hasher.update(child.summary.digest?);
+    }
+    Some(hasher.finalize().into())
+}
+
+/// 节点之下所有文件的大小中位数，文件数量为偶数时取中间两者的平均值（向下取整），没有文件时为 0
+/// 每次汇总时遍历子树并以选择算法求得，各级目录无需保存其下所有文件的大小
+pub(crate) fn median_file_size<'a, I>(nodes: I) -> u64
+where
+    I: IntoIterator<Item = &'a TreeNode>,
+{
+    let mut sizes = Vec::new();
+    let mut stack: Vec<&TreeNode> = nodes.into_iter().collect();
+    while let Some(node) = stack.pop() {
+        if node.is_dir {
+            stack.extend(node.children.iter().flatten());
+        } else if node.summary.file_count > 0 {
+            sizes.push(node.summary.size);
+        }
+    }
+    let len = sizes.len();
+    if len == 0 {
+        return 0;
+    }
+    let (lower, middle, _) = sizes.select_nth_unstable(len / 2);
+    let middle = *middle;
+    match lower.iter().max() {
+        Some(previous) if len % 2 == 0 => (previous + middle) / 2,
+        _ => middle,
+    }
+}
+
+/// 从各节点记录的最大文件中选出最大的 `top_n` 个，按大小从大到小排列，大小相同时按路径的字典序排列
+/// 借助容量为 `top_n` 的最小堆筛选，最后仅排序一次
+pub(crate) fn largest_files<'a, I>(summaries: I, top_n: usize) -> Vec<(PathBuf, u64)>
+where
+    I: IntoIterator<Item = &'a NodeSummary>,
+{
+    if top_n == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(top_n + 1);
+    for summary in summaries {
+        for (path, size) in &summary.largest_files {
+            heap.push(Reverse((*size, Reverse(path))));
+            if heap.len() > top_n {
+                heap.pop();
+            }
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((size, Reverse(path)))| (path.clone(), size))
+        .collect()
+}
+
+/// 取两个可选时间中较早者，忽略为空的一方
+fn earliest(time: Option<SystemTime>, other: Option<SystemTime>) -> Option<SystemTime> {
+    match (time, other) {
+        (Some(time), Some(other)) => Some(time.min(other)),
+        (time, other) => time.or(other),
     }
 }
 
+/// 按键的顺序序列化 `HashMap`，使导出结果稳定，便于比较与审阅
+#[cfg(feature = "serde")]
+fn serialize_sorted<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: serde::Serialize + Ord,
+    V: serde::Serialize,
+    S: serde::Serializer,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
 // --------------------- 单元测试 ---------------------
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fs::MemoryFs;
     use crate::tree::node::TreeNode;
+    use crate::tree::root::ProjectTree;
 
     #[test]
     fn test_display() {
@@ -141,6 +1165,7 @@ mod tests {
         assert_eq!(ns.size, 0);
         assert_eq!(ns.count, 0);
         assert_eq!(ns.updated_at, None);
+        assert_eq!(ns.summarized_at, None);
         assert_eq!(ns.suffixes.len(), 0);
     }
 
@@ -153,6 +1178,644 @@ mod tests {
         println!("{}", node);
     }
 
+    #[test]
+    fn test_suffixes() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        // 多个目录中重复出现的后缀仅出现一次
+        assert_eq!(node.summary.suffixes, vec!["js", "rs", "txt"]);
+        let file = node.find("./tests/examples/tree/summary/test.rs").unwrap();
+        assert_eq!(file.summary.suffixes, vec!["rs"]);
+    }
+
+    #[test]
+    fn test_by_extension() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        let by_extension = &node.summary.by_extension;
+        assert_eq!(by_extension.len(), 3);
+        assert_eq!(by_extension["txt"].files, 7);
+        assert_eq!(by_extension["rs"].files, 3);
+        assert_eq!(by_extension["js"].files, 1);
+        // 各后缀之和与目录总计一致
+        let total = by_extension
+            .values()
+            .fold(ExtStats::default(), |mut total, stats| {
+                total.merge(stats);
+                total
+            });
+        assert_eq!(total.size, node.summary.size);
+        assert_eq!(total.lines, node.summary.count);
+        // 文件节点不保存按后缀划分的统计信息
+        let file = node.find("./tests/examples/tree/summary/test.js").unwrap();
+        assert!(file.summary.by_extension.is_empty());
+        assert_eq!(file.summary.suffixes, vec!["js"]);
+    }
+
+    #[test]
+    fn test_extension_diversity() {
+        let fs = MemoryFs::new()
+            .add_file("project/lib/a.rs", "")
+            .add_file("project/lib/b.rs", "")
+            .add_file("project/mixed/a.rs", "")
+            .add_file("project/mixed/b.rs", "")
+            .add_file("project/mixed/c.sql", "")
+            .add_file("project/mixed/d.css", "")
+            .add_file("project/mixed/Makefile", "");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let lib = &root.find("project/lib").unwrap().summary;
+        assert_eq!(
+            (lib.distinct_extensions(), lib.extension_diversity()),
+            (1, 0.0)
+        );
+        let mixed = &root.find("project/mixed").unwrap().summary;
+        assert_eq!(mixed.distinct_extensions(), 3);
+        // 占比为 1/2、1/4、1/4
+        assert_eq!(mixed.extension_diversity(), 1.5);
+        assert_eq!(NodeSummary::new().extension_diversity(), 0.0);
+    }
+
+    #[test]
+    fn test_by_mime() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        let file = node.find("./tests/examples/tree/summary/test.rs").unwrap();
+        assert_eq!(file.summary.mime.as_deref(), Some("text/x-rust"));
+        assert_eq!(node.summary.mime, None);
+        assert_eq!(node.summary.by_mime["text/x-rust"], 3);
+        assert_eq!(node.summary.by_mime["text/javascript"], 1);
+    }
+
+    #[test]
+    fn test_hidden_stats() {
+        let fs = MemoryFs::new()
+            .add_file("project/.env", "KEY=1")
+            .add_file("project/.git/HEAD", "ref")
+            .add_file("project/.git/.keep", "")
+            .add_file("project/.git/objects/ab", "1234")
+            .add_file("project/src/.hidden.rs", "12")
+            .add_file("project/src/lib.rs", "123456");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let git = root.find("project/.git").unwrap();
+        assert_eq!((git.summary.hidden_count, git.summary.hidden_size), (1, 0));
+        let src = root.find("project/src").unwrap();
+        assert_eq!((src.summary.hidden_count, src.summary.hidden_size), (1, 2));
+        // .env、.git、.git/.keep 与 src/.hidden.rs
+        assert_eq!(root.summary.hidden_count, 4);
+        assert_eq!(root.summary.hidden_size, 5 + 7 + 2);
+    }
+
+    #[test]
+    fn test_function_count() {
+        let fs = MemoryFs::new()
+            .add_file("project/lib.rs", "pub fn a() {}\nfn b() {}\n")
+            .add_file(
+                "project/py/app.py",
+                "class A:\n    def run(self):\n        pass\n",
+            )
+            .add_file("project/py/main.c", "int main() { return 0; }\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.find("project/lib.rs").unwrap().summary.functions, 2);
+        assert_eq!(root.find("project/py").unwrap().summary.functions, 1);
+        assert_eq!(root.summary.functions, 3);
+    }
+
+    #[cfg(feature = "manifest")]
+    #[test]
+    fn test_manifests() {
+        use crate::tree::manifest::ManifestKind;
+        let fs = MemoryFs::new()
+            .add_file("project/Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n")
+            .add_file(
+                "project/crates/core/Cargo.toml",
+                "[package]\nname = \"core\"\n[dependencies]\nserde = \"1\"\n[dev-dependencies]\ntempfile = \"3\"\n",
+            )
+            .add_file(
+                "project/web/package.json",
+                r#"{ "dependencies": { "react": "^19", "vite": "^6" } }"#,
+            )
+            .add_file("project/web/Cargo.toml.bak", "[dependencies]\nserde = \"1\"\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let core = root.find("project/crates/core/Cargo.toml").unwrap();
+        assert_eq!(
+            core.summary.manifest.as_ref().unwrap().name.as_deref(),
+            Some("core")
+        );
+        let workspace = root.find("project/Cargo.toml").unwrap();
+        assert_eq!(
+            workspace
+                .summary
+                .manifest
+                .as_ref()
+                .unwrap()
+                .workspace_members,
+            vec!["crates/*"]
+        );
+        assert_eq!(root.find("project/crates").unwrap().summary.dependencies, 1);
+        assert_eq!(root.summary.by_manifest[&ManifestKind::Cargo], 2);
+        assert_eq!(root.summary.by_manifest[&ManifestKind::Npm], 1);
+        assert_eq!(
+            (root.summary.dependencies, root.summary.dev_dependencies),
+            (3, 1)
+        );
+        assert_eq!(root.summary.manifest, None);
+    }
+
+    #[test]
+    fn test_code_origin() {
+        let minified = "var a=1;".repeat(1000);
+        let fs = MemoryFs::new()
+            .add_file("project/src/main.go", "package main\n")
+            .add_file(
+                "project/src/api.pb.go",
+                "// Code generated. DO NOT EDIT.\npackage api\n",
+            )
+            .add_file("project/dist/app.js", minified.as_str())
+            .add_file("project/vendor/lib/lib.go", "package lib\n")
+            .add_file("project/vendor/lib/lib.pb.go", "package lib\n");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs.clone());
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let origin = |path: &str| root.find(path).unwrap().summary.origin;
+        assert_eq!(origin("project/src/api.pb.go"), Some(CodeOrigin::Generated));
+        assert_eq!(origin("project/src/main.go"), None);
+        assert_eq!(origin("project/src"), None);
+        assert_eq!(origin("project/dist"), Some(CodeOrigin::Generated));
+        assert_eq!(origin("project/vendor"), Some(CodeOrigin::Vendored));
+        // 第三方目录下的所有节点均为第三方代码
+        assert_eq!(origin("project/vendor/lib"), Some(CodeOrigin::Vendored));
+        assert_eq!(
+            origin("project/vendor/lib/lib.go"),
+            Some(CodeOrigin::Vendored)
+        );
+        let generated = root.find("project/vendor/lib/lib.pb.go").unwrap();
+        assert_eq!(generated.summary.origin, Some(CodeOrigin::Vendored));
+        assert_eq!(generated.summary.generated.files, 0);
+        assert_eq!(generated.summary.vendored.files, 1);
+        // 第三方目录下的生成文件仅计入第三方代码
+        assert_eq!(root.summary.generated.files, 2);
+        assert_eq!(root.summary.generated.lines, 3);
+        assert_eq!(
+            (root.summary.vendored.files, root.summary.vendored.lines),
+            (2, 2)
+        );
+        assert_eq!(root.summary.origin, None);
+
+        // 流式分析时，第三方目录的子目录先于其生成记录，同样标记为第三方代码
+        let mut records = Vec::new();
+        ProjectTree::new("test", "project", None)
+            .with_fs(fs)
+            .stream(|record| records.push(record))
+            .expect("stream failed");
+        let lib = records
+            .iter()
+            .find(|record| record.path == "project/vendor/lib")
+            .unwrap();
+        assert_eq!(lib.summary.origin, Some(CodeOrigin::Vendored));
+        assert_eq!(lib.summary.vendored.files, 2);
+    }
+
+    #[test]
+    fn test_licenses() {
+        let mit = "Permission is hereby granted, free of charge, to any person obtaining a copy ...\n\
+            The above copyright notice and this permission notice shall be included in all\n\
+            copies or substantial portions of the Software.";
+        let fs = MemoryFs::new()
+            .add_file("project/LICENSE-MIT", mit)
+            .add_file(
+                "project/LICENSE-APACHE",
+                "Apache License\nVersion 2.0, January 2004",
+            )
+            .add_file("project/vendor/lib/COPYING", "Copyright (c) Someone")
+            .add_file("project/src/license.rs", "// MIT");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let mit = root.find("project/LICENSE-MIT").unwrap();
+        assert_eq!(mit.summary.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            root.find("project/src/license.rs").unwrap().summary.license,
+            None
+        );
+        let licenses: Vec<_> = root
+            .summary
+            .licenses
+            .iter()
+            .map(|(path, license)| (path.to_str().unwrap(), license.as_str()))
+            .collect();
+        assert_eq!(
+            licenses,
+            vec![
+                ("project/LICENSE-APACHE", "Apache-2.0"),
+                ("project/LICENSE-MIT", "MIT"),
+                ("project/vendor/lib/COPYING", "NOASSERTION"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scripts() {
+        let fs = MemoryFs::new()
+            .add_file("bin/setup", "#!/bin/sh\necho setup\n")
+            .add_file("bin/deploy", "#!/usr/bin/env bash\necho deploy\n")
+            .add_file("bin/check.sh", "#!/bin/bash\necho check\n")
+            .add_file("bin/serve", "#!/usr/bin/env node\nconsole.log(1)\n")
+            .add_file("bin/README", "no shebang\n");
+        let mut tree = ProjectTree::new("test", "bin", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let setup = root.find("bin/setup").unwrap();
+        assert_eq!(setup.summary.language, Some(Language::Shell));
+        assert_eq!(setup.summary.interpreter.as_deref(), Some("sh"));
+        assert_eq!(root.find("bin/README").unwrap().summary.interpreter, None);
+        assert_eq!(root.summary.by_language[&Language::Shell].files, 3);
+        assert_eq!(root.summary.by_language[&Language::JavaScript].files, 1);
+        assert_eq!(root.summary.by_interpreter["bash"], 2);
+        assert_eq!(root.summary.by_interpreter["sh"], 1);
+        assert_eq!(root.summary.by_interpreter["node"], 1);
+    }
+
+    #[test]
+    fn test_images() {
+        let fs = MemoryFs::new()
+            .add_file("assets/a.gif", b"GIF89a\x20\x00\x10\x00".to_vec())
+            .add_file("assets/icons/b.gif", b"GIF87a\x02\x00\x03\x00".to_vec())
+            // 后缀错误的 PNG 同样可以识别
+            .add_file(
+                "assets/icons/c.dat",
+                b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x0a\x00\x00\x00\x0a".to_vec(),
+            )
+            .add_file("assets/readme.txt", "not an image");
+        let mut tree = ProjectTree::new("test", "assets", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let image = root.find("assets/a.gif").unwrap().summary.image.unwrap();
+        assert_eq!((image.width, image.height), (32, 16));
+        assert_eq!(root.find("assets/readme.txt").unwrap().summary.image, None);
+        assert_eq!(root.summary.image, None);
+        assert_eq!(root.summary.pixels, 512 + 6 + 100);
+        assert_eq!(root.summary.by_image_format[&ImageFormat::Gif], 2);
+        assert_eq!(root.summary.by_image_format[&ImageFormat::Png], 1);
+    }
+
+    #[test]
+    fn test_file_size_stats() {
+        let fs = MemoryFs::new()
+            .add_file("project/a", "1")
+            .add_file("project/b", "22")
+            .add_file("project/sub/c", "4444")
+            .add_file("project/sub/d", "1".repeat(93));
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.summary.file_count, 4);
+        assert_eq!(root.summary.avg_file_size, 25);
+        assert_eq!(root.summary.median_file_size, 3);
+        let sub = root.find("project/sub").unwrap();
+        assert_eq!(
+            (sub.summary.file_count, sub.summary.median_file_size),
+            (2, 48)
+        );
+        let file = root.find("project/a").unwrap();
+        assert_eq!(
+            (file.summary.file_count, file.summary.avg_file_size),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn test_dir_count() {
+        let fs = MemoryFs::new()
+            .add_file("project/a", "1")
+            .add_file("project/src/b", "2")
+            .add_file("project/src/tree/c", "3")
+            .add_dir("project/docs");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!((root.summary.file_count, root.summary.dir_count), (3, 3));
+        let src = root.find("project/src").unwrap();
+        assert_eq!((src.summary.file_count, src.summary.dir_count), (2, 1));
+        let file = root.find("project/a").unwrap();
+        assert_eq!((file.summary.file_count, file.summary.dir_count), (1, 0));
+    }
+
+    #[test]
+    fn test_direct_stats() {
+        let fs = MemoryFs::new()
+            .add_file("project/a.txt", "1\n2\n")
+            .add_file("project/b.txt", "3\n")
+            .add_file("project/src/c.txt", "4\n5\n6\n")
+            .add_file("project/src/tree/d.txt", "7\n")
+            .add_dir("project/docs");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let direct = root.summary.direct;
+        assert_eq!((direct.files, direct.size, direct.lines), (2, 6, 3));
+        assert_eq!(root.summary.direct_dir_count, 2);
+        assert_eq!((root.summary.file_count, root.summary.dir_count), (4, 3));
+        let src = root.find("project/src").unwrap();
+        assert_eq!((src.summary.direct.files, src.summary.direct.lines), (1, 3));
+        assert_eq!(src.summary.direct_dir_count, 1);
+        let file = root.find("project/a.txt").unwrap();
+        assert_eq!(file.summary.direct, ExtStats::default());
+    }
+
+    #[test]
+    fn test_size_histogram() {
+        let fs = MemoryFs::new()
+            .add_file("project/empty", "")
+            .add_file("project/small", "1".repeat(1023))
+            .add_file("project/sub/edge", "1".repeat(1024))
+            .add_file("project/sub/large", "1".repeat(200 << 10));
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.summary.size_histogram.counts, [2, 1, 0, 1, 0, 0, 0]);
+        let sub = root.find("project/sub").unwrap();
+        assert_eq!(sub.summary.size_histogram.counts, [0, 1, 0, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_depth_stats() {
+        let fs = MemoryFs::new()
+            .add_file("project/a", "")
+            .add_file("project/x/b", "")
+            .add_file("project/x/y/z/d", "")
+            .add_file("project/x/y/z/c", "");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.summary.max_depth, 4);
+        assert_eq!(root.summary.avg_depth, 2.75);
+        assert_eq!(
+            root.summary.deepest_path,
+            Some(PathBuf::from("project/x/y/z/c"))
+        );
+        let x = root.find("project/x").unwrap();
+        assert_eq!((x.summary.max_depth, x.summary.avg_depth), (3, 7.0 / 3.0));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_internal_fields() {
+        let fs = MemoryFs::new()
+            .add_file("project/a", "")
+            .add_file("project/x/b", "")
+            .add_file("project/x/y/c", "");
+        let config = crate::tree::config::ProjectConfig::new().with_track_top_n(2);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_mut().unwrap();
+        // 汇总所需的内部状态不参与序列化
+        let value = serde_json::to_value(&root.summary).unwrap();
+        assert!(value.get("depth_sum").is_none());
+        assert!(value.get("top_n").is_none());
+        // 反序列化后重新汇总，平均深度不受影响
+        for child in root.children.iter_mut().flatten() {
+            let json = serde_json::to_string(&child.summary).unwrap();
+            child.summary = serde_json::from_str(&json).unwrap();
+        }
+        let summary = NodeSummary::collect(root);
+        assert_eq!(summary.avg_depth, root.summary.avg_depth);
+        assert_eq!(summary.max_depth, 3);
+    }
+
+    #[test]
+    fn test_empty_dirs() {
+        let fs = MemoryFs::new()
+            .add_file("project/a", "")
+            .add_dir("project/outer/inner");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        // 仅包含空目录的目录本身不为空
+        let outer = root.find("project/outer").unwrap();
+        assert!(!outer.summary.is_empty);
+        assert!(root.find("project/outer/inner").unwrap().summary.is_empty);
+        assert_eq!(outer.summary.empty_dir_count, 1);
+        assert_eq!(root.summary.empty_dir_count, 1);
+        assert!(!root.find("project/a").unwrap().summary.is_empty);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let fs = MemoryFs::new()
+            .add_file("project/unix.txt", "a\nb\n")
+            .add_file("project/windows.txt", "a\r\nb\r\n")
+            .add_file("project/sub/mixed.txt", "a\r\nb\n")
+            .add_file("project/sub/single.txt", "a");
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let mixed = root.find("project/sub/mixed.txt").unwrap();
+        assert_eq!(mixed.summary.line_ending, Some(LineEnding::Mixed));
+        assert_eq!(
+            root.find("project/sub/single.txt")
+                .unwrap()
+                .summary
+                .line_ending,
+            None
+        );
+        let expected = LineEndingStats {
+            lf: 1,
+            crlf: 1,
+            mixed: 1,
+        };
+        assert_eq!(root.summary.line_endings, expected);
+    }
+
+    #[test]
+    fn test_max_line_length() {
+        let fs = MemoryFs::new()
+            .add_file("project/short.rs", "fn a() {}\n")
+            .add_file(
+                "project/min/app.js",
+                format!("{}\nshort\n", "x".repeat(500)),
+            );
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(
+            root.find("project/short.rs")
+                .unwrap()
+                .summary
+                .max_line_length,
+            9
+        );
+        assert_eq!(
+            root.find("project/min").unwrap().summary.max_line_length,
+            500
+        );
+        assert_eq!(root.summary.max_line_length, 500);
+    }
+
+    #[test]
+    fn test_line_stats() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        // 各类行数之和与总行数一致
+        assert_eq!(node.summary.lines.total(), node.summary.count);
+        let mut lines = LineStats::default();
+        for child in node.children.as_ref().unwrap() {
+            lines.merge(&child.summary.lines);
+        }
+        assert_eq!(lines, node.summary.lines);
+    }
+
+    #[test]
+    fn test_large_file() {
+        use crate::tree::config::ProjectConfig;
+        use crate::tree::node::count::estimate_tokens;
+        // 超出开头保留部分的内容逐块读取统计，多字节字符与行跨越块的边界
+        let contents = "// 你好，世界 TODO\nlet a = 1;\n".repeat(40_000);
+        let fs = MemoryFs::new().add_file("project/large.rs", contents.as_str());
+        let config = ProjectConfig::new()
+            .with_count_words(true)
+            .with_count_tokens(true)
+            .add_markers(["TODO"]);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs);
+        let stats = tree.analyze().expect("analyze failed");
+        assert_eq!(stats.bytes_read, contents.len() as u64);
+        let summary = &tree.find("large.rs").unwrap().summary;
+        assert_eq!(summary.count, 80_000);
+        assert_eq!(
+            (summary.lines.code, summary.lines.comments),
+            (40_000, 40_000)
+        );
+        assert_eq!(summary.encoding, Some(Encoding::Utf8));
+        assert!(!summary.lossy);
+        assert_eq!(summary.chars, contents.chars().count() as u64);
+        assert_eq!(summary.tokens, estimate_tokens(&contents));
+        assert_eq!(summary.markers["TODO"], 40_000);
+        #[cfg(feature = "hash")]
+        {
+            use sha2::{Digest, Sha256};
+            let digest: [u8; 32] = Sha256::digest(contents.as_bytes()).into();
+            assert_eq!(summary.digest, Some(digest));
+        }
+    }
+
+    #[test]
+    fn test_largest_file() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        let (path, size) = node.summary.largest_file.clone().unwrap();
+        // 最大文件的大小不小于其余所有文件
+        let mut stack = vec![node];
+        while let Some(current) = stack.pop() {
+            match &current.children {
+                Some(children) => stack.extend(children),
+                None => assert!(current.summary.size <= size),
+            }
+        }
+        let file = node.find(path.to_str().unwrap()).unwrap();
+        assert_eq!(file.summary.size, size);
+        assert_eq!(file.summary.largest_file, Some((path, size)));
+    }
+
+    #[test]
+    fn test_largest_files() {
+        use crate::tree::config::ProjectConfig;
+        let fs = MemoryFs::new()
+            .add_file("project/a", "1")
+            .add_file("project/b", "22")
+            .add_file("project/sub/c", "4444")
+            .add_file("project/sub/d", "333")
+            .add_file("project/sub/e", "333");
+        let config = ProjectConfig::new().with_track_top_n(3);
+        let mut tree = ProjectTree::new("test", "project", Some(config)).with_fs(fs.clone());
+        tree.analyze().expect("analyze failed");
+        let root = tree.root.as_ref().unwrap();
+        let files = |node: &TreeNode| {
+            node.summary
+                .largest_files
+                .iter()
+                .map(|(path, size)| (path.to_string_lossy().into_owned(), *size))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            files(root),
+            vec![
+                ("project/sub/c".to_string(), 4),
+                ("project/sub/d".to_string(), 3),
+                ("project/sub/e".to_string(), 3)
+            ]
+        );
+        let sub = root.find("project/sub").unwrap();
+        assert_eq!(files(sub).len(), 3);
+        let a = root.find("project/a").unwrap();
+        assert_eq!(files(a), vec![("project/a".to_string(), 1)]);
+        // 未设置时不记录
+        let mut tree = ProjectTree::new("test", "project", None).with_fs(fs);
+        tree.analyze().expect("analyze failed");
+        assert!(tree.root.as_ref().unwrap().summary.largest_files.is_empty());
+
+        // 跨多个节点筛选时，大小相同者按路径排列，且结果与节点的顺序无关
+        let summary = |files: &[(&str, u64)]| NodeSummary {
+            largest_files: files
+                .iter()
+                .map(|(path, size)| (PathBuf::from(path), *size))
+                .collect(),
+            ..Default::default()
+        };
+        let a = summary(&[("x", 5), ("b", 2)]);
+        let b = summary(&[("a", 2), ("y", 1)]);
+        let expected = vec![(PathBuf::from("x"), 5), (PathBuf::from("a"), 2)];
+        assert_eq!(largest_files([&a, &b], 2), expected);
+        assert_eq!(largest_files([&b, &a], 2), expected);
+        assert!(largest_files([&a, &b], 0).is_empty());
+    }
+
+    #[test]
+    fn test_mtime() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        let newest = node.summary.newest_mtime.unwrap();
+        let oldest = node.summary.oldest_mtime.unwrap();
+        assert!(oldest <= newest);
+        // 目录的修改时间为其下最晚的修改时间，而非总结的时间
+        assert_eq!(node.summary.updated_at, Some(newest));
+        assert!(node.summary.summarized_at.unwrap() >= newest);
+        // 每个文件的修改时间均位于目录的区间内
+        let mut stack = vec![node];
+        while let Some(current) = stack.pop() {
+            match &current.children {
+                Some(children) => stack.extend(children),
+                None => {
+                    let modified = current.summary.newest_mtime.unwrap();
+                    assert_eq!(current.summary.oldest_mtime, Some(modified));
+                    assert_eq!(current.summary.updated_at, Some(modified));
+                    assert!(oldest <= modified && modified <= newest);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_created_at() {
+        let tree = ProjectTree::try_plant("test", "./tests/examples/tree", None).unwrap();
+        let node = tree.root.as_ref().unwrap();
+        let file = node.find("./tests/examples/tree/summary/test.rs").unwrap();
+        let created = std::fs::metadata("./tests/examples/tree/summary/test.rs")
+            .and_then(|metadata| metadata.created())
+            .ok();
+        assert_eq!(file.summary.created_at, created);
+        // 平台支持创建时间时，目录的创建时间不晚于其下任一文件
+        if let Some(created) = created {
+            assert!(node.summary.created_at.unwrap() <= created);
+        }
+    }
+
     #[test]
     fn test_summary_with_dir() {
         // 创建节点
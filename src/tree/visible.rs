@@ -57,7 +57,10 @@ impl ProjectTreeVisible for ProjectTree {
 
         // 打印当前节点信息
         let node_type = if node.is_dir { "DIR" } else { "FILE" };
-        println!("{}- {} [{}]", indent, node.path, node_type);
+        match &node.marker {
+            Some(marker) => println!("{}- {} [{}] ({})", indent, node.path, node_type, marker),
+            None => println!("{}- {} [{}]", indent, node.path, node_type),
+        }
 
         // 递归打印子节点
         if let Some(ref children) = node.children {
@@ -0,0 +1,134 @@
+//! # 警告
+//! 构建与总结过程中遇到的可恢复问题（如无权限的目录、损坏的符号链接、超出大小上限的文件）不会中断操作，
+//! 而是连同路径与原因记录为警告，保存在项目树的 `warnings` 中，由调用方决定如何展示。
+use std::fmt::Display;
+use std::io::ErrorKind;
+
+/// 警告类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum WarningKind {
+    /// 没有访问权限
+    PermissionDenied,
+    /// 符号链接指向的目标不存在
+    BrokenSymlink,
+    /// 无法读取的路径，如读取过程中出错的文件
+    Unreadable,
+    /// 文件超出配置的大小上限，未读取其内容
+    SizeCapExceeded,
+    /// 沙箱模式下指向项目根路径之外的节点
+    OutsideRoot,
+    /// 其他问题
+    Other,
+}
+
+impl WarningKind {
+    /// 根据 IO 错误推断警告类型
+    pub(crate) fn of(error: &std::io::Error) -> Self {
+        match error.kind() {
+            ErrorKind::PermissionDenied => WarningKind::PermissionDenied,
+            _ => WarningKind::Unreadable,
+        }
+    }
+}
+
+impl Display for WarningKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WarningKind::PermissionDenied => "permission denied",
+            WarningKind::BrokenSymlink => "broken symlink",
+            WarningKind::Unreadable => "unreadable",
+            WarningKind::SizeCapExceeded => "size cap exceeded",
+            WarningKind::OutsideRoot => "outside root",
+            WarningKind::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 警告
+/// - path：出错的路径
+/// - kind：警告类型
+/// - message：具体原因
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct Warning {
+    /// 出错的路径
+    pub path: String,
+    /// 警告类型
+    pub kind: WarningKind,
+    /// 具体原因
+    pub message: String,
+}
+
+/// 构建警告，`Warning` 的旧名称
+pub type BuildWarning = Warning;
+
+impl Warning {
+    /// 创建警告，类型为 `WarningKind::Other`，可通过 `with_kind` 指定
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use arui_core::tree::warning::{Warning, WarningKind};
+    ///
+    /// let warning = Warning::new("./not_exist", "No such file or directory");
+    /// assert_eq!(warning.to_string(), "./not_exist: No such file or directory");
+    /// let warning = warning.with_kind(WarningKind::BrokenSymlink);
+    /// assert_eq!(warning.kind, WarningKind::BrokenSymlink);
+    /// ```
+    pub fn new<P, M>(path: P, message: M) -> Self
+    where
+        P: Into<String>,
+        M: Into<String>,
+    {
+        Warning {
+            path: path.into(),
+            kind: WarningKind::Other,
+            message: message.into(),
+        }
+    }
+
+    /// 设置警告类型
+    pub fn with_kind(mut self, kind: WarningKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// 根据 IO 错误创建警告，并推断其类型
+    pub(crate) fn from_io<P: Into<String>>(path: P, error: &std::io::Error) -> Self {
+        Warning::new(path, error.to_string()).with_kind(WarningKind::of(error))
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_kind() {
+        let denied = std::io::Error::from(ErrorKind::PermissionDenied);
+        assert_eq!(
+            Warning::from_io("/root", &denied).kind,
+            WarningKind::PermissionDenied
+        );
+        let other = std::io::Error::other("read failed");
+        let warning = Warning::from_io("/file", &other);
+        assert_eq!(warning.kind, WarningKind::Unreadable);
+        assert_eq!(warning.to_string(), "/file: read failed");
+        assert_eq!(
+            WarningKind::SizeCapExceeded.to_string(),
+            "size cap exceeded"
+        );
+    }
+}
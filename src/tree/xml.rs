@@ -0,0 +1,113 @@
+//! # XML 导出
+//! 将项目树导出为 XML 目录清单，供只接受 XML 的既有工具使用。
+//! 根元素为 `<project>`，其下为根节点；目录节点为 `<directory>`，文件节点为 `<file>`，
+//! 子节点按项目树中的顺序嵌套，每级缩进两个空格。
+//! 属性值中的特殊字符按 XML 的规则转义，XML 1.0 不允许出现的控制字符替换为 `U+FFFD`。
+use crate::tree::node::TreeNode;
+use std::io::{Result, Write};
+
+/// 将项目树写入 `writer`，包含 XML 声明
+/// - name：项目别名，写入根元素的 `name` 属性
+/// - root：根节点
+///
+/// 每个节点包含以下属性：
+/// - `name`、`path`：节点名称与路径
+/// - `type`：文件类型，如 `file`、`directory`、`fifo`
+/// - `size`、`lines`：大小与文本行数，目录节点为其下所有文件之和
+/// - `files`：目录下的文件数量，仅目录节点包含
+/// - `extension`、`language`：文件后缀与识别到的语言，仅文件节点在可识别时包含
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::tree::node::TreeNode;
+/// use arui_core::tree::xml;
+///
+/// let mut root = TreeNode::new("./project", true);
+/// root.children = Some(vec![TreeNode::new("./project/a&b.rs", false)]);
+/// let mut output = Vec::new();
+/// xml::write("test", &root, &mut output).unwrap();
+/// let output = String::from_utf8(output).unwrap();
+/// assert!(output.contains("<project name=\"test\">"));
+/// assert!(output.contains("<file name=\"a&amp;b.rs\" path=\"./project/a&amp;b.rs\" type=\"file\""));
+/// ```
+pub fn write<W: Write>(name: &str, root: &TreeNode, mut writer: W) -> Result<()> {
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<project name=\"{}\">", escape(name))?;
+    write_node(root, 1, &mut writer)?;
+    writeln!(writer, "</project>")
+}
+
+/// 递归写入节点及其子节点
+/// - depth：缩进层级
+fn write_node<W: Write>(node: &TreeNode, depth: usize, writer: &mut W) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    let element = if node.is_dir { "directory" } else { "file" };
+    let summary = &node.summary;
+    write!(
+        writer,
+        "{}<{} name=\"{}\" path=\"{}\" type=\"{}\" size=\"{}\" lines=\"{}\"",
+        indent,
+        element,
+        escape(node.name()),
+        escape(&node.path),
+        node.kind,
+        summary.size,
+        summary.count
+    )?;
+    if node.is_dir {
+        write!(writer, " files=\"{}\"", summary.file_count)?;
+    } else {
+        if let Some(extension) = summary.suffixes.first() {
+            write!(writer, " extension=\"{}\"", escape(extension))?;
+        }
+        if let Some(language) = summary.language {
+            write!(writer, " language=\"{}\"", escape(language.name()))?;
+        }
+    }
+    match &node.children {
+        Some(children) if !children.is_empty() => {
+            writeln!(writer, ">")?;
+            for child in children {
+                write_node(child, depth + 1, writer)?;
+            }
+            writeln!(writer, "{}</{}>", indent, element)
+        }
+        _ => writeln!(writer, "/>"),
+    }
+}
+
+/// 转义属性值中的特殊字符
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            char if char.is_control() && char < ' ' => escaped.push('\u{fffd}'),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+// --------------------- 单元测试 ---------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("src/main.rs"), "src/main.rs");
+        assert_eq!(escape("<a & 'b'>"), "&lt;a &amp; &apos;b&apos;&gt;");
+        assert_eq!(escape("say \"hi\"\n"), "say &quot;hi&quot;&#10;");
+        assert_eq!(escape("bell\u{7}"), "bell\u{fffd}");
+    }
+}
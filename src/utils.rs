@@ -2,8 +2,11 @@
 //! 提供一些通用的工具方法，包括:
 //! - 生成随机 id
 //! - 检查路径是否合法
+//! - 简化 Windows 扩展长度路径
+//! - 路径的 Unicode 规范化
 use crate::errors::IOError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
 use uuid::Uuid;
 
 /// 生成随机 id
@@ -23,6 +26,7 @@ pub fn generate_id() -> String {
 /// 检查路径是否合法
 /// 接受一个路径参数，可以是绝对路径也可以是一个相对路径
 /// 若路径不存在则抛出错误 InvalidPath，存在则返回当前路径的绝对路径
+/// Windows 上的绝对路径会尽量去除 `\\?\` 前缀，见 `simplify_path`
 ///
 /// # Examples
 ///
@@ -44,7 +48,78 @@ where
     if !path.exists() {
         Err(IOError::InvalidPath(s))
     } else {
-        Ok(path.canonicalize()?.to_string_lossy().to_string())
+        Ok(simplify_path(path.canonicalize()?)
+            .to_string_lossy()
+            .to_string())
+    }
+}
+
+/// Windows 下普通路径的最大长度，超出时必须保留扩展长度前缀
+const MAX_PATH: usize = 260;
+
+/// 简化 Windows 扩展长度路径
+/// Windows 上 `canonicalize` 返回的路径带有 `\\?\` 前缀（如 `\\?\C:\project`、`\\?\UNC\server\share`），
+/// 大多数程序无法识别，展示时也不直观。该函数在不改变含义的前提下将其转换为普通形式：
+/// - `\\?\C:\project` 转换为 `C:\project`
+/// - `\\?\UNC\server\share\project` 转换为 `\\server\share\project`
+///
+/// 转换后超出 `MAX_PATH` 限制，或包含在普通形式下含义不同的内容（如 `/`、`.`、`..`）时保留原样，其余路径原样返回
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::utils::simplify_path;
+/// use std::path::Path;
+///
+/// assert_eq!(simplify_path(r"\\?\C:\project"), Path::new(r"C:\project"));
+/// assert_eq!(simplify_path(r"\\?\UNC\server\share"), Path::new(r"\\server\share"));
+/// assert_eq!(simplify_path("./src"), Path::new("./src"));
+/// ```
+pub fn simplify_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    let simplified = if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = text.strip_prefix(r"\\?\") {
+        // 仅转换盘符路径，如 `C:\`，其余设备路径保持原样
+        let bytes = rest.as_bytes();
+        if bytes.len() < 3 || !bytes[0].is_ascii_alphabetic() || &bytes[1..3] != b":\\" {
+            return path.to_path_buf();
+        }
+        rest.to_string()
+    } else {
+        return path.to_path_buf();
+    };
+    let literal = simplified.contains('/')
+        || simplified
+            .split('\\')
+            .skip(1)
+            .any(|part| part == "." || part == "..");
+    if simplified.len() >= MAX_PATH || literal {
+        return path.to_path_buf();
+    }
+    PathBuf::from(simplified)
+}
+
+/// 将路径转换为 Unicode NFC 形式
+/// macOS 等系统上的文件名可能以 NFD 形式存储（如 `e` 与组合重音符号分开存储），
+/// 与用户输入的 NFC 形式看起来相同却无法直接比较。比较路径前应先通过该函数转换，已是 NFC 形式或非 UTF-8 的路径原样返回
+///
+/// # Examples
+///
+/// ```rust
+/// use arui_core::utils::normalize_unicode;
+/// use std::path::Path;
+///
+/// assert_eq!(normalize_unicode("cafe\u{301}"), Path::new("caf\u{e9}"));
+/// ```
+pub fn normalize_unicode<P: AsRef<Path>>(path: P) -> PathBuf {
+    let path = path.as_ref();
+    match path.to_str() {
+        Some(text) if !is_nfc(text) => PathBuf::from(text.nfc().collect::<String>()),
+        _ => path.to_path_buf(),
     }
 }
 
@@ -68,8 +143,40 @@ mod tests {
             .to_string_lossy()
             .to_string();
         // current_path 拼接上 src 应该和 valid_path 相同
-        assert_eq!(current_path + "/src", valid_path.unwrap());
+        assert_eq!(
+            Path::new(&current_path).join("src"),
+            Path::new(&valid_path.unwrap())
+        );
         let invalid_path = check_path("/not_exist".to_string());
         assert!(invalid_path.is_err());
     }
+
+    #[test]
+    fn test_normalize_unicode() {
+        let nfd = "/photos/Cafe\u{301}/re\u{301}sume\u{301}.txt";
+        let nfc = "/photos/Caf\u{e9}/r\u{e9}sum\u{e9}.txt";
+        assert_eq!(normalize_unicode(nfd), Path::new(nfc));
+        assert_eq!(normalize_unicode(nfc), Path::new(nfc));
+        assert!(normalize_unicode(nfd).starts_with(normalize_unicode("/photos/Caf\u{e9}")));
+    }
+
+    #[test]
+    fn test_simplify_path() {
+        assert_eq!(simplify_path(r"\\?\D:\a\b"), Path::new(r"D:\a\b"));
+        assert_eq!(
+            simplify_path(r"\\?\UNC\server\share\dir"),
+            Path::new(r"\\server\share\dir")
+        );
+        // 非盘符的设备路径与含义不同的路径保持原样
+        assert_eq!(
+            simplify_path(r"\\?\Volume{1234}\dir"),
+            Path::new(r"\\?\Volume{1234}\dir")
+        );
+        assert_eq!(simplify_path(r"\\?\C:\a\..\b"), Path::new(r"\\?\C:\a\..\b"));
+        assert_eq!(simplify_path(r"\\?\C:\a/b"), Path::new(r"\\?\C:\a/b"));
+        // 超出长度限制的路径保持原样
+        let long = format!(r"\\?\C:\{}", "a".repeat(300));
+        assert_eq!(simplify_path(&long), Path::new(&long));
+        assert_eq!(simplify_path("/usr/lib"), Path::new("/usr/lib"));
+    }
 }
@@ -22,6 +22,7 @@ fn test_build_projet_tree() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_plant_project_tree() {
     let name = "test";
     let path = "./tests";